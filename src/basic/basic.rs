@@ -6,11 +6,11 @@ use chashmap::CHashMap;
 
 use crate::declaration::FunctionPath;
 use crate::extension::Indent;
-use crate::inference::TypeResolution;
+use crate::inference::{Instantiation, TypeResolution};
 
 use super::{BasicNode, NodeTarget};
 
-pub type BasicFunctions = CHashMap<(Arc<FunctionPath>, Reversibility), Arc<BasicFunction>>;
+pub type BasicFunctions = CHashMap<(Arc<FunctionPath>, Reversibility, Instantiation), Arc<BasicFunction>>;
 
 #[derive(Debug)]
 pub struct BasicFunction {
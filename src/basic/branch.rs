@@ -63,7 +63,7 @@ pub struct Divergence {
 
 impl Divergence {
 	pub fn truth(discriminant: Value, target: NodeTarget, default: NodeTarget) -> Self {
-		let branches = vec![(Discriminant::item(Item::Truth(true)), target)];
+		let branches = vec![(Discriminant::TRUE, target)];
 		Divergence { discriminant, branches, default }
 	}
 
@@ -90,19 +90,37 @@ impl fmt::Display for Divergence {
 	}
 }
 
+/// The bit pattern of an integer or truth item.
 #[derive(Clone, PartialEq)]
-pub struct Discriminant(pub u64);
+pub struct Discriminant(pub u128);
 
 impl Discriminant {
-	pub fn item(item: Item) -> Self {
-		Discriminant(match item {
+	pub const TRUE: Self = Discriminant(!0);
+
+	pub fn variant(index: usize) -> Self {
+		Discriminant(index as u128)
+	}
+
+	/// Provides none if an arbitrary precision integer exceeds 128 bits.
+	pub fn item(item: Item) -> Option<Self> {
+		Some(Discriminant(match item {
 			Item::Truth(truth) => match truth {
 				false => 0,
-				true => !0,
+				true => Self::TRUE.0,
 			},
-			Item::Unsigned64(value) => value,
+			Item::Unsigned8(value) => value as u128,
+			Item::Unsigned16(value) => value as u128,
+			Item::Unsigned32(value) => value as u128,
+			Item::Unsigned64(value) => value as u128,
+			Item::Unsigned128(value) => value,
+			Item::Signed8(value) => value as u128,
+			Item::Signed16(value) => value as u128,
+			Item::Signed32(value) => value as u128,
+			Item::Signed64(value) => value as u128,
+			Item::Signed128(value) => value as u128,
+			Item::Integer(value) => value.to_i128()? as u128,
 			_ => panic!("Invalid discriminant item"),
-		})
+		}))
 	}
 }
 
@@ -121,3 +139,17 @@ impl fmt::Debug for Discriminant {
 		write!(f, "Discriminant({})", self)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use crate::basic::Integer;
+
+	use super::*;
+
+	#[test]
+	fn test_integer_discriminant() {
+		let integer = Integer::from(i128::min_value());
+		assert_eq!(Discriminant::item(Item::Integer(integer.clone())), Some(Discriminant(1 << 127)));
+		assert_eq!(Discriminant::item(Item::Integer(&integer - &Integer::from(1i128))), None);
+	}
+}
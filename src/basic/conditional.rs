@@ -1,6 +1,8 @@
-use crate::inference::TypeContext;
-use crate::node::{Arithmetic, BinaryOperator, ConditionEnd, ConditionStart,
-	ExpressionKey, FunctionContext, MutationKind};
+use std::sync::Arc;
+
+use crate::inference::{TypeContext, TypeResolution};
+use crate::node::{Arithmetic, Arm, BinaryOperator, BindingVariable, ConditionEnd,
+	ConditionStart, ExpressionKey, FunctionContext, MutationKind, Variable};
 use crate::span::{Span, Spanned};
 
 use super::*;
@@ -55,6 +57,33 @@ pub fn termination(function: &FunctionContext, type_context: &TypeContext, conte
 	(Value::Item(Item::Unit), component)
 }
 
+/// Lowers a conjunction or disjunction that only evaluates the right operand
+/// if the left operand does not determine the result.
+pub fn short_circuit(function: &FunctionContext, type_context: &TypeContext, context: &mut BasicContext,
+                     operator: &BinaryOperator, left: &ExpressionKey, right: &ExpressionKey,
+                     span: Span) -> (Value, Component) {
+	let (left_value, mut left) = basic(function, type_context, context, left);
+	let location = Location::new(context.temporary());
+	let statement = Statement::Binding(location.variable.clone(), Compound::Value(left_value));
+	left = context.push(left, Spanned::new(statement, span));
+
+	let (right_value, mut right) = basic(function, type_context, context, right);
+	let statement = Statement::Mutation(MutationKind::Assign, location.clone(), right_value);
+	right = context.push(right, Spanned::new(statement, span));
+
+	let exit = context.component();
+	let value = Value::Location(location.clone());
+	let divergence = match operator {
+		BinaryOperator::And => Divergence::truth(value, right.entry, exit.entry),
+		BinaryOperator::Or => Divergence::truth(value, exit.entry, right.entry),
+		_ => panic!("Invalid short circuit operator: {:?}", operator),
+	};
+
+	context.divergence(Direction::Advance, &left, divergence, span);
+	context.link(Direction::Advance, &right, &exit, span);
+	(Value::Location(location), Component::new(left.entry, exit.exit))
+}
+
 fn comparison(context: &mut BasicContext, location: Location, span: Span, value: u64) -> (Value, Component) {
 	let (variable, value) = (context.temporary(), Value::Item(Item::Unsigned64(value)));
 	let comparison = Compound::Binary(BinaryOperator::Equality, Value::Location(location), value);
@@ -173,3 +202,153 @@ pub fn conditional(function: &FunctionContext, type_context: &TypeContext, conte
 	let temporary = temporary.map(|temporary| Value::Location(Location::new(temporary)));
 	(temporary.unwrap_or(Value::Item(Item::Unit)), Component::new(entry.entry, exit.exit))
 }
+
+/// Lowers a match into a divergence on the variant of the value. In reversible
+/// functions each arm asserts that the variant is unchanged on exit so that
+/// the arm taken can be recovered by diverging on the variant in reverse.
+pub fn matching(function: &FunctionContext, type_context: &TypeContext, context: &mut BasicContext,
+                value: &ExpressionKey, arms: &[Arm], span: Span) -> (Value, Component) {
+	let (value_span, (matched, mut entry)) = (function[value].span,
+		basic(function, type_context, context, value));
+	let mut location = match matched {
+		Value::Location(location) => location,
+		Value::Item(_) => {
+			let variable = context.temporary();
+			let statement = Statement::Binding(variable.clone(), Compound::Value(matched));
+			entry = context.push(entry, Spanned::new(statement, value_span));
+			Location::new(variable)
+		}
+	};
+
+	let mut resolution = &type_context[value];
+	while let TypeResolution::Reference(_, type_resolution) = resolution {
+		location.projections.push(Projection::Dereference);
+		resolution = type_resolution;
+	}
+
+	let structure = match resolution {
+		TypeResolution::Instance(structure, _) => {
+			let structure = Spanned::new(Arc::new(structure.clone()), value_span);
+			crate::node::structure(context.context, &structure).unwrap()
+		}
+		other => panic!("Match value type: {}, must be an instance", other),
+	};
+
+	let (exit, unreachable) = (context.component(), context.component());
+	let discriminant = Value::Location(location.clone());
+	let (branches, default) = (Vec::new(), unreachable.entry);
+	let mut advance = Divergence { discriminant: discriminant.clone(), branches, default };
+	let (branches, default) = (Vec::new(), unreachable.exit);
+	let mut reverse = Divergence { discriminant: discriminant.clone(), branches, default };
+
+	let mut temporary = None;
+	for arm in arms {
+		let expression_span = function[&arm.expression].span;
+		let (index, _) = structure.variant(&arm.variant.node)
+			.expect("Match arm variant is not defined");
+		let variant = Discriminant::variant(index);
+
+		context.push_frame();
+		let mut component = context.component();
+		for binding in &arm.bindings {
+			let BindingVariable(variable, _) = &binding.node;
+			let field = Value::Location(field(&location, variable));
+			let statement = Statement::Binding(variable.clone(), Compound::Value(field));
+			component = context.push(component, Spanned::new(statement, binding.span));
+		}
+
+		let (value, other) = basic(function, type_context, context, &arm.expression);
+		component = context.join(component, other, expression_span);
+		if value != Value::Item(Item::Unit) {
+			let location = temporary.get_or_insert_with(|| context.temporary()).clone();
+			let statement = Statement::Mutation(MutationKind::Assign, Location::new(location), value);
+			component = context.push(component, Spanned::new(statement, expression_span));
+		}
+
+		arm.bindings.iter().for_each(|binding| {
+			let BindingVariable(variable, _) = &binding.node;
+			context.consume_variable(variable);
+		});
+
+		let other = context.pop_frame();
+		component = context.join(component, other, expression_span);
+
+		// Bindings are moved back into the fields of the value.
+		let mut restore = context.component();
+		for binding in &arm.bindings {
+			let BindingVariable(variable, _) = &binding.node;
+			let field = Value::Location(field(&location, variable));
+			let statement = Statement::Binding(variable.clone(), Compound::Value(field));
+			restore = context.push(restore, Spanned::new(statement, binding.span));
+			context.consume_variable(variable);
+		}
+
+		let restore = context.invert(restore);
+		context.link(Direction::Advance, &component, &restore, expression_span);
+		context.link(Direction::Reverse, &restore, &component, expression_span);
+		let component = Component::new(component.entry, restore.exit);
+		advance.branches.push((variant.clone(), component.entry));
+
+		match context.is_reversible() {
+			false => context.link(Direction::Advance, &component, &exit, expression_span),
+			true => {
+				let (branches, default) = (vec![(variant.clone(), exit.entry)], unreachable.entry);
+				let assertion = Divergence { discriminant: discriminant.clone(), branches, default };
+				context.divergence(Direction::Advance, &component, assertion, expression_span);
+				context.link(Direction::Reverse, &component, &entry, expression_span);
+				reverse.branches.push((variant, component.exit));
+			}
+		}
+	}
+
+	context.divergence(Direction::Advance, &entry, advance, span);
+	if context.is_reversible() {
+		context.divergence(Direction::Reverse, &exit, reverse, span);
+	}
+
+	if let Some(temporary) = &temporary {
+		let compound = Compound::Value(Value::Item(Item::Uninitialised));
+		let statement = Statement::Binding(temporary.clone(), compound);
+		context[&entry.exit].statements.push(Spanned::new(statement, span));
+	}
+
+	let temporary = temporary.map(|temporary| Value::Location(Location::new(temporary)));
+	(temporary.unwrap_or(Value::Item(Item::Unit)), Component::new(entry.entry, exit.exit))
+}
+
+fn field(location: &Location, variable: &Variable) -> Location {
+	let Variable(field, _) = variable;
+	location.clone().push(Projection::Field(field.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::source::{context, function_path};
+
+	const SOURCE: &str = "data Shape:\n\t| Circle: radius: u32\n\t\
+		| Square: width: u32, height: u32\n\t| Empty\n\n\
+		fn size(shape: Shape) -> u32:\n\tmatch shape:\n\t\tCircle(radius): radius\n\t\t\
+		Square(width, height): width * height\n\t\tEmpty: 0\n\n\
+		fn circle() -> Shape:\n\tShape::Circle: radius: 5\n\n\
+		fn square() -> Shape:\n\tShape::Square: width: 2, height: 3\n\n\
+		fn empty() -> Shape:\n\tShape::Empty\n";
+
+	#[test]
+	fn test_matching() {
+		let context = &context(SOURCE);
+		let size = &function_path("size");
+		let cases = [("circle", "5", "crate::Shape::Circle", "radius: 5"),
+			("square", "6", "crate::Shape::Square", "width: 2"), ("empty", "0", "crate::Shape::Empty", "")];
+		for (shape, area, variant, field) in &cases {
+			let shape = crate::evaluation::function(context, &function_path(shape), Vec::new());
+			let shape = shape.unwrap().collapse().unwrap();
+			let item = crate::evaluation::function(context, size, vec![shape.clone()]).unwrap();
+			assert_eq!(item.collapse().unwrap().to_string(), *area);
+
+			// Reversing the match restores the variant of the value.
+			let cycle = crate::evaluation::cycle(context, size, vec![shape]).unwrap();
+			let cycle = cycle.collapse().unwrap().to_string();
+			assert!(cycle.contains(variant) && cycle.contains(field), "{}", cycle);
+		}
+	}
+}
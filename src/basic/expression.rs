@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use crate::declaration::FunctionPath;
 use crate::inference::{TypeContext, TypeResolution};
-use crate::node::{Ascription, BindingVariable, Expression, ExpressionKey,
+use crate::node::{Ascription, BinaryOperator, BindingVariable, Expression, ExpressionKey,
 	FunctionContext, MutationKind, Parameter, Pattern, UnaryOperator};
 use crate::span::Spanned;
 
@@ -33,7 +33,17 @@ pub fn basic(function: &FunctionContext, type_context: &TypeContext,
 				value = Some(other_value);
 			}
 
-			let value = value.unwrap();
+			// Projected values are copied so that the projected variable is dropped with the frame.
+			let mut value = value.unwrap();
+			if let Value::Location(location) = &value {
+				if !location.projections.is_empty() {
+					let variable = context.temporary();
+					let statement = Statement::Binding(variable.clone(), Compound::Value(value));
+					component = context.push(component, Spanned::new(statement, span));
+					value = Value::Location(Location::new(variable));
+				}
+			}
+
 			context.consume_value(&value);
 			let other = context.pop_frame();
 			(value, context.join(component, other, span))
@@ -64,6 +74,8 @@ pub fn basic(function: &FunctionContext, type_context: &TypeContext,
 				condition_start, condition_end, expression, span),
 		Expression::Conditional(branches) =>
 			super::conditional::conditional(function, type_context, context, branches, span),
+		Expression::Match(value, arms) =>
+			super::conditional::matching(function, type_context, context, value, arms, span),
 		Expression::Mutation(mutation, mutable, expression) => {
 			let (value, component) = basic(function, type_context, context, expression);
 			let (variable, other) = basic(function, type_context, context, mutable);
@@ -135,6 +147,39 @@ pub fn basic(function: &FunctionContext, type_context: &TypeContext,
 			let location = location.push(Projection::Field(field.node.clone()));
 			(Value::Location(location), component)
 		}
+		Expression::Index(expression, index) => {
+			let (value, mut component) = basic(function, type_context, context, expression);
+			let mut location = match value {
+				Value::Location(location) => location,
+				Value::Item(_) => {
+					let variable = context.temporary();
+					let statement = Statement::Binding(variable.clone(), Compound::Value(value));
+					component = context.push(component, Spanned::new(statement, span));
+					Location::new(variable)
+				}
+			};
+
+			let mut resolution = &type_context[expression];
+			while let TypeResolution::Reference(_, type_resolution) = resolution {
+				location.projections.push(Projection::Dereference);
+				resolution = type_resolution;
+			}
+
+			let (value, other) = basic(function, type_context, context, index);
+			component = context.join(component, other, span);
+			let variable = match value {
+				Value::Location(Location { variable, ref projections }) if projections.is_empty() => variable,
+				_ => {
+					let variable = context.temporary();
+					let statement = Statement::Binding(variable.clone(), Compound::Value(value));
+					component = context.push(component, Spanned::new(statement, span));
+					variable
+				}
+			};
+
+			let location = location.push(Projection::Index(variable));
+			(Value::Location(location), component)
+		}
 		Expression::MethodCall(receiver, method, expressions) => {
 			let mut values = Vec::new();
 			let (mut value, mut component) = basic(function, type_context, context, receiver);
@@ -151,10 +196,20 @@ pub fn basic(function: &FunctionContext, type_context: &TypeContext,
 						}
 						receiver = resolution;
 					}
-					_ => panic!("Receiver type resolution must be instance or reference")
+					TypeResolution::Template(template) =>
+						panic!("Template: {}, is not instantiated", template),
+					TypeResolution::Array(_, _) => panic!("Method call cannot be performed on array"),
 				}
 			}.clone();
 
+			if structure.is_string() {
+				assert_eq!(&*method.node, "length");
+				let variable = context.temporary();
+				let statement = Statement::Binding(variable.clone(), Compound::Length(value));
+				let component = context.push(component, Spanned::new(statement, span));
+				return (Value::Location(Location::new(variable)), component);
+			}
+
 			let function_path = method.clone().map(|method|
 				Arc::new(FunctionPath::method(structure, method)));
 			let function_type = crate::node::function_type(context.context, &function_path).unwrap();
@@ -177,7 +232,8 @@ pub fn basic(function: &FunctionContext, type_context: &TypeContext,
 			}
 
 			let variable = context.temporary();
-			let compound = Compound::FunctionCall(function_path, values);
+			let instantiation = type_context.instantiation(expression_key).cloned().unwrap_or_default();
+			let compound = Compound::FunctionCall(function_path, instantiation, values);
 			let statement = Spanned::new(Statement::Binding(variable.clone(), compound), span);
 			(Value::Location(Location::new(variable)), context.push(component, statement))
 		}
@@ -191,12 +247,18 @@ pub fn basic(function: &FunctionContext, type_context: &TypeContext,
 			}
 
 			let variable = context.temporary();
+			let instantiation = type_context.instantiation(expression_key).cloned().unwrap_or_default();
 			let compound = Compound::FunctionCall(function_path.clone()
-				.map(|function_path| Arc::new(function_path)), values);
+				.map(|function_path| Arc::new(function_path)), instantiation, values);
 			let statement = Spanned::new(Statement::Binding(variable.clone(), compound), span);
 			(Value::Location(Location::new(variable)), context.push(component, statement))
 		}
 		Expression::Unary(operator, expression) => {
+			if let (UnaryOperator::Negate, Expression::Integer(integer, _)) =
+				(&operator.node, &function[expression].node) {
+				return (integer_value(type_context, expression_key, *integer, true), context.component());
+			}
+
 			let variable = context.temporary();
 			let (mut value, mut component) = basic(function, type_context, context, expression);
 			if let (UnaryOperator::Reference(_), Value::Item(_)) = (&operator.node, &value) {
@@ -218,6 +280,10 @@ pub fn basic(function: &FunctionContext, type_context: &TypeContext,
 				}
 			}
 		}
+		Expression::Binary(operator, left, right) if !context.is_reversible() &&
+			(operator.node == BinaryOperator::And || operator.node == BinaryOperator::Or) =>
+			super::conditional::short_circuit(function, type_context, context,
+				&operator.node, left, right, span),
 		Expression::Binary(operator, left, right) => {
 			let (left_value, left) = basic(function, type_context, context, left);
 			let (right_value, right) = basic(function, type_context, context, right);
@@ -234,18 +300,60 @@ pub fn basic(function: &FunctionContext, type_context: &TypeContext,
 				.map(|(field, (_, expression))| (field.clone(), expression)), span,
 				&mut |context, expression| basic(function, type_context, context, expression))
 		}
+		Expression::Variant(structure_path, variant, expressions) => {
+			let structure_path = structure_path.clone().map(Arc::new);
+			let structure = crate::node::structure(context.context, &structure_path).unwrap();
+			let (index, _) = structure.variant(&variant.node).unwrap();
+
+			let mut instance = Instance::new(type_context[expression_key].clone());
+			instance.variant = Some((index, variant.node.clone()));
+			super::pattern::fields(context, instance, expressions.iter()
+				.map(|(field, (_, expression))| (field.clone(), expression)), span,
+				&mut |context, expression| basic(function, type_context, context, expression))
+		}
+		Expression::Array(elements) => {
+			let instance = Instance::new(type_context[expression_key].clone());
+			super::pattern::fields(context, instance, elements.iter().enumerate()
+				.map(|(index, expression)| (index.to_string().into(), expression)), span,
+				&mut |context, expression| basic(function, type_context, context, expression))
+		}
 		Expression::Pattern(expression) =>
 			super::pattern::pattern(function, type_context, context, expression, span),
 		Expression::Variable(variable) =>
 			(Value::Location(Location::new(variable.clone())), context.component()),
-		Expression::Integer(integer) => {
-			let resolution = &type_context[expression_key];
-			(Value::Item(resolution.intrinsic()
-				.and_then(|intrinsic| Item::integer(intrinsic, *integer))
-				.unwrap_or_else(|| panic!("Type: {:?}, is not of intrinsic integer", resolution))),
-				context.component())
-		}
+		Expression::Integer(integer, _) =>
+			(integer_value(type_context, expression_key, *integer, false), context.component()),
 		Expression::Truth(truth) => (Value::Item(Item::Truth(*truth)), context.component()),
 		Expression::Item(item) => (Value::Item(item.clone()), context.component()),
 	}
 }
+
+fn integer_value(type_context: &TypeContext, expression_key: &ExpressionKey,
+                 magnitude: u128, is_negative: bool) -> Value {
+	let resolution = &type_context[expression_key];
+	Value::Item(resolution.intrinsic().and_then(|intrinsic| Item::literal(intrinsic, magnitude, is_negative))
+		.unwrap_or_else(|| panic!("Type: {:?}, is not of intrinsic integer", resolution)))
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::source::{context, function_path};
+
+	use super::*;
+
+	#[test]
+	fn test_projected_return() {
+		let context = &context("data Point:\n\tx: u32,\n\ty: u32,\n\n\
+			fn index(x: u32) -> u32:\n\tlet array = [x, x + 1]\n\tarray[1]\n\n\
+			fn field(x: u32) -> u32:\n\tlet point = Point: x: x, y: 2\n\tpoint.x\n");
+		for (function, value) in &[("index", "4"), ("field", "3")] {
+			let path = &function_path(function);
+			let item = crate::evaluation::function(context, path, vec![Item::Unsigned32(3)]).unwrap();
+			assert_eq!(item.collapse().unwrap().to_string(), *value);
+
+			let cycle = crate::evaluation::cycle(context, path, vec![Item::Unsigned32(3)]).unwrap();
+			let cycle = cycle.collapse().unwrap().to_string();
+			assert!(cycle.contains("0: 3"), "{}", cycle);
+		}
+	}
+}
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use crate::context::Context;
 use crate::declaration::FunctionPath;
 use crate::error::Diagnostic;
+use crate::inference::{InferenceError, Instantiation, TypeResolution};
 use crate::node::{ExpressionKey, Parameter, Variable};
 use crate::span::Spanned;
 
@@ -12,15 +13,30 @@ use super::expression::basic;
 /// Lowers a partially evaluated function.
 pub fn function(context: &Context, function_path: &Spanned<Arc<FunctionPath>>,
                 reversibility: Reversibility) -> Result<Arc<BasicFunction>, Diagnostic> {
-	let context_key = (function_path.node.clone(), reversibility);
+	instance(context, function_path, reversibility, &Instantiation::new())
+}
+
+/// Lowers a partially evaluated function with its templates substituted by the instantiation.
+/// All templates in the function signature must be instantiated.
+pub fn instance(context: &Context, function_path: &Spanned<Arc<FunctionPath>>, reversibility: Reversibility,
+                instantiation: &Instantiation) -> Result<Arc<BasicFunction>, Diagnostic> {
+	let context_key = (function_path.node.clone(), reversibility, instantiation.clone());
 	if let Some(function) = context.basic_functions.get(&context_key) {
 		return Ok(function.clone());
 	}
 
-	let mut basic_context = BasicContext::new(context, reversibility);
 	let function = crate::evaluation::partial_function(context, function_path)?;
-	let type_context = crate::inference::function(context, function_path)?;
+	let type_context = crate::inference::instance(context, function_path, instantiation)?;
 	let span = function.context[&function.expression].span;
+	let signature = (0..function.function_type.parameters.len()).map(Variable::new_temporary)
+		.map(|variable| &type_context[&variable]).chain(Some(&type_context[&function.expression]));
+	let uninstantiated = type_context.deferred().or_else(|| signature.filter_map(template).next());
+	if let Some(template) = uninstantiated {
+		let error = InferenceError::Uninstantiated(template.clone());
+		return Err(Diagnostic::new(Spanned::new(error, function_path.span)));
+	}
+
+	let mut basic_context = BasicContext::new(context, reversibility);
 
 	let mut parameters = Vec::new();
 	let parameter = basic_context.temporary();
@@ -70,3 +86,74 @@ pub fn expression(context: &Context, function_path: &Spanned<Arc<FunctionPath>>,
 	let (nodes, component) = basic_context.flatten(component);
 	Ok(BasicFunction { parameters: Vec::new(), component, nodes })
 }
+
+/// Finds a template within the type resolution.
+fn template(resolution: &TypeResolution) -> Option<&Arc<str>> {
+	match resolution {
+		TypeResolution::Template(template) => Some(template),
+		TypeResolution::Reference(_, resolution) |
+		TypeResolution::Array(resolution, _) => template(resolution),
+		TypeResolution::Instance(_, resolutions) => resolutions.iter().filter_map(template).next(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::basic::Item;
+	use crate::error::CompileError;
+	use crate::intrinsic::Intrinsic;
+	use crate::source::{context, function_path};
+
+	use super::*;
+
+	fn instantiation(intrinsic: Intrinsic) -> Instantiation {
+		let resolution = TypeResolution::Instance(intrinsic.structure(), Vec::new());
+		Some(("T".into(), resolution)).into_iter().collect()
+	}
+
+	#[test]
+	fn test_instances() {
+		let context = &context("fn identity<$T>(value: $T) -> $T:\n\tvalue\n");
+		let path = &function_path("identity");
+		for intrinsic in &[Intrinsic::Unsigned32, Intrinsic::Signed64] {
+			let instantiation = &instantiation(*intrinsic);
+			let function = instance(context, path, Reversibility::Entropic, instantiation).unwrap();
+			assert_eq!(function.parameters, vec![instantiation["T"].clone()]);
+		}
+		assert_eq!(context.basic_functions.len(), 2);
+	}
+
+	#[test]
+	fn test_uninstantiated() {
+		let context = &context("data Square:\n\twidth: u32,\n\n\
+			fn identity<$T>(value: $T) -> $T:\n\tvalue\n\n\
+			fn width<$T>(shape: &$T) -> u32:\n\tshape.width\n");
+		for identifier in &["identity", "width"] {
+			match function(context, &function_path(identifier), Reversibility::Entropic) {
+				Err(diagnostic) => match diagnostic.error.node {
+					CompileError::Inference(InferenceError::Uninstantiated(template)) =>
+						assert_eq!(template.as_ref(), "T"),
+					other => panic!("Expected uninstantiated error, instead got: {:?}", other),
+				},
+				Ok(_) => panic!("Expected uninstantiated error for: {}", identifier),
+			}
+		}
+	}
+
+	#[test]
+	fn test_interface_dispatch() {
+		let context = &context("interface Shape:\n\tfn area(&self) -> u32\n\n\
+			data Square:\n\tside: u32,\n\ndata Rectangle:\n\twidth: u32,\n\theight: u32,\n\n\
+			define Shape for Square:\n\tfn area(&self) -> u32:\n\t\tself.side * self.side\n\n\
+			define Shape for Rectangle:\n\tfn area(&self) -> u32:\n\t\tself.width * self.height\n\n\
+			fn total<$T: Shape>(shapes: &[$T; 2]) -> u32:\n\tshapes[0].area() + shapes[1].area()\n\n\
+			fn squares() -> u32:\n\tlet a = Square: side: 2\n\tlet b = Square: side: 3\n\ttotal(&[a, b])\n\n\
+			fn rectangles() -> u32:\n\tlet a = Rectangle: width: 2, height: 5\n\t\
+			let b = Rectangle: width: 1, height: 1\n\ttotal(&[a, b])\n");
+		let areas = [("squares", Item::Unsigned32(13)), ("rectangles", Item::Unsigned32(11))];
+		for (function, area) in &areas {
+			let item = crate::evaluation::function(context, &function_path(function), Vec::new());
+			assert_eq!(&item.unwrap().collapse().unwrap(), area);
+		}
+	}
+}
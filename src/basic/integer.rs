@@ -0,0 +1,410 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, BitAnd, BitOr, BitXor, Mul, Neg, Not, Shl, Shr, Sub};
+
+/// An arbitrary precision signed integer.
+/// The magnitude is stored as little endian limbs without trailing zeroes.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Integer {
+	negative: bool,
+	magnitude: Vec<u32>,
+}
+
+impl Integer {
+	fn new(negative: bool, mut magnitude: Vec<u32>) -> Self {
+		while magnitude.last() == Some(&0) {
+			magnitude.pop();
+		}
+
+		let negative = negative && !magnitude.is_empty();
+		Integer { negative, magnitude }
+	}
+
+	pub fn is_zero(&self) -> bool {
+		self.magnitude.is_empty()
+	}
+
+	pub fn is_negative(&self) -> bool {
+		self.negative
+	}
+
+	pub fn to_i128(&self) -> Option<i128> {
+		if self.magnitude.len() > 4 {
+			return None;
+		}
+
+		let magnitude = self.magnitude.iter().rev()
+			.fold(0u128, |value, limb| (value << 32) | *limb as u128);
+		match self.negative {
+			false if magnitude <= i128::max_value() as u128 => Some(magnitude as i128),
+			true if magnitude <= i128::min_value() as u128 => Some((magnitude as i128).wrapping_neg()),
+			_ => None,
+		}
+	}
+
+	pub fn to_u32(&self) -> Option<u32> {
+		match (self.negative, self.magnitude.as_slice()) {
+			(false, []) => Some(0),
+			(false, [limb]) => Some(*limb),
+			_ => None,
+		}
+	}
+
+	/// Divides with truncation towards zero. Returns none if the divisor is zero.
+	pub fn checked_div_rem(&self, other: &Integer) -> Option<(Integer, Integer)> {
+		if other.is_zero() {
+			return None;
+		}
+
+		let (quotient, remainder) = divide(&self.magnitude, &other.magnitude);
+		Some((Integer::new(self.negative != other.negative, quotient),
+			Integer::new(self.negative, remainder)))
+	}
+
+	/// Provides the limbs of the two's complement representation.
+	fn complement(&self, length: usize) -> Vec<u32> {
+		let mut limbs = self.magnitude.clone();
+		limbs.resize(length, 0);
+		if self.negative {
+			limbs.iter_mut().for_each(|limb| *limb = !*limb);
+			increment(&mut limbs);
+		}
+		limbs
+	}
+
+	fn from_complement(mut limbs: Vec<u32>) -> Self {
+		let negative = limbs.last().map(|limb| limb >> 31 == 1).unwrap_or(false);
+		if negative {
+			limbs.iter_mut().for_each(|limb| *limb = !*limb);
+			increment(&mut limbs);
+		}
+		Integer::new(negative, limbs)
+	}
+
+	fn bitwise(&self, other: &Integer, function: impl Fn(u32, u32) -> u32) -> Integer {
+		let length = self.magnitude.len().max(other.magnitude.len()) + 1;
+		let (left, right) = (self.complement(length), other.complement(length));
+		Integer::from_complement(left.iter().zip(right.iter())
+			.map(|(left, right)| function(*left, *right)).collect())
+	}
+}
+
+impl From<i128> for Integer {
+	fn from(integer: i128) -> Self {
+		let magnitude = match integer < 0 {
+			false => integer as u128,
+			true => (integer as u128).wrapping_neg(),
+		};
+		Integer::new(integer < 0, limbs(magnitude))
+	}
+}
+
+impl From<u128> for Integer {
+	fn from(integer: u128) -> Self {
+		Integer::new(false, limbs(integer))
+	}
+}
+
+impl Ord for Integer {
+	fn cmp(&self, other: &Self) -> Ordering {
+		match (self.negative, other.negative) {
+			(false, true) => Ordering::Greater,
+			(true, false) => Ordering::Less,
+			(false, false) => compare(&self.magnitude, &other.magnitude),
+			(true, true) => compare(&other.magnitude, &self.magnitude),
+		}
+	}
+}
+
+impl PartialOrd for Integer {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Add for &Integer {
+	type Output = Integer;
+
+	fn add(self, other: &Integer) -> Integer {
+		if self.negative == other.negative {
+			return Integer::new(self.negative, add(&self.magnitude, &other.magnitude));
+		}
+
+		match compare(&self.magnitude, &other.magnitude) {
+			Ordering::Less => Integer::new(other.negative, subtract(&other.magnitude, &self.magnitude)),
+			_ => Integer::new(self.negative, subtract(&self.magnitude, &other.magnitude)),
+		}
+	}
+}
+
+impl Sub for &Integer {
+	type Output = Integer;
+
+	fn sub(self, other: &Integer) -> Integer {
+		self + &-other
+	}
+}
+
+impl Mul for &Integer {
+	type Output = Integer;
+
+	fn mul(self, other: &Integer) -> Integer {
+		let mut product = vec![0u32; self.magnitude.len() + other.magnitude.len()];
+		for (index, left) in self.magnitude.iter().enumerate() {
+			let mut carry = 0u64;
+			for (offset, right) in other.magnitude.iter().enumerate() {
+				let value = product[index + offset] as u64 + *left as u64 * *right as u64 + carry;
+				product[index + offset] = value as u32;
+				carry = value >> 32;
+			}
+			product[index + other.magnitude.len()] = carry as u32;
+		}
+		Integer::new(self.negative != other.negative, product)
+	}
+}
+
+impl Neg for &Integer {
+	type Output = Integer;
+
+	fn neg(self) -> Integer {
+		Integer::new(!self.negative, self.magnitude.clone())
+	}
+}
+
+impl Not for &Integer {
+	type Output = Integer;
+
+	fn not(self) -> Integer {
+		&-self - &Integer::from(1i128)
+	}
+}
+
+impl BitAnd for &Integer {
+	type Output = Integer;
+
+	fn bitand(self, other: &Integer) -> Integer {
+		self.bitwise(other, |left, right| left & right)
+	}
+}
+
+impl BitOr for &Integer {
+	type Output = Integer;
+
+	fn bitor(self, other: &Integer) -> Integer {
+		self.bitwise(other, |left, right| left | right)
+	}
+}
+
+impl BitXor for &Integer {
+	type Output = Integer;
+
+	fn bitxor(self, other: &Integer) -> Integer {
+		self.bitwise(other, |left, right| left ^ right)
+	}
+}
+
+impl Shl<u32> for &Integer {
+	type Output = Integer;
+
+	fn shl(self, shift: u32) -> Integer {
+		Integer::new(self.negative, shift_left(&self.magnitude, shift))
+	}
+}
+
+impl Shr<u32> for &Integer {
+	type Output = Integer;
+
+	/// Shifts towards negative infinity such that the result is the floor of the division.
+	fn shr(self, shift: u32) -> Integer {
+		match self.negative {
+			false => Integer::new(false, shift_right(&self.magnitude, shift)),
+			true => {
+				let one = Integer::from(1i128);
+				let magnitude = subtract(&self.magnitude, &one.magnitude);
+				let magnitude = add(&shift_right(&magnitude, shift), &one.magnitude);
+				Integer::new(true, magnitude)
+			}
+		}
+	}
+}
+
+impl fmt::Display for Integer {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.is_zero() {
+			return write!(f, "0");
+		}
+
+		let (mut chunks, mut magnitude) = (Vec::new(), self.magnitude.clone());
+		while !magnitude.is_empty() {
+			let (quotient, remainder) = divide(&magnitude, &[1_000_000_000]);
+			chunks.push(remainder.first().cloned().unwrap_or(0));
+			magnitude = quotient;
+		}
+
+		if self.negative {
+			write!(f, "-")?;
+		}
+
+		let mut chunks = chunks.iter().rev();
+		write!(f, "{}", chunks.next().unwrap())?;
+		chunks.try_for_each(|chunk| write!(f, "{:09}", chunk))
+	}
+}
+
+impl fmt::Debug for Integer {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "Integer({})", self)
+	}
+}
+
+fn limbs(mut integer: u128) -> Vec<u32> {
+	let mut limbs = Vec::new();
+	while integer != 0 {
+		limbs.push(integer as u32);
+		integer >>= 32;
+	}
+	limbs
+}
+
+fn increment(limbs: &mut [u32]) {
+	for limb in limbs {
+		let (value, carry) = limb.overflowing_add(1);
+		*limb = value;
+		if !carry {
+			break;
+		}
+	}
+}
+
+fn compare(left: &[u32], right: &[u32]) -> Ordering {
+	left.len().cmp(&right.len()).then_with(|| left.iter().rev().cmp(right.iter().rev()))
+}
+
+fn add(left: &[u32], right: &[u32]) -> Vec<u32> {
+	let mut carry = 0u64;
+	let mut sum: Vec<_> = (0..left.len().max(right.len())).map(|index| {
+		let value = *left.get(index).unwrap_or(&0) as u64 + *right.get(index).unwrap_or(&0) as u64 + carry;
+		carry = value >> 32;
+		value as u32
+	}).collect();
+	sum.push(carry as u32);
+	sum
+}
+
+/// Subtracts the right magnitude from the left magnitude which must not be less.
+fn subtract(left: &[u32], right: &[u32]) -> Vec<u32> {
+	let mut borrow = 0i64;
+	let mut difference: Vec<_> = left.iter().enumerate().map(|(index, limb)| {
+		let mut value = *limb as i64 - *right.get(index).unwrap_or(&0) as i64 - borrow;
+		borrow = (value < 0) as i64;
+		if value < 0 {
+			value += 1 << 32;
+		}
+		value as u32
+	}).collect();
+
+	while difference.last() == Some(&0) {
+		difference.pop();
+	}
+	difference
+}
+
+fn shift_left(magnitude: &[u32], shift: u32) -> Vec<u32> {
+	let (limbs, bits) = ((shift / 32) as usize, shift % 32);
+	let mut shifted = vec![0; limbs];
+	let mut carry = 0;
+	for limb in magnitude {
+		shifted.push((limb << bits) | carry);
+		carry = match bits {
+			0 => 0,
+			_ => limb >> (32 - bits),
+		};
+	}
+
+	shifted.push(carry);
+	while shifted.last() == Some(&0) {
+		shifted.pop();
+	}
+	shifted
+}
+
+fn shift_right(magnitude: &[u32], shift: u32) -> Vec<u32> {
+	let (limbs, bits) = ((shift / 32) as usize, shift % 32);
+	let magnitude = magnitude.get(limbs..).unwrap_or(&[]);
+	let mut shifted: Vec<_> = magnitude.iter().enumerate().map(|(index, limb)| {
+		let next = *magnitude.get(index + 1).unwrap_or(&0);
+		match bits {
+			0 => *limb,
+			_ => (limb >> bits) | (next << (32 - bits)),
+		}
+	}).collect();
+
+	while shifted.last() == Some(&0) {
+		shifted.pop();
+	}
+	shifted
+}
+
+/// Divides magnitudes by binary long division. The divisor must not be zero.
+fn divide(dividend: &[u32], divisor: &[u32]) -> (Vec<u32>, Vec<u32>) {
+	let mut quotient = vec![0; dividend.len()];
+	let mut remainder = Vec::new();
+	for bit in (0..dividend.len() * 32).rev() {
+		remainder = shift_left(&remainder, 1);
+		if (dividend[bit / 32] >> (bit % 32)) & 1 == 1 {
+			match remainder.first_mut() {
+				Some(limb) => *limb |= 1,
+				None => remainder.push(1),
+			}
+		}
+
+		if compare(&remainder, divisor) != Ordering::Less {
+			remainder = subtract(&remainder, divisor);
+			quotient[bit / 32] |= 1 << (bit % 32);
+		}
+	}
+
+	while quotient.last() == Some(&0) {
+		quotient.pop();
+	}
+	(quotient, remainder)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn integer(integer: i128) -> Integer {
+		Integer::from(integer)
+	}
+
+	#[test]
+	fn test_arithmetic() {
+		let large = &integer(i128::max_value()) * &integer(i128::max_value());
+		assert_eq!(large.to_string(), "28948022309329048855892746252171976962977213799489202546401021394546514198529");
+		assert_eq!(&(&large - &integer(1)) + &integer(1), large);
+		assert_eq!((&integer(-7) * &integer(3)).to_i128(), Some(-21));
+		assert_eq!(integer(i128::min_value()).to_i128(), Some(i128::min_value()));
+		assert!(integer(-3) < integer(2));
+	}
+
+	#[test]
+	fn test_division() {
+		let (quotient, remainder) = integer(-7).checked_div_rem(&integer(2)).unwrap();
+		assert_eq!((quotient.to_i128(), remainder.to_i128()), (Some(-3), Some(-1)));
+		let large = &integer(1) << 100;
+		let (quotient, remainder) = large.checked_div_rem(&integer(3)).unwrap();
+		assert_eq!(&(&quotient * &integer(3)) + &remainder, large);
+		assert!(large.checked_div_rem(&integer(0)).is_none());
+	}
+
+	#[test]
+	fn test_bitwise() {
+		assert_eq!((&integer(-6) & &integer(13)).to_i128(), Some(-6 & 13));
+		assert_eq!((&integer(-6) | &integer(13)).to_i128(), Some(-6 | 13));
+		assert_eq!((&integer(-6) ^ &integer(-13)).to_i128(), Some(-6 ^ -13));
+		assert_eq!((!&integer(5)).to_i128(), Some(!5));
+		assert_eq!((&integer(-7) >> 1).to_i128(), Some(-7 >> 1));
+		assert_eq!((&integer(-7) << 40).to_i128(), Some(-7 << 40));
+	}
+}
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::fmt::{self, Write};
 use std::sync::Arc;
 
@@ -6,6 +7,8 @@ use crate::extension::Indent;
 use crate::inference::TypeResolution;
 use crate::intrinsic::Intrinsic;
 
+use super::Integer;
+
 #[derive(Clone, PartialEq)]
 pub enum Item<T = Instance> {
 	Truth(bool),
@@ -17,13 +20,59 @@ pub enum Item<T = Instance> {
 	Unsigned16(u16),
 	Unsigned32(u32),
 	Unsigned64(u64),
+	Signed128(i128),
+	Unsigned128(u128),
+	/// An arbitrary precision integer.
+	Integer(Integer),
+	Character(char),
+	/// An immutable string of bytes.
+	String(Arc<str>),
 	Instance(T),
 	Uninitialised,
 	Unit,
 }
 
 impl Item {
+	/// Creates an integer item if the integer is within the range of the intrinsic.
 	pub fn integer(intrinsic: Intrinsic, integer: i128) -> Option<Self> {
+		Some(match intrinsic {
+			Intrinsic::Signed8 => Item::Signed8(i8::try_from(integer).ok()?),
+			Intrinsic::Signed16 => Item::Signed16(i16::try_from(integer).ok()?),
+			Intrinsic::Signed32 => Item::Signed32(i32::try_from(integer).ok()?),
+			Intrinsic::Signed64 => Item::Signed64(i64::try_from(integer).ok()?),
+			Intrinsic::Unsigned8 => Item::Unsigned8(u8::try_from(integer).ok()?),
+			Intrinsic::Unsigned16 => Item::Unsigned16(u16::try_from(integer).ok()?),
+			Intrinsic::Unsigned32 => Item::Unsigned32(u32::try_from(integer).ok()?),
+			Intrinsic::Unsigned64 => Item::Unsigned64(u64::try_from(integer).ok()?),
+			Intrinsic::Signed128 => Item::Signed128(integer),
+			Intrinsic::Unsigned128 => Item::Unsigned128(u128::try_from(integer).ok()?),
+			Intrinsic::Integer => Item::Integer(Integer::from(integer)),
+			_ => return None,
+		})
+	}
+
+	/// Creates an integer item from the magnitude of a literal that may be negated.
+	pub fn literal(intrinsic: Intrinsic, magnitude: u128, is_negative: bool) -> Option<Self> {
+		match (intrinsic, is_negative) {
+			(Intrinsic::Unsigned128, false) => Some(Item::Unsigned128(magnitude)),
+			(Intrinsic::Integer, _) => {
+				let integer = Integer::from(magnitude);
+				Some(Item::Integer(match is_negative {
+					false => integer,
+					true => -&integer,
+				}))
+			}
+			(_, false) => Item::integer(intrinsic, i128::try_from(magnitude).ok()?),
+			// The magnitude of the minimum signed integer exceeds the maximum.
+			(_, true) => Item::integer(intrinsic, match magnitude {
+				0 => 0,
+				_ => -i128::try_from(magnitude - 1).ok()? - 1,
+			}),
+		}
+	}
+
+	/// Creates an integer item by truncating the integer to the width of the intrinsic.
+	pub fn wrapping_integer(intrinsic: Intrinsic, integer: i128) -> Option<Self> {
 		Some(match intrinsic {
 			Intrinsic::Signed8 => Item::Signed8(integer as i8),
 			Intrinsic::Signed16 => Item::Signed16(integer as i16),
@@ -33,6 +82,9 @@ impl Item {
 			Intrinsic::Unsigned16 => Item::Unsigned16(integer as u16),
 			Intrinsic::Unsigned32 => Item::Unsigned32(integer as u32),
 			Intrinsic::Unsigned64 => Item::Unsigned64(integer as u64),
+			Intrinsic::Signed128 => Item::Signed128(integer),
+			Intrinsic::Unsigned128 => Item::Unsigned128(integer as u128),
+			Intrinsic::Integer => Item::Integer(Integer::from(integer)),
 			_ => return None,
 		})
 	}
@@ -48,6 +100,11 @@ impl Item {
 			Item::Unsigned16(_) => Intrinsic::Unsigned16.structure(),
 			Item::Unsigned32(_) => Intrinsic::Unsigned32.structure(),
 			Item::Unsigned64(_) => Intrinsic::Unsigned64.structure(),
+			Item::Signed128(_) => Intrinsic::Signed128.structure(),
+			Item::Unsigned128(_) => Intrinsic::Unsigned128.structure(),
+			Item::Integer(_) => Intrinsic::Integer.structure(),
+			Item::Character(_) => Intrinsic::Character.structure(),
+			Item::String(_) => Intrinsic::String.structure(),
 			Item::Unit => Intrinsic::Unit.structure(),
 			Item::Instance(instance) => return Some(instance.type_resolution.clone()),
 			Item::Uninitialised => return None,
@@ -67,6 +124,19 @@ impl<T> fmt::Display for Item<T> where T: fmt::Display {
 			Item::Unsigned16(integer) => write!(f, "{}", integer),
 			Item::Unsigned32(integer) => write!(f, "{}", integer),
 			Item::Unsigned64(integer) => write!(f, "{}", integer),
+			Item::Signed128(integer) => write!(f, "{}", integer),
+			Item::Unsigned128(integer) => write!(f, "{}", integer),
+			Item::Integer(integer) => write!(f, "{}", integer),
+			Item::Character(character) => {
+				f.write_char('\'')?;
+				escape(f, *character, '\'')?;
+				f.write_char('\'')
+			}
+			Item::String(string) => {
+				f.write_char('"')?;
+				string.chars().try_for_each(|character| escape(f, character, '"'))?;
+				f.write_char('"')
+			}
 			Item::Instance(instance) => write!(f, "{}", instance),
 			Item::Uninitialised => write!(f, "<!>"),
 			Item::Unit => write!(f, "()"),
@@ -74,6 +144,19 @@ impl<T> fmt::Display for Item<T> where T: fmt::Display {
 	}
 }
 
+/// Writes a character of a text literal delimited by the quote.
+fn escape(f: &mut fmt::Formatter, character: char, quote: char) -> fmt::Result {
+	match character {
+		'\n' => f.write_str("\\n"),
+		'\r' => f.write_str("\\r"),
+		'\t' => f.write_str("\\t"),
+		'\0' => f.write_str("\\0"),
+		'\\' => f.write_str("\\\\"),
+		_ if character == quote => write!(f, "\\{}", quote),
+		_ => f.write_char(character),
+	}
+}
+
 impl<T> fmt::Debug for Item<T> where T: fmt::Debug {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		match self {
@@ -86,6 +169,11 @@ impl<T> fmt::Debug for Item<T> where T: fmt::Debug {
 			Item::Unsigned16(integer) => write!(f, "Unsigned16({})", integer),
 			Item::Unsigned32(integer) => write!(f, "Unsigned32({})", integer),
 			Item::Unsigned64(integer) => write!(f, "Unsigned64({})", integer),
+			Item::Signed128(integer) => write!(f, "Signed128({})", integer),
+			Item::Unsigned128(integer) => write!(f, "Unsigned128({})", integer),
+			Item::Integer(integer) => write!(f, "{:?}", integer),
+			Item::Character(character) => write!(f, "Character({:?})", character),
+			Item::String(string) => write!(f, "String({:?})", string),
 			Item::Instance(instance) => write!(f, "Instance({:?})", instance),
 			Item::Uninitialised => write!(f, "Uninitialised"),
 			Item::Unit => write!(f, "Unit"),
@@ -97,11 +185,13 @@ impl<T> fmt::Debug for Item<T> where T: fmt::Debug {
 pub struct Instance {
 	pub type_resolution: TypeResolution,
 	pub fields: HashMap<Arc<str>, Item<Self>>,
+	/// Index and identifier of the variant if the instance is of a sum type.
+	pub variant: Option<(usize, Arc<str>)>,
 }
 
 impl Instance {
 	pub fn new(type_resolution: TypeResolution) -> Self {
-		Instance { type_resolution, fields: HashMap::new() }
+		Instance { type_resolution, fields: HashMap::new(), variant: None }
 	}
 
 	pub fn tuple() -> Self {
@@ -111,9 +201,21 @@ impl Instance {
 
 impl fmt::Display for Instance {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		writeln!(f, "{}:", self.type_resolution)?;
+		match &self.variant {
+			Some((_, variant)) => write!(f, "{}::{}", self.type_resolution, variant),
+			None => write!(f, "{}", self.type_resolution),
+		}?;
+
+		if self.variant.is_some() && self.fields.is_empty() { return Ok(()); }
+		writeln!(f, ":")?;
+
+		let mut fields: Vec<_> = self.fields.iter().collect();
+		if self.type_resolution.is_array() {
+			fields.sort_by_key(|(field, _)| field.parse::<usize>().ok());
+		}
+
 		let indent = &mut Indent::new(f);
-		for (index, (field, item)) in self.fields.iter().enumerate() {
+		for (index, (field, item)) in fields.into_iter().enumerate() {
 			match item {
 				Item::Instance(_) => write!(indent, "{}: {}", field, item),
 				_ => write!(indent, "{}: {},", field, item),
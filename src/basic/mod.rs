@@ -1,9 +1,10 @@
 pub use basic::{BasicFunction, BasicFunctions, Component, Direction, Reversibility};
 pub use branch::{Branch, Discriminant, Divergence};
 use context::BasicContext;
-pub use function::{function, expression};
+pub use function::{function, expression, instance};
 pub use node::{BasicNode, NodeTarget};
 pub use statement::{Compound, Location, Projection, Statement, Value};
+pub use integer::Integer;
 pub use item::{Item, Instance};
 
 mod basic;
@@ -15,4 +16,5 @@ mod function;
 mod pattern;
 mod branch;
 mod item;
+mod integer;
 mod node;
@@ -2,6 +2,7 @@ use std::fmt;
 use std::sync::Arc;
 
 use crate::declaration::FunctionPath;
+use crate::inference::Instantiation;
 use crate::node::{BinaryOperator, MutationKind, UnaryOperator, Variable};
 use crate::span::Spanned;
 
@@ -73,6 +74,7 @@ impl fmt::Debug for Location {
 #[derive(Debug, Clone, PartialEq)]
 pub enum Projection {
 	Field(Arc<str>),
+	Index(Variable),
 	Dereference,
 }
 
@@ -80,6 +82,7 @@ impl fmt::Display for Projection {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		match self {
 			Projection::Field(field) => write!(f, ".{}", field),
+			Projection::Index(variable) => write!(f, "[{}]", variable),
 			Projection::Dereference => write!(f, "*"),
 		}
 	}
@@ -90,7 +93,9 @@ pub enum Compound {
 	Value(Value),
 	Unary(UnaryOperator, Value),
 	Binary(BinaryOperator, Value, Value),
-	FunctionCall(Spanned<Arc<FunctionPath>>, Vec<Value>),
+	FunctionCall(Spanned<Arc<FunctionPath>>, Instantiation, Vec<Value>),
+	/// Length in bytes of a string.
+	Length(Value),
 }
 
 impl fmt::Display for Compound {
@@ -102,8 +107,20 @@ impl fmt::Display for Compound {
 				write!(f, "{}{}", operator, value),
 			Compound::Binary(operator, left, right) =>
 				write!(f, "{} {} {}", left, operator, right),
-			Compound::FunctionCall(function, values) => {
+			Compound::Length(value) =>
+				write!(f, "length {}", value),
+			Compound::FunctionCall(function, instantiation, values) => {
 				write!(f, "{}", function)?;
+				instantiation.iter().enumerate().try_for_each(|(index, (template, resolution))|
+					match index {
+						0 => write!(f, "<${} = {}", template, resolution),
+						_ => write!(f, ", ${} = {}", template, resolution),
+					})?;
+
+				if !instantiation.is_empty() {
+					write!(f, ">")?;
+				}
+
 				values.iter().try_for_each(|value|
 					write!(f, " {}", value))
 			}
@@ -0,0 +1,83 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::basic::{Location, Projection};
+use crate::error::CompileError;
+use crate::node::{Permission, Variable};
+use crate::span::Span;
+
+#[derive(Debug, PartialEq)]
+pub enum BorrowError {
+	Conflict(Arc<str>),
+	Use(Arc<str>),
+	Mutation(Arc<str>),
+	Drop(Arc<str>),
+	Outlived(Arc<str>),
+	EscapingVariable(Arc<str>),
+	EscapingTemporary,
+	Outlives(Arc<str>, Arc<str>),
+	MissingLifetime(Arc<str>, Arc<str>),
+}
+
+impl fmt::Display for BorrowError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			BorrowError::Conflict(variable) =>
+				write!(f, "Variable: {}, is already borrowed", variable),
+			BorrowError::Use(variable) =>
+				write!(f, "Variable: {}, is used while uniquely borrowed", variable),
+			BorrowError::Mutation(variable) =>
+				write!(f, "Variable: {}, is mutated while borrowed", variable),
+			BorrowError::Drop(variable) =>
+				write!(f, "Variable: {}, is dropped while borrowed", variable),
+			BorrowError::Outlived(variable) =>
+				write!(f, "Variable: {}, does not live as long as its reference", variable),
+			BorrowError::EscapingVariable(variable) =>
+				write!(f, "Reference to variable: {}, escapes its function", variable),
+			BorrowError::EscapingTemporary =>
+				write!(f, "Reference to a temporary value escapes its function"),
+			BorrowError::Outlives(lifetime, required) =>
+				write!(f, "Lifetime: '{}, does not outlive lifetime: '{}", lifetime, required),
+			BorrowError::MissingLifetime(parameter, required) =>
+				write!(f, "Parameter: {}, does not have lifetime: '{}", parameter, required),
+		}
+	}
+}
+
+impl From<BorrowError> for CompileError {
+	fn from(error: BorrowError) -> Self {
+		CompileError::Borrow(error)
+	}
+}
+
+pub type LoanKey = usize;
+
+/// A reference to a place that is live while any of its holders may still be used.
+/// Loans without holders are temporaries of the expression being checked.
+#[derive(Debug)]
+pub struct Loan {
+	/// The place that is referenced or none if the value is a temporary.
+	pub place: Option<Location>,
+	pub permission: Permission,
+	pub holders: Vec<Variable>,
+	/// The loan that the place is reached through.
+	pub parent: Option<LoanKey>,
+	/// Loans held by parameters refer to places outside the function.
+	pub is_parameter: bool,
+	pub span: Span,
+}
+
+impl Loan {
+	/// Checks if the place of the loan and the place may refer to the same value.
+	/// Indices are not known statically so any two indices may refer to the same element.
+	pub fn overlaps(&self, place: &Location) -> bool {
+		match &self.place {
+			None => false,
+			Some(other) => other.variable == place.variable && Iterator::zip(other
+				.projections.iter(), place.projections.iter()).all(|(left, right)| match (left, right) {
+				(Projection::Index(_), Projection::Index(_)) => true,
+				_ => left == right,
+			}),
+		}
+	}
+}
@@ -0,0 +1,767 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::basic::{Location, Projection};
+use crate::context::Context;
+use crate::declaration::{FunctionPath, ModulePath, StructurePath};
+use crate::error::Diagnostic;
+use crate::inference::{TypeContext, TypeResolution};
+use crate::node::{Ascription, AscriptionPattern, BindingVariable, Expression, ExpressionKey,
+	FunctionContext, FunctionType, MutationKind, NodeFunction, Parameter, Pattern, Permission,
+	UnaryOperator, Variable};
+use crate::span::{Span, Spanned};
+
+use super::{BorrowError, Loan, LoanKey};
+
+#[derive(Debug)]
+struct BorrowContext<'a> {
+	context: &'a Context,
+	function: &'a FunctionContext,
+	types: &'a TypeContext,
+	/// The expression of the function body.
+	body: ExpressionKey,
+	loans: BTreeMap<LoanKey, Loan>,
+	next_loan: LoanKey,
+	/// Lifetimes that are named in the ascription of each parameter.
+	parameters: HashMap<Variable, Vec<Arc<str>>>,
+	/// Expressions that may be evaluated after the current statement starts.
+	later: Vec<ExpressionKey>,
+	mentions: HashMap<ExpressionKey, HashSet<Variable>>,
+}
+
+/// Checks that unique references are not aliased, that borrowed places are not
+/// used, mutated or dropped while the reference is live and that references do
+/// not outlive the values they refer to.
+pub fn function(context: &Context, function: &NodeFunction, types: &TypeContext) -> Result<(), Diagnostic> {
+	let mut borrow = BorrowContext {
+		context,
+		function: &function.context,
+		types,
+		body: function.expression,
+		loans: BTreeMap::new(),
+		next_loan: 0,
+		parameters: HashMap::new(),
+		later: Vec::new(),
+		mentions: HashMap::new(),
+	};
+
+	for parameter in &function.function_type.parameters {
+		let Parameter(pattern, ascription) = &parameter.node;
+		let mut variables = Vec::new();
+		pattern.traverse(&mut |terminal| variables.push(terminal.clone()));
+		for terminal in variables {
+			let BindingVariable(variable, _) = terminal.node;
+			borrow.parameters.insert(variable.clone(), lifetimes(ascription));
+			if borrow.contains_reference(&types[&variable]) {
+				let permission = match &types[&variable] {
+					TypeResolution::Reference(permission, _) => *permission,
+					_ => Permission::Shared,
+				};
+
+				let place = Location::new(variable.clone()).push(Projection::Dereference);
+				borrow.insert(Loan {
+					place: Some(place),
+					permission,
+					holders: vec![variable],
+					parent: None,
+					is_parameter: true,
+					span: terminal.span,
+				});
+			}
+		}
+	}
+
+	let loans = borrow.expression(&function.expression)?;
+	let lifetimes = lifetimes(&function.function_type.return_type.node);
+	loans.iter().try_for_each(|loan| borrow.escape(loan, &lifetimes))
+}
+
+impl<'a> BorrowContext<'a> {
+	/// Checks an expression and provides the loans that its value holds.
+	fn expression(&mut self, expression: &ExpressionKey) -> Result<Vec<LoanKey>, Diagnostic> {
+		let function = self.function;
+		let span = function[expression].span;
+		Ok(match &function[expression].node {
+			Expression::Block(block) => self.block(expression, block)?,
+			Expression::Binding(pattern, _, value) => {
+				let loans = self.expression(value)?;
+				if self.contains_reference(&self.types[value]) {
+					let mut variables = Vec::new();
+					pattern.node.traverse(&mut |terminal|
+						variables.push(terminal.node.0.clone()));
+					loans.iter().for_each(|loan| self.loans.get_mut(loan)
+						.unwrap().holders.extend(variables.iter().cloned()));
+				}
+				Vec::new()
+			}
+			Expression::TerminationLoop(condition_start, condition_end, body) => {
+				self.later.push(*expression);
+				if let Some(condition_start) = condition_start {
+					self.expression(condition_start)?;
+				}
+
+				self.expression(condition_end)?;
+				self.expression(body)?;
+				self.later.pop();
+				Vec::new()
+			}
+			Expression::Conditional(branches) => {
+				let mut loans = Vec::new();
+				for (condition_start, condition_end, body) in branches {
+					self.expression(condition_start)?;
+					if let Some(condition_end) = condition_end {
+						self.expression(condition_end)?;
+					}
+					loans.extend(self.expression(body)?);
+				}
+				loans
+			}
+			Expression::Match(value, arms) => {
+				let (held, mut loans) = (self.expression(value)?, Vec::new());
+				for arm in arms {
+					if self.contains_reference(&self.types[value]) {
+						let variables = arm.bindings.iter().map(|binding| binding.node.0.clone());
+						held.iter().for_each(|loan| self.loans.get_mut(loan)
+							.unwrap().holders.extend(variables.clone()));
+					}
+					loans.extend(self.expression(&arm.expression)?);
+				}
+				loans
+			}
+			Expression::Mutation(kind, left, right) => {
+				match kind.node {
+					MutationKind::Swap => {
+						let (left_loans, right_loans) = (self.held(left), self.held(right));
+						self.mutation(left, &right_loans)?;
+						self.mutation(right, &left_loans)?;
+					}
+					_ => {
+						let loans = self.expression(right)?;
+						self.mutation(left, &loans)?;
+					}
+				}
+				Vec::new()
+			}
+			Expression::ExplicitDrop(pattern, value) => {
+				self.expression(value)?;
+				let mut variables = Vec::new();
+				pattern.traverse(&mut |variable| variables.push(variable.clone()));
+				for variable in &variables {
+					let place = Location::new(variable.node.clone());
+					let error = BorrowError::Drop(variable.node.0.clone());
+					self.conflict(&place, None, variable.span, error, |_| true)?;
+				}
+
+				let variables: Vec<_> = variables.into_iter().map(|variable| variable.node).collect();
+				self.forget(&variables, &[]);
+				Vec::new()
+			}
+			Expression::Unary(operator, inner) => match &operator.node {
+				UnaryOperator::Reference(permission) => self.reference(inner, *permission, span)?,
+				UnaryOperator::Dereference => {
+					self.place_use(expression)?;
+					let loans = self.expression(inner)?;
+					self.reference_loans(expression, loans)
+				}
+				UnaryOperator::Negate | UnaryOperator::Not => {
+					self.expression(inner)?;
+					Vec::new()
+				}
+			},
+			Expression::Binary(_, left, right) => {
+				self.expression(left)?;
+				self.expression(right)?;
+				Vec::new()
+			}
+			Expression::Field(receiver, _) => match self.place(expression) {
+				Some((place, _)) => {
+					self.place_use(expression)?;
+					let loans = self.holder(&place.variable);
+					self.reference_loans(expression, loans)
+				}
+				None => {
+					let loans = self.expression(receiver)?;
+					self.reference_loans(expression, loans)
+				}
+			},
+			Expression::Index(receiver, index) => {
+				self.expression(index)?;
+				match self.place(expression) {
+					Some((place, _)) => {
+						self.place_use(expression)?;
+						let loans = self.holder(&place.variable);
+						self.reference_loans(expression, loans)
+					}
+					None => {
+						let loans = self.expression(receiver)?;
+						self.reference_loans(expression, loans)
+					}
+				}
+			}
+			Expression::MethodCall(receiver, method, arguments) => {
+				let mut resolution = &self.types[receiver];
+				let function_type = loop {
+					match resolution {
+						// Intrinsic methods only read from the receiver.
+						TypeResolution::Instance(structure, _) if structure.is_string() => {
+							self.expression(receiver)?;
+							return Ok(Vec::new());
+						}
+						TypeResolution::Instance(structure, _) => {
+							let path = FunctionPath::method(structure.clone(), method.node.clone());
+							let path = Spanned::new(Arc::new(path), method.span);
+							break crate::node::function_type(self.context, &path)?;
+						}
+						TypeResolution::Reference(_, inner) => resolution = inner,
+						TypeResolution::Template(template) => {
+							let path = Spanned::new(self.function.function_path.clone(), method.span);
+							let function_type = crate::node::function_type(self.context, &path)?;
+							break crate::node::template_method(self.context, &function_type,
+								template, &method.node)?.expect("Template method has no bound");
+						}
+						TypeResolution::Array(_, _) => panic!("Method call cannot be performed on array"),
+					}
+				};
+
+				let start = self.next_loan;
+				let mut loans = self.arguments(&function_type, arguments, 1)?;
+				let Parameter(_, ascription) = &function_type.parameters[0].node;
+				let receiver_loans = match (ascription, &self.types[receiver]) {
+					(Pattern::Terminal(ascription), TypeResolution::Instance(_, _)) |
+					(Pattern::Terminal(ascription), TypeResolution::Template(_)) =>
+						match &ascription.node {
+							Ascription::Reference(permission, _, _) =>
+								self.reference(receiver, *permission, function[receiver].span)?,
+							_ => self.expression(receiver)?,
+						},
+					_ => self.expression(receiver)?,
+				};
+
+				if self.is_returned(&function_type, 0) {
+					loans.extend(receiver_loans);
+				}
+
+				self.release(start, &loans);
+				self.reference_loans(expression, loans)
+			}
+			Expression::FunctionCall(path, arguments, _) => {
+				let path = path.clone().map(Arc::new);
+				let function_type = crate::node::function_type(self.context, &path)?;
+				let start = self.next_loan;
+				let loans = self.arguments(&function_type, arguments, 0)?;
+				self.release(start, &loans);
+				self.reference_loans(expression, loans)
+			}
+			Expression::Structure(_, fields) | Expression::Variant(_, _, fields) => {
+				let mut fields: Vec<_> = fields.values().collect();
+				fields.sort_by_key(|(span, _)| span.byte_start);
+				let mut loans = Vec::new();
+				for (_, field) in fields {
+					loans.extend(self.expression(field)?);
+				}
+				loans
+			}
+			Expression::Array(elements) => {
+				let mut loans = Vec::new();
+				for element in elements {
+					loans.extend(self.expression(element)?);
+				}
+				loans
+			}
+			Expression::Pattern(pattern) => {
+				let mut elements = Vec::new();
+				pattern.traverse(&mut |element| elements.push(*element));
+				let mut loans = Vec::new();
+				for element in &elements {
+					loans.extend(self.expression(element)?);
+				}
+				loans
+			}
+			Expression::Variable(variable) => {
+				self.place_use(expression)?;
+				let loans = self.holder(variable);
+				self.reference_loans(expression, loans)
+			}
+			Expression::Integer(_, _) | Expression::Truth(_) | Expression::Item(_) => Vec::new(),
+		})
+	}
+
+	fn block(&mut self, expression: &ExpressionKey, block: &[ExpressionKey])
+	         -> Result<Vec<LoanKey>, Diagnostic> {
+		let mut value = Vec::new();
+		for (index, statement) in block.iter().enumerate() {
+			let length = self.later.len();
+			self.later.extend(&block[index..]);
+			let start = self.next_loan;
+			value = self.expression(statement)?;
+			self.later.truncate(length);
+
+			match index + 1 == block.len() {
+				true => self.release(start, &value),
+				false => self.release(start, &[]),
+			}
+		}
+
+		let mut variables = Vec::new();
+		for statement in block {
+			if let Expression::Binding(pattern, _, _) = &self.function[statement].node {
+				pattern.node.traverse(&mut |terminal| variables.push(terminal.node.0.clone()));
+			}
+		}
+
+		self.forget(&variables, &value);
+		let is_body = expression == &self.body;
+		for key in self.loans.keys().cloned().collect::<Vec<_>>() {
+			let loan = &self.loans[&key];
+			let is_outlived = match &loan.place {
+				Some(place) => variables.contains(&place.variable),
+				None => false,
+			};
+
+			let is_returned = value.contains(&key) && !is_body;
+			if is_outlived && (is_returned || (!loan.holders.is_empty() && self.is_live(key))) {
+				let loan = &self.loans[&key];
+				let Variable(identifier, _) = &loan.place.as_ref().unwrap().variable;
+				let error = BorrowError::Outlived(identifier.clone());
+				return Err(Diagnostic::new(Spanned::new(error, loan.span)));
+			}
+		}
+		Ok(value)
+	}
+
+	/// Creates a loan of the place of an expression or of a temporary value.
+	fn reference(&mut self, expression: &ExpressionKey, permission: Permission, span: Span)
+	             -> Result<Vec<LoanKey>, Diagnostic> {
+		let (place, parent, mut loans) = match self.place(expression) {
+			Some((place, parent)) => {
+				let Variable(identifier, _) = &place.variable;
+				let error = BorrowError::Conflict(identifier.clone());
+				self.conflict(&place, parent, span, error, |loan|
+					permission == Permission::Unique || loan.permission == Permission::Unique)?;
+				(Some(place), parent, Vec::new())
+			}
+			None => (None, None, self.expression(expression)?),
+		};
+
+		let loan = Loan { place, permission, holders: Vec::new(), parent, is_parameter: false, span };
+		loans.push(self.insert(loan));
+		Ok(loans)
+	}
+
+	/// Checks the mutation of the place of an expression that is assigned a value holding the loans.
+	fn mutation(&mut self, expression: &ExpressionKey, loans: &[LoanKey]) -> Result<(), Diagnostic> {
+		let span = self.function[expression].span;
+		if let Expression::Pattern(pattern) = &self.function[expression].node {
+			let mut elements = Vec::new();
+			pattern.traverse(&mut |element| elements.push(*element));
+			return elements.iter().try_for_each(|element| self.mutation(element, &[]));
+		}
+
+		let (place, parent) = match self.place(expression) {
+			Some(place) => place,
+			None => return Ok(()),
+		};
+
+		let Variable(identifier, _) = &place.variable;
+		let error = BorrowError::Mutation(identifier.clone());
+		self.conflict(&place, parent, span, error, |_| true)?;
+
+		if self.parameters.contains_key(&place.variable) &&
+			place.projections.contains(&Projection::Dereference) {
+			return loans.iter().try_for_each(|loan| self.escape(loan, &[]));
+		}
+
+		if place.projections.is_empty() {
+			self.loans.values_mut().for_each(|loan|
+				loan.holders.retain(|holder| holder != &place.variable));
+		}
+
+		if self.contains_reference(&self.types[expression]) {
+			loans.iter().for_each(|loan| self.loans.get_mut(loan)
+				.unwrap().holders.push(place.variable.clone()));
+		}
+		Ok(())
+	}
+
+	/// Checks the arguments of a function call starting from the parameter
+	/// offset and provides the loans that may be held by the return value.
+	fn arguments(&mut self, function_type: &FunctionType, arguments: &[ExpressionKey],
+	             offset: usize) -> Result<Vec<LoanKey>, Diagnostic> {
+		let mut loans = Vec::new();
+		for (index, argument) in arguments.iter().enumerate() {
+			let argument_loans = self.expression(argument)?;
+			if self.is_returned(function_type, index + offset) {
+				loans.extend(argument_loans);
+			}
+		}
+		Ok(loans)
+	}
+
+	/// Checks if the return value may hold references from the parameter.
+	fn is_returned(&self, function_type: &FunctionType, parameter: usize) -> bool {
+		let return_lifetimes = lifetimes(&function_type.return_type.node);
+		let Parameter(_, ascription) = &function_type.parameters[parameter].node;
+		return_lifetimes.is_empty() || lifetimes(ascription).iter()
+			.any(|lifetime| return_lifetimes.contains(lifetime))
+	}
+
+	/// Checks that a loan held by a value that leaves the function refers to a place
+	/// outside of the function with one of the lifetimes if any are specified.
+	fn escape(&self, loan: &LoanKey, lifetimes: &[Arc<str>]) -> Result<(), Diagnostic> {
+		let loan = &self.loans[loan];
+		let place = loan.place.as_ref().ok_or_else(|| Diagnostic::new(Spanned::new(
+			BorrowError::EscapingTemporary, loan.span)))?;
+
+		let Variable(identifier, _) = &place.variable;
+		let parameter_lifetimes = match self.parameters.get(&place.variable) {
+			Some(parameter_lifetimes) if place.projections.contains(&Projection::Dereference) =>
+				parameter_lifetimes,
+			_ => {
+				let error = BorrowError::EscapingVariable(identifier.clone());
+				return Err(Diagnostic::new(Spanned::new(error, loan.span)));
+			}
+		};
+
+		match lifetimes.first() {
+			Some(_) if parameter_lifetimes.iter().any(|lifetime| lifetimes.contains(lifetime)) => Ok(()),
+			Some(required) => {
+				let error = match parameter_lifetimes.first() {
+					Some(lifetime) => BorrowError::Outlives(lifetime.clone(), required.clone()),
+					None => BorrowError::MissingLifetime(identifier.clone(), required.clone()),
+				};
+				Err(Diagnostic::new(Spanned::new(error, loan.span)))
+			}
+			None => Ok(()),
+		}
+	}
+
+	/// Checks that the place of an expression is not uniquely borrowed.
+	fn place_use(&mut self, expression: &ExpressionKey) -> Result<(), Diagnostic> {
+		let span = self.function[expression].span;
+		match self.place(expression) {
+			Some((place, parent)) => {
+				let Variable(identifier, _) = &place.variable;
+				let error = BorrowError::Use(identifier.clone());
+				self.conflict(&place, parent, span, error, |loan|
+					loan.permission == Permission::Unique)
+			}
+			None => Ok(()),
+		}
+	}
+
+	/// Fails if a live loan that is not an ancestor of the parent overlaps the place.
+	fn conflict<F>(&mut self, place: &Location, parent: Option<LoanKey>, span: Span,
+	               error: BorrowError, is_conflict: F) -> Result<(), Diagnostic>
+		where F: Fn(&Loan) -> bool {
+		let mut ancestors = HashSet::new();
+		let mut ancestor = parent;
+		while let Some(key) = ancestor {
+			ancestors.insert(key);
+			ancestor = self.loans.get(&key).and_then(|loan| loan.parent);
+		}
+
+		let candidates: Vec<_> = self.loans.iter().filter(|(key, loan)| !ancestors.contains(key) &&
+			!loan.is_parameter && loan.overlaps(place) && is_conflict(loan))
+			.map(|(key, _)| *key).collect();
+		match candidates.into_iter().find(|key| self.is_live(*key)) {
+			None => Ok(()),
+			Some(key) => {
+				let location = self.loans[&key].span.location(self.context);
+				Err(Diagnostic::new(Spanned::new(error, span))
+					.note(format!("Borrowed at: {}", location)))
+			}
+		}
+	}
+
+	/// Provides the place an expression refers to and the loan it is reached through.
+	/// Dereferences of variables that hold a single loan are resolved to the place of the loan.
+	fn place(&self, expression: &ExpressionKey) -> Option<(Location, Option<LoanKey>)> {
+		match &self.function[expression].node {
+			Expression::Variable(variable) => Some((Location::new(variable.clone()), None)),
+			Expression::Field(receiver, field) => {
+				let (mut place, mut parent) = self.place(receiver)?;
+				let mut resolution = &self.types[receiver];
+				while let TypeResolution::Reference(_, inner) = resolution {
+					let (dereference, loan) = self.dereference(place, parent);
+					place = dereference;
+					parent = loan;
+					resolution = inner;
+				}
+				Some((place.push(Projection::Field(field.node.clone())), parent))
+			}
+			Expression::Index(receiver, index) => {
+				let (mut place, mut parent) = self.place(receiver)?;
+				let mut resolution = &self.types[receiver];
+				while let TypeResolution::Reference(_, inner) = resolution {
+					let (dereference, loan) = self.dereference(place, parent);
+					place = dereference;
+					parent = loan;
+					resolution = inner;
+				}
+
+				// Index places overlap regardless of the index variable.
+				let variable = match &self.function[index].node {
+					Expression::Variable(variable) => variable.clone(),
+					_ => Variable::new_temporary(index.0),
+				};
+				Some((place.push(Projection::Index(variable)), parent))
+			}
+			Expression::Unary(operator, inner) => match operator.node {
+				UnaryOperator::Dereference => {
+					let (place, parent) = self.place(inner)?;
+					Some(self.dereference(place, parent))
+				}
+				_ => None,
+			},
+			Expression::Pattern(Pattern::Terminal(inner)) => self.place(inner),
+			_ => None,
+		}
+	}
+
+	fn dereference(&self, place: Location, parent: Option<LoanKey>) -> (Location, Option<LoanKey>) {
+		if place.projections.is_empty() {
+			let loans: Vec<_> = self.loans.iter().filter(|(_, loan)|
+				loan.holders.contains(&place.variable)).collect();
+			if let [(key, Loan { place: Some(target), .. })] = loans.as_slice() {
+				return (target.clone(), Some(**key));
+			}
+		}
+		(place.push(Projection::Dereference), parent)
+	}
+
+	/// Provides the loans held by the variable at the root of the place of an expression.
+	fn held(&self, expression: &ExpressionKey) -> Vec<LoanKey> {
+		match self.place(expression) {
+			Some((place, _)) => self.holder(&place.variable),
+			None => Vec::new(),
+		}
+	}
+
+	fn holder(&self, variable: &Variable) -> Vec<LoanKey> {
+		self.loans.iter().filter(|(_, loan)| loan.holders.contains(variable))
+			.map(|(key, _)| *key).collect()
+	}
+
+	/// Provides the loans if the value of the expression may contain references.
+	fn reference_loans(&self, expression: &ExpressionKey, loans: Vec<LoanKey>) -> Vec<LoanKey> {
+		match self.contains_reference(&self.types[expression]) {
+			true => loans,
+			false => Vec::new(),
+		}
+	}
+
+	/// Checks if a holder of the loan may be used after the current statement starts.
+	/// Loans without holders are temporaries that are live until they are released.
+	fn is_live(&mut self, loan: LoanKey) -> bool {
+		let function = self.function;
+		let holders = self.loans[&loan].holders.clone();
+		let mentions = &mut self.mentions;
+		holders.is_empty() || self.later.iter().any(|expression| {
+			let variables = mentions.entry(*expression).or_insert_with(||
+				variables(function, expression));
+			holders.iter().any(|holder| variables.contains(holder))
+		})
+	}
+
+	fn insert(&mut self, loan: Loan) -> LoanKey {
+		let key = self.next_loan;
+		self.loans.insert(key, loan);
+		self.next_loan += 1;
+		key
+	}
+
+	/// Removes temporary loans created since the start that are not retained.
+	fn release(&mut self, start: LoanKey, retained: &[LoanKey]) {
+		let keys: Vec<_> = self.loans.range(start..).filter(|(key, loan)|
+			loan.holders.is_empty() && !retained.contains(key)).map(|(key, _)| *key).collect();
+		keys.iter().for_each(|key| { self.loans.remove(key); });
+	}
+
+	/// Removes the variables as holders and the loans that are no longer held.
+	fn forget(&mut self, variables: &[Variable], retained: &[LoanKey]) {
+		let keys: Vec<_> = self.loans.iter_mut().filter_map(|(key, loan)| {
+			let is_held = !loan.holders.is_empty();
+			loan.holders.retain(|holder| !variables.contains(holder));
+			match is_held && loan.holders.is_empty() && !retained.contains(key) {
+				true => Some(*key),
+				false => None,
+			}
+		}).collect();
+		keys.iter().for_each(|key| { self.loans.remove(key); });
+	}
+
+	fn contains_reference(&self, resolution: &TypeResolution) -> bool {
+		match resolution {
+			TypeResolution::Reference(_, _) => true,
+			TypeResolution::Array(resolution, _) => self.contains_reference(resolution),
+			TypeResolution::Template(_) => false,
+			TypeResolution::Instance(structure, resolutions) => resolutions.iter()
+				.any(|resolution| self.contains_reference(resolution)) ||
+				self.structure_reference(structure, &mut HashSet::new()),
+		}
+	}
+
+	/// Checks if the fields of a structure are ascribed with references.
+	fn structure_reference(&self, structure: &StructurePath, visited: &mut HashSet<StructurePath>) -> bool {
+		let StructurePath(path) = structure;
+		if path.module_path == ModulePath::intrinsic() || !visited.insert(structure.clone()) {
+			return false;
+		}
+
+		let path = Spanned::new(Arc::new(structure.clone()), Span::INTERNAL);
+		match crate::node::structure(self.context, &path) {
+			Err(_) => false,
+			Ok(structure) => structure.ascriptions().any(|field|
+				self.ascription_reference(field, visited)),
+		}
+	}
+
+	fn ascription_reference(&self, ascription: &AscriptionPattern,
+	                        visited: &mut HashSet<StructurePath>) -> bool {
+		let mut is_reference = false;
+		ascription.traverse(&mut |ascription| is_reference |= match &ascription.node {
+			Ascription::Reference(_, _, _) => true,
+			Ascription::Template(_) => false,
+			Ascription::Array(element, _) => self.ascription_reference(element, visited),
+			Ascription::Structure(structure, templates) => templates.iter().any(|template|
+				self.ascription_reference(template, visited)) || self.structure_reference(structure, visited),
+		});
+		is_reference
+	}
+}
+
+/// Provides the lifetimes named in an ascription in order of appearance.
+fn lifetimes(ascription: &AscriptionPattern) -> Vec<Arc<str>> {
+	let mut lifetimes = Vec::new();
+	ascription.traverse(&mut |ascription| match &ascription.node {
+		Ascription::Reference(_, lifetime, ascription) => {
+			lifetimes.extend(lifetime.iter().map(|lifetime| lifetime.node.clone()));
+			lifetimes.extend(self::lifetimes(ascription));
+		}
+		Ascription::Structure(_, templates) => templates.iter()
+			.for_each(|template| lifetimes.extend(self::lifetimes(template))),
+		Ascription::Array(element, _) => lifetimes.extend(self::lifetimes(element)),
+		Ascription::Template(_) => (),
+	});
+	lifetimes
+}
+
+/// Provides the variables that are used in an expression.
+fn variables(function: &FunctionContext, expression: &ExpressionKey) -> HashSet<Variable> {
+	let mut variables = HashSet::new();
+	let mut queue = vec![*expression];
+	while let Some(expression) = queue.pop() {
+		match &function[&expression].node {
+			Expression::Block(block) => queue.extend(block),
+			Expression::Binding(_, _, value) => queue.push(*value),
+			Expression::TerminationLoop(condition_start, condition_end, body) => {
+				queue.extend(condition_start);
+				queue.extend(&[*condition_end, *body]);
+			}
+			Expression::Mutation(_, left, right) => queue.extend(&[*left, *right]),
+			Expression::ExplicitDrop(_, value) => queue.push(*value),
+			Expression::Unary(_, inner) => queue.push(*inner),
+			Expression::Binary(_, left, right) => queue.extend(&[*left, *right]),
+			Expression::Conditional(branches) => branches.iter()
+				.for_each(|(condition_start, condition_end, body)| {
+					queue.push(*condition_start);
+					queue.extend(condition_end);
+					queue.push(*body);
+				}),
+			Expression::Match(value, arms) => {
+				queue.push(*value);
+				queue.extend(arms.iter().map(|arm| arm.expression));
+			}
+			Expression::Field(receiver, _) => queue.push(*receiver),
+			Expression::Index(receiver, index) => queue.extend(&[*receiver, *index]),
+			Expression::MethodCall(receiver, _, arguments) => {
+				queue.push(*receiver);
+				queue.extend(arguments);
+			}
+			Expression::FunctionCall(_, arguments, _) => queue.extend(arguments),
+			Expression::Structure(_, fields) | Expression::Variant(_, _, fields) => queue.extend(fields.values()
+				.map(|(_, expression)| *expression)),
+			Expression::Array(elements) => queue.extend(elements),
+			Expression::Pattern(pattern) => pattern.traverse(&mut |element| queue.push(*element)),
+			Expression::Variable(variable) => { variables.insert(variable.clone()); }
+			Expression::Integer(_, _) | Expression::Truth(_) | Expression::Item(_) => (),
+		}
+	}
+	variables
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::error::CompileError;
+	use crate::source::{context, function_path};
+
+	use super::*;
+
+	fn check(source: &str) -> Result<(), BorrowError> {
+		let context = &context(source);
+		match crate::inference::function(context, &function_path("f")) {
+			Ok(_) => Ok(()),
+			Err(diagnostic) => match diagnostic.error.node {
+				CompileError::Borrow(error) => Err(error),
+				other => panic!("Expected borrow error, instead got: {:?}", other),
+			},
+		}
+	}
+
+	#[test]
+	fn test_conflict() {
+		let error = check("fn f() -> u32:\n\tlet ~x = 1u32\n\tlet a = ~&x\n\
+			\tlet b = ~&x\n\t(*a) + (*b)\n");
+		assert_eq!(error, Err(BorrowError::Conflict("x".into())));
+	}
+
+	#[test]
+	fn test_conflict_order() {
+		let error = check("fn f() -> u32:\n\tlet ~x = 1u32\n\tlet a = &x\n\
+			\tlet b = ~&x\n\t(*a) + (*b)\n");
+		assert_eq!(error, Err(BorrowError::Conflict("x".into())));
+		let error = check("fn f() -> u32:\n\tlet ~x = 1u32\n\tlet a = ~&x\n\
+			\tlet b = &x\n\t(*a) + (*b)\n");
+		assert_eq!(error, Err(BorrowError::Conflict("x".into())));
+	}
+
+	#[test]
+	fn test_shared_loans() {
+		assert_eq!(check("fn f() -> u32:\n\tlet ~x = 1u32\n\tlet a = &x\n\
+			\tlet b = &x\n\t(*a) + (*b)\n"), Ok(()));
+	}
+
+	#[test]
+	fn test_expired_loan() {
+		assert_eq!(check("fn f() -> u32:\n\tlet ~x = 1u32\n\tlet a = ~&x\n\
+			\t*a += 1\n\tlet b = &x\n\t*b\n"), Ok(()));
+	}
+
+	#[test]
+	fn test_use_while_borrowed() {
+		let error = check("fn f() -> u32:\n\tlet ~x = 1u32\n\tlet a = ~&x\n\
+			\tlet y = x\n\t(*a) + y\n");
+		assert_eq!(error, Err(BorrowError::Use("x".into())));
+	}
+
+	#[test]
+	fn test_drop_while_borrowed() {
+		let error = check("fn f() -> u32:\n\tlet ~x = 1u32\n\tlet a = &x\n\
+			\tdrop x = 1u32\n\t*a\n");
+		assert_eq!(error, Err(BorrowError::Drop("x".into())));
+	}
+
+	#[test]
+	fn test_escaping_variable() {
+		let error = check("fn f() -> &u32:\n\tlet x = 1u32\n\t&x\n");
+		assert_eq!(error, Err(BorrowError::EscapingVariable("x".into())));
+	}
+
+	#[test]
+	fn test_outlives() {
+		let source = "fn f(x: &'a u32, y: &'b u32) -> &'a u32:\n\tx\n";
+		assert_eq!(check(source), Ok(()));
+		let source = "fn f(x: &'a u32, y: &'b u32) -> &'b u32:\n\tx\n";
+		assert_eq!(check(source), Err(BorrowError::Outlives("a".into(), "b".into())));
+	}
+}
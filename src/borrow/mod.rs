@@ -0,0 +1,6 @@
+pub use borrow::BorrowError;
+use borrow::{Loan, LoanKey};
+pub use function::function;
+
+mod borrow;
+mod function;
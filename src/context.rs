@@ -1,3 +1,5 @@
+use parking_lot::RwLock;
+
 use crate::*;
 
 #[derive(Debug, Default)]
@@ -6,13 +8,42 @@ pub struct Context {
 	pub sources: source::Sources,
 	pub source_keys: source::SourceKeys,
 	pub module_contexts: declaration::ModuleContexts,
+	pub module_pending: declaration::ModulesPending,
 	pub declarations_function: declaration::DeclarationsFunction,
 	pub declarations_structure: declaration::DeclarationsStructure,
+	pub declarations_interface: declaration::DeclarationsInterface,
+	pub declarations_test: declaration::DeclarationsTest,
 	pub function_types: node::FunctionTypes,
 	pub node_functions: node::NodeFunctions,
 	pub node_structures: node::NodeStructures,
 	pub node_definitions: node::NodeDefinitions,
+	pub node_interfaces: node::NodeInterfaces,
+	pub node_implementations: node::NodeImplementations,
 	pub type_contexts: inference::TypeContexts,
 	pub partial_functions: evaluation::PartialFunctions,
 	pub basic_functions: basic::BasicFunctions,
+	pub arithmetic: RwLock<evaluation::ArithmeticMode>,
+}
+
+impl Context {
+	/// Removes all sources, declarations and lowered items.
+	pub fn clear(&self) {
+		self.sources.clear();
+		self.source_keys.clear();
+		self.module_contexts.write().clear();
+		self.module_pending.clear();
+		self.declarations_function.clear();
+		self.declarations_structure.clear();
+		self.declarations_interface.clear();
+		self.declarations_test.write().clear();
+		self.function_types.clear();
+		self.node_functions.clear();
+		self.node_structures.clear();
+		self.node_definitions.clear();
+		self.node_interfaces.clear();
+		self.node_implementations.clear();
+		self.type_contexts.clear();
+		self.partial_functions.clear();
+		self.basic_functions.clear();
+	}
 }
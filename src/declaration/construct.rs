@@ -4,7 +4,7 @@ use crate::error::Diagnostic;
 use crate::lexer::Token;
 use crate::span::{Span, Spanned};
 
-use super::{Declaration, DeclarationError, DeclarationPath, FunctionPath,
+use super::{Declaration, DeclarationError, DeclarationPath, FunctionPath, InterfacePath,
 	ModuleContext, ModulePending, SourceParse, StructurePath};
 
 const ROOT_FILE: &str = "main.lx";
@@ -27,6 +27,22 @@ impl<'a> SourceParse<'a> {
 		};
 	}
 
+	pub fn interface(&mut self, identifier: Arc<str>, declaration: Declaration, placement_span: Span) {
+		let module_path = self.current_module.clone();
+		let path = DeclarationPath { module_path, identifier };
+		let interface_path = Arc::new(InterfacePath(path));
+		let declarations = &self.context.declarations_interface;
+		match declarations.get(&interface_path) {
+			None => declarations.insert(interface_path, declaration),
+			Some(duplicate) => {
+				let location = duplicate.span().location(self.context);
+				let error = DeclarationError::DuplicateInterface(interface_path);
+				self.context.emit(Err(Diagnostic::new(Spanned::new(error, placement_span))
+					.note(format!("Duplicate declared in: {}", location))))
+			}
+		};
+	}
+
 	pub fn function(&mut self, identifier: Arc<str>, declaration: Declaration, placement_span: Span) {
 		let declarations = &self.context.declarations_function;
 		match self.is_definition {
@@ -34,6 +50,10 @@ impl<'a> SourceParse<'a> {
 				let module_path = self.current_module.clone();
 				let path = DeclarationPath { module_path, identifier };
 				let function_path = Arc::new(FunctionPath(path));
+				if std::mem::replace(&mut self.is_test, false) {
+					self.context.declarations_test.write().insert(function_path.clone());
+				}
+
 				match declarations.get(&function_path) {
 					None => return declarations.insert(function_path, declaration).unwrap_none(),
 					Some(duplicate) => {
@@ -50,10 +70,38 @@ impl<'a> SourceParse<'a> {
 		}
 	}
 
-	pub fn module(&mut self, identifier: Arc<str>, declaration_span: Span, placement_span: Span) -> Option<()> {
+	/// Parses an attribute that applies to the next function declaration.
+	pub fn attribute(&mut self) {
+		let token = self.lexer.next();
+		match token.node {
+			Token::Identifier(identifier) => match identifier.as_ref() {
+				"test" => self.is_test = true,
+				_ => {
+					let error = DeclarationError::UndefinedAttribute(identifier);
+					let _: Option<!> = self.context.emit(Err(Diagnostic::new(Spanned::new(error, token.span))));
+				}
+			},
+			_ => {
+				let error = Spanned::new(DeclarationError::ExpectedIdentifier, token.span);
+				let _: Option<!> = self.context.emit(Err(Diagnostic::new(error)));
+			}
+		}
+
+		if self.lexer.peek().node != Token::LineBreak {
+			let error = Spanned::new(DeclarationError::ExpectedConstructTerminator, self.lexer.peek().span);
+			let _: Option<!> = self.context.emit(Err(Diagnostic::new(error)));
+			self.skip_next_block();
+			self.is_test = false;
+		}
+	}
+
+	pub fn module(&mut self, identifier: Arc<str>, declaration_span: Span, placement_span: Span,
+	              documentation: Option<Arc<str>>) -> Option<()> {
+		let is_exported = std::mem::replace(&mut self.is_export, false);
 		match self.lexer.next().node {
-			Token::Separator => self.nested(identifier, placement_span),
-			Token::LineBreak => self.external(identifier, declaration_span, placement_span),
+			Token::Separator => self.nested(identifier, placement_span, documentation, is_exported),
+			Token::LineBreak => self.external(identifier, declaration_span,
+				placement_span, documentation, is_exported),
 			_ => {
 				let error = Spanned::new(DeclarationError::ExpectedConstructTerminator, placement_span);
 				self.context.emit(Err(Diagnostic::new(error)))
@@ -61,16 +109,19 @@ impl<'a> SourceParse<'a> {
 		}
 	}
 
-	fn nested(&mut self, identifier: Arc<str>, placement_span: Span) -> Option<()> {
+	fn nested(&mut self, identifier: Arc<str>, placement_span: Span,
+	          documentation: Option<Arc<str>>, is_exported: bool) -> Option<()> {
 		self.require_block();
 		self.item_indents.push(self.current_indent);
 		self.current_module = self.current_module.clone().push(identifier);
-		let module_context = ModuleContext::new(self.current_module.clone(), placement_span);
+		let module_context = ModuleContext::new(self.current_module.clone(),
+			placement_span, documentation, is_exported);
 		self.context.module_contexts.write().insert(self.current_module.clone(), module_context);
 		Some(())
 	}
 
-	fn external(&mut self, identifier: Arc<str>, declaration_span: Span, placement_span: Span) -> Option<()> {
+	fn external(&mut self, identifier: Arc<str>, declaration_span: Span, placement_span: Span,
+	            documentation: Option<Arc<str>>, is_exported: bool) -> Option<()> {
 		if self.current_module != self.module_path {
 			let error = Spanned::new(DeclarationError::NestedExternalModule, placement_span);
 			return self.context.emit(Err(Diagnostic::new(error)));
@@ -90,6 +141,8 @@ impl<'a> SourceParse<'a> {
 			expected_path: Arc::new(expected_path),
 			expected_module_path: Some(Arc::new(expected_module_path)),
 			declaration_span,
+			documentation,
+			is_exported,
 		})
 	}
 }
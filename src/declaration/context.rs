@@ -11,17 +11,21 @@ pub type ModuleContexts = RwLock<HashMap<Arc<ModulePath>, ModuleContext>>;
 
 #[derive(Debug)]
 pub struct ModuleContext {
+	pub module_path: Arc<ModulePath>,
 	pub inclusions: Vec<Spanned<Inclusion>>,
 	pub definitions: Vec<Definition>,
+	pub documentation: Option<Arc<str>>,
+	pub is_exported: bool,
 }
 
 impl ModuleContext {
-	pub fn new(module_path: Arc<ModulePath>, span: Span) -> Self {
+	pub fn new(module_path: Arc<ModulePath>, span: Span, documentation: Option<Arc<str>>,
+	           is_exported: bool) -> Self {
 		let mut inclusions = Vec::new();
 		let terminal = InclusionTerminal::Wildcard;
-		let inclusion = Inclusion { module_path, terminal };
+		let inclusion = Inclusion { module_path: module_path.clone(), terminal };
 		inclusions.push(Spanned::new(inclusion, span));
-		ModuleContext { inclusions, definitions: Vec::new() }
+		ModuleContext { module_path, inclusions, definitions: Vec::new(), documentation, is_exported }
 	}
 }
 
@@ -1,8 +1,10 @@
+use std::collections::HashSet;
 use std::fmt;
 use std::path::PathBuf;
 use std::sync::Arc;
 
 use chashmap::CHashMap;
+use parking_lot::RwLock;
 
 use crate::context::Context;
 use crate::error::{CompileError, Diagnostic};
@@ -10,10 +12,13 @@ use crate::extension::LineOffset;
 use crate::source::SourceKey;
 use crate::span::{Span, Spanned};
 
-use super::{FunctionPath, ModuleContext, ModulePath, StructurePath};
+use super::{FunctionPath, InterfacePath, ModuleContext, ModulePath, StructurePath};
 
 pub type DeclarationsFunction = CHashMap<Arc<FunctionPath>, Declaration>;
 pub type DeclarationsStructure = CHashMap<Arc<StructurePath>, Declaration>;
+pub type DeclarationsInterface = CHashMap<Arc<InterfacePath>, Declaration>;
+pub type DeclarationsTest = RwLock<HashSet<Arc<FunctionPath>>>;
+pub type ModulesPending = CHashMap<Arc<ModulePath>, ModulePending>;
 
 #[derive(Debug)]
 pub enum DeclarationError {
@@ -26,8 +31,12 @@ pub enum DeclarationError {
 	ExpectedDeclaration,
 	DuplicateFunction(Arc<FunctionPath>),
 	DuplicateStructure(Arc<StructurePath>),
+	DuplicateInterface(Arc<InterfacePath>),
 	ExpectedPathElement,
 	DefinitionItem,
+	UndefinedAttribute(Arc<str>),
+	AttributeTarget,
+	ExportTarget,
 }
 
 impl fmt::Display for DeclarationError {
@@ -46,15 +55,23 @@ impl fmt::Display for DeclarationError {
 			DeclarationError::ModuleDeclarationLocation =>
 				write!(f, "Module can only be declared in root or module file"),
 			DeclarationError::ExpectedDeclaration =>
-				write!(f, "Expected a module, function, or structure declaration"),
+				write!(f, "Expected a module, function, structure, or interface declaration"),
 			DeclarationError::DuplicateFunction(path) =>
 				write!(f, "Function: {}, has already been declared", path),
 			DeclarationError::DuplicateStructure(path) =>
 				write!(f, "Structure: {}, has already been declared", path),
+			DeclarationError::DuplicateInterface(path) =>
+				write!(f, "Interface: {}, has already been declared", path),
 			DeclarationError::ExpectedPathElement =>
 				write!(f, "Expected path element"),
 			DeclarationError::DefinitionItem =>
 				write!(f, "Structure definitions must only contain functions"),
+			DeclarationError::UndefinedAttribute(attribute) =>
+				write!(f, "Attribute: {}, is not defined", attribute),
+			DeclarationError::AttributeTarget =>
+				write!(f, "Attribute must precede a function declaration"),
+			DeclarationError::ExportTarget =>
+				write!(f, "Export must precede a module, function, structure, or interface declaration"),
 		}
 	}
 }
@@ -71,6 +88,8 @@ pub struct ModulePending {
 	pub expected_path: Arc<PathBuf>,
 	pub expected_module_path: Option<Arc<PathBuf>>,
 	pub declaration_span: Span,
+	pub documentation: Option<Arc<str>>,
+	pub is_exported: bool,
 }
 
 impl Into<Declaration> for ModulePending {
@@ -78,6 +97,8 @@ impl Into<Declaration> for ModulePending {
 		Declaration {
 			source: self.declaration_span.source,
 			line_offset: LineOffset(self.declaration_span.byte_start),
+			documentation: self.documentation,
+			is_exported: self.is_exported,
 		}
 	}
 }
@@ -86,6 +107,8 @@ impl Into<Declaration> for ModulePending {
 pub struct Declaration {
 	pub source: SourceKey,
 	pub line_offset: LineOffset,
+	pub documentation: Option<Arc<str>>,
+	pub is_exported: bool,
 }
 
 impl Declaration {
@@ -100,11 +123,21 @@ pub fn module_root(context: &Context, path: PathBuf) -> Option<()> {
 		expected_path: Arc::new(path),
 		expected_module_path: None,
 		declaration_span: Span::INTERNAL,
+		documentation: None,
+		is_exported: true,
 	})
 }
 
 /// Recursively loads all modules.
+/// Modules that are retained across a reload are not loaded again.
 pub fn load_module(context: &Context, module: ModulePending) -> Option<()> {
+	context.module_pending.insert(module.module_path.clone(), module.clone());
+	if let Some(module_context) = context.module_contexts.write().get_mut(&module.module_path) {
+		module_context.documentation = module.documentation;
+		module_context.is_exported = module.is_exported;
+		return Some(());
+	}
+
 	let mut sources = Vec::new();
 	let mut source_errors = Vec::new();
 
@@ -129,7 +162,8 @@ pub fn load_module(context: &Context, module: ModulePending) -> Option<()> {
 	}
 
 	context.module_contexts.write().insert(module.module_path.clone(),
-		ModuleContext::new(module.module_path.clone(), module.declaration_span)).unwrap_none();
+		ModuleContext::new(module.module_path.clone(), module.declaration_span,
+			module.documentation.clone(), module.is_exported)).unwrap_none();
 	sources.into_iter().try_for_each(|(source_key, physical_path)|
 		super::SourceParse::parse(context, module.module_path.clone(), module.declaration_span,
 			physical_path, source_key))
@@ -1,9 +1,8 @@
 pub use context::{Inclusion, InclusionTerminal, ModuleContext, ModuleContexts, Definition};
 pub use declaration::{Declaration, DeclarationError, DeclarationsFunction,
-	DeclarationsStructure, module_root};
-use declaration::{load_module, ModulePending};
+	DeclarationsInterface, DeclarationsStructure, DeclarationsTest, ModulePending, ModulesPending, load_module, module_root};
 use parser::SourceParse;
-pub use path::{DeclarationPath, FunctionPath, ModulePath, StructurePath};
+pub use path::{DeclarationPath, FunctionPath, InterfacePath, ModulePath, StructurePath};
 
 mod declaration;
 mod parser;
@@ -5,7 +5,7 @@ use std::sync::Arc;
 use crate::context::Context;
 use crate::error::Diagnostic;
 use crate::extension::{LineOffsets, StringExtension};
-use crate::lexer::{Lexer, Token};
+use crate::lexer::{Comment, Lexer, Token};
 use crate::source::SourceKey;
 use crate::span::{Span, Spanned};
 
@@ -19,11 +19,15 @@ pub struct SourceParse<'a> {
 	pub source_key: SourceKey,
 	pub lexer: Lexer<'a>,
 
+	string: &'a str,
+	trivia: Vec<Spanned<Comment>>,
 	line_offsets: LineOffsets,
 	pub item_indents: Vec<usize>,
 	pub current_module: Arc<ModulePath>,
 	pub current_indent: usize,
 	pub is_definition: bool,
+	pub is_test: bool,
+	pub is_export: bool,
 }
 
 impl<'a> SourceParse<'a> {
@@ -42,11 +46,15 @@ impl<'a> SourceParse<'a> {
 			physical_path,
 			source_key,
 			lexer,
+			string,
+			trivia: Vec::new(),
 			line_offsets: string.line_offsets(),
 			item_indents: Vec::new(),
 			current_module: module_path,
 			current_indent: 0,
 			is_definition: false,
+			is_test: false,
+			is_export: false,
 		}.traverse();
 		Some(())
 	}
@@ -61,22 +69,50 @@ impl<'a> SourceParse<'a> {
 	fn traverse(&mut self) {
 		loop {
 			let token = self.lexer.next();
+			self.trivia.extend(self.lexer.trivia());
 			self.handle_block_change(&token);
 			let (&line_offset, _) = self.line_offsets
 				.range(..=token.span.byte_start).next_back().unwrap();
 
+			match token.node {
+				Token::LineBreak | Token::Function | Token::Export => (),
+				_ if self.is_test => {
+					self.is_test = false;
+					let error = Spanned::new(DeclarationError::AttributeTarget, token.span);
+					let _: Option<!> = self.context.emit(Err(Diagnostic::new(error)));
+				}
+				_ => (),
+			}
+
+			match token.node {
+				Token::Module | Token::Function | Token::Data | Token::Interface => (),
+				_ if self.is_export => {
+					self.is_export = false;
+					let error = Spanned::new(DeclarationError::ExportTarget, token.span);
+					let _: Option<!> = self.context.emit(Err(Diagnostic::new(error)));
+				}
+				_ => (),
+			}
+
 			match token.node {
 				Token::End => break,
 				Token::BlockOpen | Token::BlockClose | Token::LineBreak => continue,
 				Token::Function => (),
+				Token::Export => {
+					self.is_export = true;
+					continue;
+				}
+				Token::Compile if !self.is_definition => {
+					self.attribute();
+					continue;
+				}
 				_ if self.is_definition => {
 					let error = Spanned::new(DeclarationError::DefinitionItem, token.span);
 					let _: Option<!> = self.context.emit(Err(Diagnostic::new(error)));
 					self.advance_until_break();
 					continue;
 				}
-				Token::Module | Token::Data => (),
-				Token::Export => continue,
+				Token::Module | Token::Data | Token::Interface => (),
 				Token::Use => {
 					if self.inclusion_root().is_none() {
 						self.advance_until_break();
@@ -84,7 +120,12 @@ impl<'a> SourceParse<'a> {
 					continue;
 				}
 				Token::Define => {
-					let declaration = Declaration { source: self.source_key, line_offset };
+					let declaration = Declaration {
+						source: self.source_key,
+						line_offset,
+						documentation: None,
+						is_exported: false,
+					};
 					self.module_context().definitions.push(Definition::new(declaration));
 					self.item_indents.push(self.current_indent);
 					self.advance_until_break();
@@ -106,17 +147,22 @@ impl<'a> SourceParse<'a> {
 					let error = Spanned::new(DeclarationError::ExpectedIdentifier, identifier_token.span);
 					let _: Option<!> = self.context.emit(Err(Diagnostic::new(error)));
 					self.advance_until_break();
+					self.is_export = false;
 					continue;
 				}
 			};
 
 			let placement_span = token.span.extend(identifier_token.span.byte_end);
+			let documentation = self.documentation(*line_offset);
 			match token.node {
-				Token::Data | Token::Function => {
-					let declaration = Declaration { source: self.source_key, line_offset };
+				Token::Data | Token::Function | Token::Interface => {
+					let is_exported = std::mem::replace(&mut self.is_export, false);
+					let declaration = Declaration { source: self.source_key,
+						line_offset, documentation, is_exported };
 					match token.node {
 						Token::Data => self.structure(identifier, declaration, placement_span),
 						Token::Function => self.function(identifier, declaration, placement_span),
+						Token::Interface => self.interface(identifier, declaration, placement_span),
 						_ => unreachable!(),
 					};
 
@@ -126,10 +172,43 @@ impl<'a> SourceParse<'a> {
 				Token::Module => {
 					let declaration_span = Span::new(self.source_key,
 						*line_offset, placement_span.byte_end);
-					self.module(identifier, declaration_span, placement_span)
+					self.module(identifier, declaration_span, placement_span, documentation)
 				}
 				_ => unreachable!(),
 			};
 		}
 	}
+
+	/// Takes the documentation comments on the lines directly preceding the line offset.
+	/// Attributes may be placed between the comments and the declaration.
+	fn documentation(&mut self, line_offset: usize) -> Option<Arc<str>> {
+		let (preceding, following) = std::mem::take(&mut self.trivia).into_iter()
+			.partition::<Vec<_>, _>(|comment| comment.span.byte_end <= line_offset);
+		self.trivia = following;
+
+		let mut lines = Vec::new();
+		let mut byte_end = line_offset;
+		for comment in preceding.iter().rev() {
+			let Span { byte_start, byte_end: comment_end, .. } = comment.span;
+			let line_start = self.string[..byte_start].rfind('\n').map(|index| index + 1).unwrap_or(0);
+			let is_adjacent = self.string[line_start..byte_start].trim().is_empty() &&
+				self.string[comment_end..byte_end].lines().map(str::trim)
+					.all(|line| line.is_empty() || line.starts_with('#'));
+
+			match &comment.node {
+				Comment::Documentation(line) if is_adjacent => lines.push(match line.starts_with(' ') {
+					true => &line[1..],
+					false => line,
+				}),
+				_ => break,
+			}
+			byte_end = line_start;
+		}
+
+		lines.reverse();
+		match lines.is_empty() {
+			true => None,
+			false => Some(lines.join("\n").into()),
+		}
+	}
 }
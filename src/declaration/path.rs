@@ -57,6 +57,12 @@ impl ModulePath {
 		}
 	}
 
+	/// Checks if the path is equal to or a descendant of the module path.
+	pub fn is_within(&self, module_path: &ModulePath) -> bool {
+		self == module_path || self.parent.as_ref()
+			.map(|parent| parent.is_within(module_path)).unwrap_or(false)
+	}
+
 	pub fn push(self: Arc<ModulePath>, identifier: Arc<str>) -> Arc<Self> {
 		Self::new(Some(self), identifier)
 	}
@@ -139,6 +145,12 @@ impl fmt::Debug for FunctionPath {
 #[derive(Clone, Hash, Eq, PartialEq)]
 pub struct StructurePath(pub DeclarationPath);
 
+impl StructurePath {
+	pub fn is_string(&self) -> bool {
+		self == &crate::intrinsic::Intrinsic::String.structure()
+	}
+}
+
 impl fmt::Display for StructurePath {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		let StructurePath(path) = self;
@@ -152,6 +164,22 @@ impl fmt::Debug for StructurePath {
 	}
 }
 
+#[derive(Clone, Hash, Eq, PartialEq)]
+pub struct InterfacePath(pub DeclarationPath);
+
+impl fmt::Display for InterfacePath {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let InterfacePath(path) = self;
+		write!(f, "{}", path)
+	}
+}
+
+impl fmt::Debug for InterfacePath {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "InterfacePath({})", self)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -168,4 +196,13 @@ mod tests {
 		let module = ModulePath::unresolved().push("crate".into()).push("element".into());
 		assert_eq!(module.tail(), ModulePath::root().push("element".into()));
 	}
+
+	#[test]
+	fn test_module_within() {
+		let module = ModulePath::root().push("first".into());
+		let child = module.clone().push("second".into());
+		assert!(child.is_within(&module));
+		assert!(module.is_within(&module));
+		assert!(!module.is_within(&child));
+	}
 }
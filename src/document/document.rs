@@ -0,0 +1,287 @@
+use std::fmt::Write;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::context::Context;
+use crate::declaration::{Declaration, FunctionPath, InterfacePath, ModulePath, StructurePath};
+use crate::error::Diagnostic;
+use crate::span::Spanned;
+
+/// Writes a page for every module and an index of the modules into the directory.
+/// Returns true if no diagnostics were emitted.
+pub fn document(context: &Context, directory: &Path) -> bool {
+	let errors = std::mem::replace(context.errors.write().as_mut(), Vec::new());
+	if !errors.is_empty() {
+		errors.iter().for_each(|diagnostic| eprint!("{}", crate::error::string(context, diagnostic)));
+		return false;
+	}
+
+	if let Err(error) = std::fs::create_dir_all(directory) {
+		eprintln!("Directory at: {}, could not be created: {}", directory.display(), error);
+		return false;
+	}
+
+	let mut modules: Vec<_> = context.module_contexts.read().keys().cloned().collect();
+	modules.sort_by_key(|module_path| module_path.to_string());
+
+	let mut pages = vec![("index.html".to_owned(), index(context, &modules))];
+	for module_path in &modules {
+		match module(context, module_path, &modules) {
+			Ok(page) => pages.push((file_name(module_path), page)),
+			Err(diagnostic) => {
+				let _: Option<!> = context.emit(Err(diagnostic));
+			}
+		}
+	}
+
+	let mut success = true;
+	for (file_name, page) in pages {
+		let path = directory.join(file_name);
+		match std::fs::write(&path, page) {
+			Ok(()) => println!("Documented: {}", path.display()),
+			Err(error) => {
+				eprintln!("File at: {}, could not be written: {}", path.display(), error);
+				success = false;
+			}
+		}
+	}
+
+	let errors = std::mem::replace(context.errors.write().as_mut(), Vec::new());
+	errors.iter().for_each(|diagnostic| eprint!("{}", crate::error::string(context, diagnostic)));
+	success && errors.is_empty()
+}
+
+fn index(context: &Context, modules: &[Arc<ModulePath>]) -> String {
+	let mut page = header("Modules");
+	page += "<h1>Modules</h1>\n<ul>\n";
+	for module_path in modules {
+		let module_contexts = context.module_contexts.read();
+		let summary = module_contexts[module_path].documentation.as_ref()
+			.and_then(|documentation| documentation.lines().next().map(escape))
+			.map(|summary| format!(" - {}", summary)).unwrap_or_default();
+		writeln!(page, "<li><a href=\"{}\"><code>{}</code></a>{}</li>", file_name(module_path),
+			escape(&module_path.to_string()), summary).unwrap();
+	}
+
+	page += "</ul>\n</body>\n</html>\n";
+	page
+}
+
+fn module(context: &Context, module_path: &Arc<ModulePath>,
+          modules: &[Arc<ModulePath>]) -> Result<String, Diagnostic> {
+	let mut page = header(&module_path.to_string());
+	writeln!(page, "<h1>Module <code>{}</code></h1>", escape(&module_path.to_string())).unwrap();
+	documentation(&mut page, &context.module_contexts.read()[module_path].documentation);
+
+	let children: Vec<_> = modules.iter().filter(|child|
+		child.parent.as_ref() == Some(module_path)).collect();
+	if !children.is_empty() {
+		page += "<h2>Modules</h2>\n<ul>\n";
+		for child in children {
+			writeln!(page, "<li><a href=\"{}\"><code>{}</code></a></li>",
+				file_name(child), escape(&child.identifier)).unwrap();
+		}
+		page += "</ul>\n";
+	}
+
+	let mut structures: Vec<_> = context.declarations_structure.clone().into_iter()
+		.filter(|(path, _)| &(path.0).module_path == module_path).collect();
+	structures.sort_by_key(|(path, _)| (path.0).identifier.clone());
+	if !structures.is_empty() {
+		page += "<h2>Structures</h2>\n";
+	}
+
+	for (path, declaration) in structures {
+		let structure = crate::parser::structure(context, &Spanned::new(path.clone(), declaration.span()))?;
+		let StructurePath(path) = path.as_ref();
+		writeln!(page, "<h3 id=\"{0}\"><code>data {0}{1}</code></h3>",
+			escape(&path.identifier), escape(&templates(&structure.templates))).unwrap();
+		documentation(&mut page, &declaration.documentation);
+
+		page += "<ul>\n";
+		for (field, ascription) in crate::format::fields(&structure.fields) {
+			writeln!(page, "<li><code>{}: {}</code></li>", escape(field),
+				escape(&crate::format::ascription(ascription))).unwrap();
+		}
+
+		for variant in &structure.variants {
+			writeln!(page, "<li><code>| {}</code></li>", escape(&crate::format::variant(variant))).unwrap();
+		}
+		page += "</ul>\n";
+	}
+
+	let mut interfaces: Vec<_> = context.declarations_interface.clone().into_iter()
+		.filter(|(path, _)| &(path.0).module_path == module_path).collect();
+	interfaces.sort_by_key(|(path, _)| (path.0).identifier.clone());
+	if !interfaces.is_empty() {
+		page += "<h2>Interfaces</h2>\n";
+	}
+
+	for (path, declaration) in interfaces {
+		let source = declaration.source.get(context);
+		let string = source.read_string().map_err(|error|
+			Diagnostic::new(Spanned::new(error, declaration.span())))?;
+		let interface = crate::parser::interface(context, &Spanned::new(path.clone(), declaration.span()))?;
+		let InterfacePath(path) = path.as_ref();
+		writeln!(page, "<h3 id=\"{0}\"><code>interface {0}</code></h3>", escape(&path.identifier)).unwrap();
+		documentation(&mut page, &declaration.documentation);
+
+		page += "<ul>\n";
+		for (identifier, function_type) in &interface.methods {
+			let signature = crate::format::signature(&identifier.node, function_type, string);
+			writeln!(page, "<li><code>{}</code></li>", escape(&signature)).unwrap();
+		}
+		page += "</ul>\n";
+	}
+
+	let module_contexts = context.module_contexts.read();
+	let definitions = &module_contexts[module_path].definitions;
+	if !definitions.is_empty() {
+		page += "<h2>Definitions</h2>\n";
+	}
+
+	for definition in definitions {
+		let node_definition = crate::parser::definition(context,
+			module_path.clone(), &definition.declaration)?;
+		let StructurePath(structure) = &node_definition.structure.node;
+		let interface = node_definition.interface.as_ref().map(|interface| {
+			let InterfacePath(path) = &interface.node;
+			format!("{} for ", crate::format::path(path))
+		}).unwrap_or_default();
+		writeln!(page, "<h3><code>define {}{}{}</code></h3>", escape(&interface),
+			escape(&crate::format::path(structure)), escape(&templates(&node_definition.templates))).unwrap();
+
+		for (_, method) in &definition.methods {
+			let method = &method.node;
+			let path = context.declarations_function.clone().into_iter()
+				.find(|(_, declaration)| declaration.source == method.source &&
+					declaration.line_offset == method.line_offset).map(|(path, _)| path);
+			if let Some(path) = path {
+				function(context, &mut page, &path, method, "h4")?;
+			}
+		}
+	}
+
+	let mut functions: Vec<_> = context.declarations_function.clone().into_iter()
+		.filter(|(path, _)| &(path.0).module_path == module_path &&
+			!context.node_definitions.contains_key(path)).collect();
+	functions.sort_by_key(|(path, _)| (path.0).identifier.clone());
+	if !functions.is_empty() {
+		page += "<h2>Functions</h2>\n";
+	}
+
+	for (path, declaration) in functions {
+		function(context, &mut page, &path, &declaration, "h3")?;
+	}
+
+	page += "</body>\n</html>\n";
+	Ok(page)
+}
+
+fn function(context: &Context, page: &mut String, path: &Arc<FunctionPath>,
+            declaration: &Declaration, heading: &str) -> Result<(), Diagnostic> {
+	let function_path = Spanned::new(path.clone(), declaration.span());
+	let function_type = crate::parser::function_type(context, &function_path)?;
+	let source = declaration.source.get(context);
+	let string = source.read_string().map_err(|error|
+		Diagnostic::new(Spanned::new(error, declaration.span())))?;
+
+	let FunctionPath(path) = path.as_ref();
+	let signature = crate::format::signature(&path.identifier, &function_type, string);
+	writeln!(page, "<{0} id=\"{1}\"><code>{2}</code></{0}>", heading,
+		escape(&path.identifier), escape(&signature)).unwrap();
+	documentation(page, &declaration.documentation);
+	Ok(())
+}
+
+/// Writes each paragraph of the documentation.
+fn documentation(page: &mut String, documentation: &Option<Arc<str>>) {
+	if let Some(documentation) = documentation {
+		documentation.split("\n\n").map(str::trim).filter(|paragraph| !paragraph.is_empty())
+			.for_each(|paragraph| writeln!(page, "<p>{}</p>", escape(paragraph)).unwrap());
+	}
+}
+
+fn header(title: &str) -> String {
+	format!("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+		<title>{}</title>\n</head>\n<body>\n", escape(title))
+}
+
+fn templates(templates: &[Spanned<Arc<str>>]) -> String {
+	match templates.is_empty() {
+		true => String::new(),
+		false => format!("<{}>", templates.iter().map(|template|
+			format!("${}", template.node)).collect::<Vec<_>>().join(", ")),
+	}
+}
+
+fn file_name(module_path: &ModulePath) -> String {
+	format!("{}.html", module_path.to_string().replace("::", "."))
+}
+
+fn escape(string: &str) -> String {
+	string.chars().fold(String::new(), |mut escaped, character| {
+		match character {
+			'&' => escaped.push_str("&amp;"),
+			'<' => escaped.push_str("&lt;"),
+			'>' => escaped.push_str("&gt;"),
+			'"' => escaped.push_str("&quot;"),
+			_ => escaped.push(character),
+		}
+		escaped
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::source::TemporaryDirectory;
+
+	use super::*;
+
+	const SOURCE: &str = "/// A point in the plane.\n///\n/// Coordinates are <unsigned> & never \"negative\".\ndata Point:\n\tx: u32,\n\ty: u32,\n\n\
+		/// Shapes with an area.\ninterface Area:\n\tfn area(&self) -> u32\n\n\
+		define Area for Point:\n\t/// Product of the coordinates.\n\tfn area(&self) -> u32:\n\t\tself.x * self.y\n\n\
+		define Point:\n\tfn scale(~&self, factor: u32):\n\t\tself.x *= factor\n\n\
+		/// Provides the first element.\nfn first<$T>(pair: &[$T; 2]) -> &$T:\n\t&pair[0]\n\n\
+		/// Nested module.\nmodule inner:\n\tfn one() -> u32:\n\t\t1\n";
+
+	#[test]
+	fn test_module() {
+		let context = &crate::source::context(SOURCE);
+		let modules: Vec<_> = context.module_contexts.read().keys().cloned().collect();
+		let page = module(context, &ModulePath::root(), &modules).unwrap();
+
+		assert!(page.contains("<li><a href=\"crate.inner.html\"><code>inner</code></a></li>"));
+		assert!(page.contains("<h3 id=\"Point\"><code>data Point</code></h3>\n\
+			<p>A point in the plane.</p>\n<p>Coordinates are &lt;unsigned&gt; &amp; \
+			never &quot;negative&quot;.</p>\n<ul>\n<li><code>x: u32</code></li>\n"));
+		assert!(page.contains("<p>Shapes with an area.</p>\n<ul>\n\
+			<li><code>fn area(&amp;self) -&gt; u32</code></li>\n"));
+		assert!(page.contains("<h3><code>define Area for Point</code></h3>\n\
+			<h4 id=\"area\"><code>fn area(&amp;self) -&gt; u32</code></h4>\n\
+			<p>Product of the coordinates.</p>\n<h3><code>define Point</code></h3>\n\
+			<h4 id=\"scale\"><code>fn scale(~&amp;self, factor: u32)</code></h4>\n"));
+		assert!(page.contains("<h2>Functions</h2>\n<h3 id=\"first\">\
+			<code>fn first&lt;$T&gt;(pair: &amp;[$T; 2]) -&gt; &amp;$T</code></h3>\n\
+			<p>Provides the first element.</p>\n"));
+		assert!(!page.contains("one"));
+	}
+
+	#[test]
+	fn test_document() {
+		let directory = TemporaryDirectory::new(&[("main.lx", SOURCE)]);
+		let output = directory.0.join("doc");
+		assert!(document(&directory.context(), &output));
+
+		let index = std::fs::read_to_string(output.join("index.html")).unwrap();
+		assert!(index.contains("<li><a href=\"crate.inner.html\"><code>crate::inner</code></a> \
+			- Nested module.</li>"));
+		let inner = std::fs::read_to_string(output.join("crate.inner.html")).unwrap();
+		assert!(inner.contains("<p>Nested module.</p>") && inner.contains("<code>fn one() -&gt; u32</code>"));
+	}
+
+	#[test]
+	fn test_escape() {
+		assert_eq!(escape("<&\"'>"), "&lt;&amp;&quot;'&gt;");
+	}
+}
@@ -0,0 +1,3 @@
+pub use document::document;
+
+mod document;
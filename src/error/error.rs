@@ -42,18 +42,37 @@ impl Context {
 pub enum CompileError {
 	Source(source::SourceError),
 	Declaration(declaration::DeclarationError),
+	Lexer(lexer::LexerError),
 	Inference(inference::InferenceError),
+	Borrow(borrow::BorrowError),
 	Parser(parser::ParserError),
 	Node(node::NodeError),
 	Evaluation(evaluation::EvaluationError),
 }
 
+impl CompileError {
+	pub fn category(&self) -> &'static str {
+		match self {
+			CompileError::Source(_) => "Source",
+			CompileError::Declaration(_) => "Declaration",
+			CompileError::Lexer(_) => "Lexer",
+			CompileError::Inference(_) => "Inference",
+			CompileError::Borrow(_) => "Borrow",
+			CompileError::Parser(_) => "Parser",
+			CompileError::Node(_) => "Node",
+			CompileError::Evaluation(_) => "Evaluation",
+		}
+	}
+}
+
 impl fmt::Display for CompileError {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		match self {
 			CompileError::Source(error) => write!(f, "{}", error),
 			CompileError::Declaration(error) => write!(f, "{}", error),
+			CompileError::Lexer(error) => write!(f, "{}", error),
 			CompileError::Inference(error) => write!(f, "{}", error),
+			CompileError::Borrow(error) => write!(f, "{}", error),
 			CompileError::Parser(error) => write!(f, "{}", error),
 			CompileError::Node(error) => write!(f, "{}", error),
 			CompileError::Evaluation(error) => write!(f, "{}", error),
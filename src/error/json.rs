@@ -0,0 +1,87 @@
+use std::fmt::{Result, Write};
+
+use crate::context::Context;
+use crate::error::Diagnostic;
+use crate::extension::StringExtension;
+use crate::source::SourceKey;
+use crate::span::Span;
+
+/// Renders a diagnostic as a single line JSON object.
+pub fn json(context: &Context, diagnostic: &Diagnostic) -> String {
+	let mut string = String::new();
+	display(&mut string, context, diagnostic)
+		.expect("Failed to write diagnostic to string");
+	string
+}
+
+fn display(write: &mut dyn Write, context: &Context, diagnostic: &Diagnostic) -> Result {
+	let error = &diagnostic.error;
+	write!(write, "{{\"category\":\"{}\",", error.node.category())?;
+	write!(write, "\"message\":\"{}\",", escape(&error.node.to_string()))?;
+	display_span(write, context, error.span)?;
+
+	write!(write, ",\"notes\":[")?;
+	diagnostic.notes.iter().enumerate().try_for_each(|(index, note)| match index {
+		0 => write!(write, "\"{}\"", escape(note)),
+		_ => write!(write, ",\"{}\"", escape(note)),
+	})?;
+	writeln!(write, "]}}")
+}
+
+fn display_span(write: &mut dyn Write, context: &Context, span: Span) -> Result {
+	if span.source == SourceKey::INTERNAL {
+		return write!(write, "\"path\":null,\"span\":null");
+	}
+
+	let source = span.source.get(context);
+	let path = escape(&source.path.display().to_string());
+	write!(write, "\"path\":\"{}\",", path)?;
+
+	let string = match source.read_string() {
+		Ok(string) => string,
+		Err(_) => return write!(write, "\"span\":null"),
+	};
+
+	let line_offsets = &string.line_offsets();
+	let position = |byte_offset: usize| {
+		let (&line_offset, &line) = line_offsets.range(..=byte_offset).next_back().unwrap();
+		let column = string.get(*line_offset..byte_offset)
+			.map(|slice| slice.chars().count()).unwrap_or(0);
+		(line, column + 1)
+	};
+
+	let (line_start, column_start) = position(span.byte_start);
+	let (line_end, column_end) = position(span.byte_end);
+	write!(write, "\"span\":{{\"byte_start\":{},\"byte_end\":{},", span.byte_start, span.byte_end)?;
+	write!(write, "\"line_start\":{},\"column_start\":{},", line_start, column_start)?;
+	write!(write, "\"line_end\":{},\"column_end\":{}}}", line_end, column_end)
+}
+
+/// Escapes the characters of a string for a JSON string literal.
+pub fn escape(string: &str) -> String {
+	string.chars().fold(String::new(), |mut string, character| {
+		match character {
+			'"' => string.push_str("\\\""),
+			'\\' => string.push_str("\\\\"),
+			'\n' => string.push_str("\\n"),
+			'\t' => string.push_str("\\t"),
+			'\r' => string.push_str("\\r"),
+			character if character.is_control() =>
+				string.push_str(&format!("\\u{:04x}", character as u32)),
+			character => string.push(character),
+		}
+		string
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_escape() {
+		assert_eq!(escape("plain"), "plain");
+		assert_eq!(escape("\"quote\"\n"), "\\\"quote\\\"\\n");
+		assert_eq!(escape("\\\t\u{1}"), "\\\\\\t\\u0001");
+	}
+}
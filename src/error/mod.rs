@@ -1,5 +1,7 @@
 pub use display::string;
 pub use error::{CompileError, Diagnostic, Errors};
+pub use json::{escape, json};
 
 mod error;
 mod display;
+mod json;
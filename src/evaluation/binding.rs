@@ -1,83 +1,223 @@
-use crate::basic::{Compound, Direction, Item, Value};
+use std::fmt;
+
+use crate::basic::{Compound, Direction, Integer, Item, Value};
 use crate::node::{Arithmetic, BinaryOperator, UnaryOperator, Variable};
 
-use super::{EvaluationError, EvaluationItem, ValueContext};
+use super::{ArithmeticMode, EvaluationError, EvaluationItem, ValueContext};
 
 macro_rules! binary_integer {
-    ($operator:expr, $left:expr, $right:expr, $identifier:ident) => {
+    ($operator:expr, $mode:expr, $left:expr, $right:expr, $identifier:ident) => {
         if let (Item::$identifier(left), Item::$identifier(right)) = ($left, $right) {
+		   let overflow = || EvaluationError::ArithmeticOverflow(format!("{} {} {}", left, $operator, right));
 		   return Ok(EvaluationItem::Item(match $operator {
 				BinaryOperator::GreaterThan => Item::Truth(left > right),
 				BinaryOperator::GreaterEqual => Item::Truth(left >= right),
 				BinaryOperator::LessThan => Item::Truth(left < right),
 				BinaryOperator::LessEqual => Item::Truth(left <= right),
 				BinaryOperator::Equality => Item::Truth(left == right),
+				BinaryOperator::NotEqual => Item::Truth(left != right),
+				BinaryOperator::And | BinaryOperator::Or =>
+					panic!("Invalid operation: {:?}, on integer values", $operator),
 				BinaryOperator::Arithmetic(operator) => Item::$identifier(match operator {
-					Arithmetic::Add => left.wrapping_add(*right),
-					Arithmetic::Minus => left.wrapping_sub(*right),
-					Arithmetic::Multiply => left.checked_mul(*right)
-						.ok_or(EvaluationError::ArithmeticOverflow)?,
+					Arithmetic::Add => arithmetic_mode!($mode, overflow,
+						left.checked_add(*right), left.wrapping_add(*right)),
+					Arithmetic::Minus => arithmetic_mode!($mode, overflow,
+						left.checked_sub(*right), left.wrapping_sub(*right)),
+					Arithmetic::Multiply => arithmetic_mode!($mode, overflow,
+						left.checked_mul(*right), left.wrapping_mul(*right)),
+					Arithmetic::Divide | Arithmetic::Modulo if *right == 0 =>
+						return Err(EvaluationError::DivisionByZero),
+					Arithmetic::Divide => arithmetic_mode!($mode, overflow,
+						left.checked_div(*right), left.wrapping_div(*right)),
+					Arithmetic::Modulo => arithmetic_mode!($mode, overflow,
+						left.checked_rem(*right), left.wrapping_rem(*right)),
+					Arithmetic::BitAnd => left & right,
+					Arithmetic::BitOr => left | right,
+					Arithmetic::BitXor => left ^ right,
+					Arithmetic::ShiftLeft => arithmetic_mode!($mode, overflow,
+						left.checked_shl(*right as u32), left.wrapping_shl(*right as u32)),
+					Arithmetic::ShiftRight => arithmetic_mode!($mode, overflow,
+						left.checked_shr(*right as u32), left.wrapping_shr(*right as u32)),
 				}),
 		   }));
         }
     };
 }
 
-pub fn binding(context: &mut ValueContext, direction: Direction,
+pub fn binding(context: &mut ValueContext, mode: ArithmeticMode, direction: Direction,
                variable: &Variable, compound: &Compound) -> Result<(), EvaluationError> {
 	Ok(match direction {
 		Direction::Advance => {
-			let item = item(context, compound)?;
+			let item = item(context, mode, compound)?;
 			context.frame().items.insert(variable.clone(), item);
 		}
 		Direction::Reverse => {
 			if let Compound::Value(Value::Location(location)) = compound {
-				*context.location(location) = context.frame().items[variable].clone();
+				*context.location(location)? = context.frame().items[variable].clone();
 			}
 		}
 	})
 }
 
-fn item(context: &mut ValueContext, compound: &Compound) -> Result<EvaluationItem, EvaluationError> {
+fn item(context: &mut ValueContext, mode: ArithmeticMode,
+        compound: &Compound) -> Result<EvaluationItem, EvaluationError> {
 	Ok(match compound {
-		Compound::Value(value) => context.value(value),
+		Compound::Value(value) => context.value(value)?,
 		Compound::Unary(operator, value) => match operator {
-			UnaryOperator::Dereference => match context.value(value) {
+			UnaryOperator::Dereference => match context.value(value)? {
 				EvaluationItem::Reference(frame, location) =>
-					context.values.dereference(frame, &location).clone(),
+					context.values.dereference(frame, &location)?.clone(),
 				_ => panic!("Cannot dereference item that is not reference"),
 			}
 			UnaryOperator::Reference(_) => match value {
-				Value::Location(location) =>
-					EvaluationItem::Reference(context.frame_index(), location.clone()),
+				Value::Location(location) => {
+					let frame = context.frame_index();
+					let location = context.resolve(frame, location).into_owned();
+					EvaluationItem::Reference(frame, location)
+				}
 				Value::Item(_) => panic!("Cannot reference item immediate"),
 			}
-			UnaryOperator::Negate => EvaluationItem::Item(match context.item(value) {
-				Item::Signed8(integer) => Item::Signed8(-integer),
-				Item::Signed16(integer) => Item::Signed16(-integer),
-				Item::Signed32(integer) => Item::Signed32(-integer),
-				Item::Signed64(integer) => Item::Signed64(-integer),
-				_ => panic!("Negation on invalid value"),
-			})
+			UnaryOperator::Negate => {
+				let overflow = |integer: &dyn fmt::Display|
+					EvaluationError::ArithmeticOverflow(format!("-({})", integer));
+				EvaluationItem::Item(match context.item(value)? {
+					Item::Signed8(integer) => Item::Signed8(arithmetic_mode!(mode,
+						|| overflow(&integer), integer.checked_neg(), integer.wrapping_neg())),
+					Item::Signed16(integer) => Item::Signed16(arithmetic_mode!(mode,
+						|| overflow(&integer), integer.checked_neg(), integer.wrapping_neg())),
+					Item::Signed32(integer) => Item::Signed32(arithmetic_mode!(mode,
+						|| overflow(&integer), integer.checked_neg(), integer.wrapping_neg())),
+					Item::Signed64(integer) => Item::Signed64(arithmetic_mode!(mode,
+						|| overflow(&integer), integer.checked_neg(), integer.wrapping_neg())),
+					Item::Signed128(integer) => Item::Signed128(arithmetic_mode!(mode,
+						|| overflow(&integer), integer.checked_neg(), integer.wrapping_neg())),
+					Item::Integer(integer) => Item::Integer(-&integer),
+					_ => panic!("Negation on invalid value"),
+				})
+			}
+			UnaryOperator::Not => EvaluationItem::Item(match context.item(value)? {
+				Item::Truth(truth) => Item::Truth(!truth),
+				_ => panic!("Logical negation on invalid value"),
+			}),
 		},
 		Compound::Binary(operator, left, right) => {
-			let (left, right) = (&context.item(left), &context.item(right));
-			binary_integer!(operator, left, right, Unsigned8);
-			binary_integer!(operator, left, right, Unsigned16);
-			binary_integer!(operator, left, right, Unsigned32);
-			binary_integer!(operator, left, right, Unsigned64);
-			binary_integer!(operator, left, right, Signed8);
-			binary_integer!(operator, left, right, Signed16);
-			binary_integer!(operator, left, right, Signed32);
-			binary_integer!(operator, left, right, Signed64);
+			let (left, right) = (&context.item(left)?, &context.item(right)?);
+			binary_integer!(operator, mode, left, right, Unsigned8);
+			binary_integer!(operator, mode, left, right, Unsigned16);
+			binary_integer!(operator, mode, left, right, Unsigned32);
+			binary_integer!(operator, mode, left, right, Unsigned64);
+			binary_integer!(operator, mode, left, right, Signed8);
+			binary_integer!(operator, mode, left, right, Signed16);
+			binary_integer!(operator, mode, left, right, Signed32);
+			binary_integer!(operator, mode, left, right, Signed64);
+			binary_integer!(operator, mode, left, right, Unsigned128);
+			binary_integer!(operator, mode, left, right, Signed128);
 			EvaluationItem::Item(match (left, right) {
+				(Item::Integer(left), Item::Integer(right)) => arbitrary(operator, left, right)?,
+				(Item::Character(left), Item::Character(right)) => text(operator, left, right),
+				(Item::String(left), Item::String(right)) => text(operator, left, right),
 				(Item::Truth(left), Item::Truth(right)) => match operator {
 					BinaryOperator::Equality => Item::Truth(left == right),
+					BinaryOperator::NotEqual => Item::Truth(left != right),
+					BinaryOperator::And => Item::Truth(*left && *right),
+					BinaryOperator::Or => Item::Truth(*left || *right),
+					BinaryOperator::Arithmetic(Arithmetic::BitAnd) => Item::Truth(left & right),
+					BinaryOperator::Arithmetic(Arithmetic::BitOr) => Item::Truth(left | right),
+					BinaryOperator::Arithmetic(Arithmetic::BitXor) => Item::Truth(left ^ right),
 					_ => panic!("Invalid operation: {:?}, on truth values", operator),
 				}
 				_ => panic!("Invalid binary operation on items: {:?}, and: {:?}", left, right)
 			})
 		}
-		Compound::FunctionCall(_, _) => unreachable!(),
+		Compound::Length(value) => EvaluationItem::Item(match context.item(value)? {
+			Item::String(string) => Item::Unsigned64(string.len() as u64),
+			_ => panic!("Length on invalid value"),
+		}),
+		Compound::FunctionCall(_, _, _) => unreachable!(),
+	})
+}
+
+/// Evaluates a comparison on characters or strings.
+fn text<T: PartialOrd, I>(operator: &BinaryOperator, left: &T, right: &T) -> Item<I> {
+	match operator {
+		BinaryOperator::GreaterThan => Item::Truth(left > right),
+		BinaryOperator::GreaterEqual => Item::Truth(left >= right),
+		BinaryOperator::LessThan => Item::Truth(left < right),
+		BinaryOperator::LessEqual => Item::Truth(left <= right),
+		BinaryOperator::Equality => Item::Truth(left == right),
+		BinaryOperator::NotEqual => Item::Truth(left != right),
+		_ => panic!("Invalid operation: {:?}, on text values", operator),
+	}
+}
+
+/// Evaluates an operation on arbitrary precision integers which cannot overflow.
+fn arbitrary<T>(operator: &BinaryOperator, left: &Integer, right: &Integer) -> Result<Item<T>, EvaluationError> {
+	let shift = || right.to_u32().ok_or_else(|| EvaluationError::ArithmeticOverflow(format!("{} {} {}", left, operator, right)));
+	Ok(match operator {
+		BinaryOperator::GreaterThan => Item::Truth(left > right),
+		BinaryOperator::GreaterEqual => Item::Truth(left >= right),
+		BinaryOperator::LessThan => Item::Truth(left < right),
+		BinaryOperator::LessEqual => Item::Truth(left <= right),
+		BinaryOperator::Equality => Item::Truth(left == right),
+		BinaryOperator::NotEqual => Item::Truth(left != right),
+		BinaryOperator::And | BinaryOperator::Or =>
+			panic!("Invalid operation: {:?}, on integer values", operator),
+		BinaryOperator::Arithmetic(arithmetic) => Item::Integer(match arithmetic {
+			Arithmetic::Add => left + right,
+			Arithmetic::Minus => left - right,
+			Arithmetic::Multiply => left * right,
+			Arithmetic::Divide => left.checked_div_rem(right)
+				.ok_or(EvaluationError::DivisionByZero)?.0,
+			Arithmetic::Modulo => left.checked_div_rem(right)
+				.ok_or(EvaluationError::DivisionByZero)?.1,
+			Arithmetic::BitAnd => left & right,
+			Arithmetic::BitOr => left | right,
+			Arithmetic::BitXor => left ^ right,
+			Arithmetic::ShiftLeft => left << shift()?,
+			Arithmetic::ShiftRight => left >> shift()?,
+		}),
 	})
 }
+
+#[cfg(test)]
+mod tests {
+	use crate::context::Context;
+	use crate::error::{CompileError, Diagnostic};
+	use crate::source::{context, function_path};
+
+	use super::*;
+
+	const SOURCE: &str = "fn add(x: u8) -> u8:\n\tx + 1\n\n\
+		fn shift(x: u8) -> u8:\n\tx << 9\n\nfn negate(x: i8) -> i8:\n\t-x\n\n\
+		fn maximum() -> u128:\n\t0xffffffffffffffffffffffffffffffff\n";
+
+	fn evaluate(context: &Context, function: &str, arguments: Vec<Item>) -> Result<Item, Diagnostic> {
+		let item = crate::evaluation::function(context, &function_path(function), arguments)?;
+		Ok(item.collapse().unwrap())
+	}
+
+	#[test]
+	fn test_arithmetic_overflow() {
+		let context = &context(SOURCE);
+		let cases = vec![("add", Item::Unsigned8(255)), ("shift", Item::Unsigned8(1)),
+			("negate", Item::Signed8(-128))];
+		for (function, argument) in cases {
+			match evaluate(context, function, vec![argument]).map_err(|diagnostic| diagnostic.error.node) {
+				Err(CompileError::Evaluation(EvaluationError::ArithmeticOverflow(_))) => (),
+				other => panic!("Expected overflow for: {}, instead got: {:?}", function, other),
+			}
+		}
+
+		let maximum = evaluate(context, "maximum", Vec::new()).unwrap();
+		assert_eq!(maximum, Item::Unsigned128(u128::max_value()));
+	}
+
+	#[test]
+	fn test_wrapping_arithmetic() {
+		let context = &context(SOURCE);
+		*context.arithmetic.write() = ArithmeticMode::Wrapping;
+		assert_eq!(evaluate(context, "add", vec![Item::Unsigned8(255)]).unwrap(), Item::Unsigned8(0));
+		assert_eq!(evaluate(context, "shift", vec![Item::Unsigned8(1)]).unwrap(), Item::Unsigned8(2));
+		assert_eq!(evaluate(context, "negate", vec![Item::Signed8(-128)]).unwrap(), Item::Signed8(-128));
+	}
+}
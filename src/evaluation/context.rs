@@ -4,15 +4,16 @@ use crate::basic::{BasicFunction, Branch, Compound, Direction, Discriminant, Ite
 	NodeTarget, Reversibility, Statement, Value};
 use crate::context::Context;
 use crate::error::Diagnostic;
-use crate::span::Spanned;
+use crate::span::{Span, Spanned};
 
-use super::{EvaluationError, EvaluationItem, ValueContext, ValueFrame};
+use super::{ArithmeticMode, EvaluationError, EvaluationItem, ValueContext, ValueFrame};
 
 #[derive(Debug)]
 pub struct EvaluationContext<'a> {
 	pub values: ValueContext,
 	pub functions: Vec<FunctionFrame>,
 	reversibility: Reversibility,
+	arithmetic: ArithmeticMode,
 	context: &'a Context,
 }
 
@@ -20,7 +21,8 @@ impl<'a> EvaluationContext<'a> {
 	pub fn new(context: &'a Context, reversibility: Reversibility,
 	           function: FunctionFrame, values: ValueFrame) -> Result<Self, Diagnostic> {
 		let (values, functions) = (ValueContext::new(values), vec![function]);
-		Ok(EvaluationContext { values, functions, reversibility, context })
+		let arithmetic = *context.arithmetic.read();
+		Ok(EvaluationContext { values, functions, reversibility, arithmetic, context })
 	}
 
 	pub fn resume(&mut self, direction: Direction) -> Result<EvaluationItem, Diagnostic> {
@@ -45,7 +47,7 @@ impl<'a> EvaluationContext<'a> {
 			match self.branch(Direction::Advance)? {
 				Some(item) => match self.functions.last_mut() {
 					Some(frame) => match &frame.statement().node {
-						Statement::Binding(variable, Compound::FunctionCall(_, _)) => {
+						Statement::Binding(variable, Compound::FunctionCall(_, _, _)) => {
 							self.values.frame().items.insert(variable.clone(), item);
 							frame.statement += 1;
 							Ok(None)
@@ -73,12 +75,14 @@ impl<'a> EvaluationContext<'a> {
 			match self.branch(Direction::Reverse)? {
 				Some(item) => match self.functions.last_mut() {
 					Some(frame) => match &frame.statement().node {
-						Statement::Binding(_, Compound::FunctionCall(_, values)) => match item {
+						Statement::Binding(_, Compound::FunctionCall(_, _, values)) => match item {
 							EvaluationItem::Item(Item::Instance(mut instance)) => {
+								let span = frame.statement().span;
 								for (index, value) in values.iter().enumerate() {
 									if let Value::Location(location) = value {
-										*self.values.location(location) = instance.fields
-											.remove(index.to_string().as_str()).unwrap();
+										*self.values.location(location).map_err(|error|
+											Diagnostic::new(Spanned::new(error, span)))? = instance
+											.fields.remove(index.to_string().as_str()).unwrap();
 									}
 								}
 								Ok(None)
@@ -110,11 +114,14 @@ impl<'a> EvaluationContext<'a> {
 		let values = &mut self.values;
 		match &statement.node {
 			Statement::Binding(variable, compound) => match compound {
-				Compound::FunctionCall(path, arguments) => {
-					let function = crate::basic::function(&self.context, path, self.reversibility)?;
+				Compound::FunctionCall(path, instantiation, arguments) => {
+					let function = crate::basic::instance(&self.context,
+						path, self.reversibility, instantiation)?;
 					let frame = match direction {
-						Direction::Advance => ValueFrame::advance(&function, arguments
-							.iter().map(|argument| values.value(argument))),
+						Direction::Advance => ValueFrame::advance(&function, arguments.iter()
+							.map(|argument| values.value(argument)).collect::<Result<Vec<_>, _>>()
+							.map_err(|error| Diagnostic::new(Spanned::new(error, statement.span)))?
+							.into_iter()),
 						Direction::Reverse => ValueFrame::reverse(&function,
 							values.frame().items[variable].clone()),
 					};
@@ -123,25 +130,21 @@ impl<'a> EvaluationContext<'a> {
 					values.frames.push(frame);
 					return Ok(true);
 				}
-				_ => super::binding::binding(&mut self.values, direction, variable, compound),
+				_ => super::binding::binding(&mut self.values,
+					self.arithmetic, direction, variable, compound),
 			}
 			Statement::Mutation(mutation, location, value) =>
 				super::mutation::mutation(&mut self.values, &self.reversibility,
-					direction, mutation, location, value),
-			Statement::ImplicitDrop(location) => Ok(match direction {
-				Direction::Reverse => {
-					if !self.values.frame().items.contains_key(&location.variable) {
-						self.values.frame().items.insert(location.variable.clone(),
-							EvaluationItem::Item(Item::Uninitialised)).unwrap_none();
-					}
-					*self.values.location(location) = self.values.stack.restore()
-				}
-				Direction::Advance => {
-					let item = self.values.location(location).clone();
-					self.values.stack.drop(item);
-				}
-			}),
-		}.map_err(|error| Diagnostic::new(Spanned::new(error, statement.span))).map(|_| false)
+					self.arithmetic, direction, mutation, location, value),
+			Statement::ImplicitDrop(location) =>
+				super::mutation::implicit_drop(&mut self.values, direction, location),
+		}.map_err(|error| match error {
+			EvaluationError::ArithmeticOverflow(ref operation) => {
+				let operation = format!("In evaluating: {}", operation);
+				Diagnostic::new(Spanned::new(error, statement.span)).note(operation)
+			}
+			_ => Diagnostic::new(Spanned::new(error, statement.span)),
+		}).map(|_| false)
 	}
 
 	/// Evaluates the current node branch. Returns an item on a return branch.
@@ -152,13 +155,22 @@ impl<'a> EvaluationContext<'a> {
 		match &branch.node {
 			Branch::Jump(target) => frame.node = *target,
 			Branch::Divergence(divergence) => {
-				let item = self.values.value(&divergence.discriminant);
-				let discriminant = Discriminant::item(item.collapse().unwrap());
+				let discriminant = self.values.value(&divergence.discriminant)
+					.map_err(|error| Diagnostic::new(Spanned::new(error, branch.span)))?;
+				let discriminant = match discriminant {
+					EvaluationItem::Item(Item::Instance(instance)) => Discriminant::variant(instance
+						.variant.as_ref().map(|(index, _)| *index).expect("Instance has no variant")),
+					item => Discriminant::item(item.collapse().unwrap()).ok_or_else(|| {
+						let error = EvaluationError::DiscriminantRange;
+						Diagnostic::new(Spanned::new(error, branch.span))
+					})?,
+				};
 				frame.node = divergence.branches.iter().find(|(value, _)| value == &discriminant)
 					.map(|(_, target)| *target).unwrap_or(divergence.default);
 			}
 			Branch::Return(value) => {
-				let item = self.values.value(value);
+				let item = self.values.value(value).map_err(|error|
+					Diagnostic::new(Spanned::new(error, branch.span)))?;
 				self.values.frames.pop().unwrap();
 				self.functions.pop().unwrap();
 				return Ok(Some(item));
@@ -193,6 +205,28 @@ impl FunctionFrame {
 		FunctionFrame { node, statement, function }
 	}
 
+	pub fn function(&self) -> &Arc<BasicFunction> {
+		&self.function
+	}
+
+	/// Provides the current node and the index of the next statement to advance.
+	pub fn position(&self) -> (NodeTarget, usize) {
+		(self.node, self.statement)
+	}
+
+	/// Provides the span of the statement or branch to be evaluated next in a direction.
+	pub fn span(&self, direction: Direction) -> Span {
+		let node = &self.function[&self.node];
+		match direction {
+			Direction::Advance => node.statements.get(self.statement)
+				.map(|statement| statement.span).unwrap_or(node.advance.span),
+			Direction::Reverse => match self.statement.checked_sub(1) {
+				Some(index) => node.statements[index].span,
+				None => node.reverse.span,
+			},
+		}
+	}
+
 	fn statement(&self) -> &Spanned<Statement> {
 		let node = &self.function[&self.node];
 		node.statements.get(self.statement).unwrap_or_else(||
@@ -6,27 +6,88 @@ use crate::basic::{Direction, Item, Reversibility};
 use crate::context::Context;
 use crate::declaration::FunctionPath;
 use crate::error::{CompileError, Diagnostic};
+use crate::inference::TypeResolution;
 use crate::node::{ExpressionKey, Variable};
 use crate::span::Spanned;
 
-use super::{EvaluationContext, EvaluationItem, FunctionFrame, ValueFrame};
+use super::{EvaluationContext, EvaluationInstance, EvaluationItem, FunctionFrame, ValueFrame};
+
+/// Determines whether integer operations that exceed the range
+/// of their type wrap around or are reported as errors.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ArithmeticMode {
+	Checked,
+	Wrapping,
+}
+
+impl Default for ArithmeticMode {
+	fn default() -> Self {
+		ArithmeticMode::Checked
+	}
+}
+
+impl fmt::Display for ArithmeticMode {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			ArithmeticMode::Checked => write!(f, "checked"),
+			ArithmeticMode::Wrapping => write!(f, "wrapping"),
+		}
+	}
+}
 
 #[derive(Debug)]
 pub enum EvaluationError {
-	ArithmeticOverflow,
+	/// Contains the operation with the values of its operands.
+	ArithmeticOverflow(String),
+	DivisionByZero,
+	/// Contains the index and the length of the array.
+	IndexBounds(u64, usize),
 	UnreachableBranch,
+	DiscriminantRange,
 	RuntimeExpression,
+	ArgumentLiteral,
+	ArgumentType(TypeResolution),
+	ReversalMismatch,
+	/// Contains the message of a panic during evaluation.
+	InternalPanic(String),
+	/// Contains the number of steps that were evaluated.
+	StepLimit(usize),
+	TestParameters,
+	TestReturn(TypeResolution),
+	TestFailure,
 }
 
 impl fmt::Display for EvaluationError {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		match self {
-			EvaluationError::ArithmeticOverflow =>
+			EvaluationError::ArithmeticOverflow(_) =>
 				write!(f, "Arithmetic operation overflow"),
+			EvaluationError::DivisionByZero =>
+				write!(f, "Division by zero"),
+			EvaluationError::IndexBounds(index, length) =>
+				write!(f, "Index: {}, is out of bounds for array of length: {}", index, length),
 			EvaluationError::UnreachableBranch =>
 				write!(f, "Unreachable branch encountered"),
+			EvaluationError::DiscriminantRange =>
+				write!(f, "Integer discriminant exceeds 128 bits"),
 			EvaluationError::RuntimeExpression =>
 				write!(f, "Expression is not available at compile time"),
+			EvaluationError::ArgumentLiteral =>
+				write!(f, "Arguments must be integer, truth, tuple or structure literals"),
+			EvaluationError::ArgumentType(resolution) =>
+				write!(f, "Argument does not match parameter type: {}", resolution),
+			EvaluationError::ReversalMismatch =>
+				write!(f, "Reversed parameters do not match arguments"),
+			EvaluationError::InternalPanic(message) =>
+				write!(f, "Evaluation failed internally: {}", message),
+			EvaluationError::StepLimit(steps) =>
+				write!(f, "Evaluation did not complete within: {}, steps", steps),
+			EvaluationError::TestParameters =>
+				write!(f, "Test functions cannot have parameters"),
+			EvaluationError::TestReturn(resolution) =>
+				write!(f, "Test functions must return truth, instead of: {}", resolution),
+			EvaluationError::TestFailure =>
+				write!(f, "Test evaluated to false"),
 		}
 	}
 }
@@ -47,6 +108,27 @@ pub fn function(context: &Context, function_path: &Spanned<Arc<FunctionPath>>,
 		.resume(Direction::Advance)
 }
 
+/// Evaluates a reversible function forwards and then in reverse
+/// from its result. Provides the reconstructed parameters.
+pub fn cycle(context: &Context, function_path: &Spanned<Arc<FunctionPath>>,
+             arguments: Vec<Item>) -> Result<EvaluationItem, Diagnostic> {
+	let function = crate::basic::function(context, function_path, Reversibility::Reversible)?;
+	let fields = arguments.iter().enumerate()
+		.map(|(index, item)| (index.to_string().into(), EvaluationItem::item(item))).collect();
+	let type_resolution = function.parameter_type();
+	let item = Item::Instance(EvaluationInstance { type_resolution, fields, variant: None });
+
+	let mut frame = ValueFrame::default();
+	frame.items.insert(Variable::new_temporary(0), EvaluationItem::Item(item));
+	let mut context = EvaluationContext::new(context, Reversibility::Reversible,
+		FunctionFrame::new(function.clone(), Direction::Advance), frame)?;
+	let item = context.resume(Direction::Advance)?;
+
+	context.values.frames.push(ValueFrame::reverse(&function, item));
+	context.functions.push(FunctionFrame::new(function, Direction::Reverse));
+	context.resume(Direction::Reverse)
+}
+
 /// Fully evaluates an expression and provides the expression result.
 pub fn expression(context: &Context, function_path: &Spanned<Arc<FunctionPath>>,
                   expression: &ExpressionKey, variables: HashMap<Variable, Item>)
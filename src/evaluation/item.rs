@@ -27,10 +27,16 @@ impl EvaluationItem {
 			Item::Unsigned16(value) => Item::Unsigned16(*value),
 			Item::Unsigned32(value) => Item::Unsigned32(*value),
 			Item::Unsigned64(value) => Item::Unsigned64(*value),
+			Item::Signed128(value) => Item::Signed128(*value),
+			Item::Unsigned128(value) => Item::Unsigned128(*value),
+			Item::Integer(value) => Item::Integer(value.clone()),
+			Item::Character(value) => Item::Character(*value),
+			Item::String(value) => Item::String(value.clone()),
 			Item::Instance(instance) => Item::Instance(EvaluationInstance {
 				type_resolution: instance.type_resolution.clone(),
 				fields: instance.fields.iter().map(|(field, item)|
 					(field.clone(), EvaluationItem::item(item))).collect(),
+				variant: instance.variant.clone(),
 			}),
 			Item::Uninitialised => Item::Uninitialised,
 			Item::Unit => Item::Unit,
@@ -50,11 +56,17 @@ impl EvaluationItem {
 				Item::Unsigned16(value) => Item::Unsigned16(*value),
 				Item::Unsigned32(value) => Item::Unsigned32(*value),
 				Item::Unsigned64(value) => Item::Unsigned64(*value),
+				Item::Signed128(value) => Item::Signed128(*value),
+				Item::Unsigned128(value) => Item::Unsigned128(*value),
+				Item::Integer(value) => Item::Integer(value.clone()),
+				Item::Character(value) => Item::Character(*value),
+				Item::String(value) => Item::String(value.clone()),
 				Item::Instance(instance) => Item::Instance(Instance {
 					type_resolution: instance.type_resolution.clone(),
 					fields: instance.fields.iter().map(|(field, item)|
 						Ok((field.clone(), item.collapse()?)))
 						.collect::<Result<_, _>>()?,
+					variant: instance.variant.clone(),
 				}),
 				Item::Uninitialised => Item::Uninitialised,
 				Item::Unit => Item::Unit,
@@ -76,4 +88,5 @@ impl fmt::Debug for EvaluationItem {
 pub struct EvaluationInstance {
 	pub type_resolution: TypeResolution,
 	pub fields: HashMap<Arc<str>, EvaluationItem>,
+	pub variant: Option<(usize, Arc<str>)>,
 }
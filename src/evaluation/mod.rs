@@ -1,9 +1,19 @@
 pub use context::{EvaluationContext, FunctionFrame};
-pub use evaluation::{EvaluationError, expression, function};
+pub use evaluation::{ArithmeticMode, cycle, EvaluationError, expression, function};
 pub use item::{EvaluationInstance, EvaluationItem};
 use item::FrameIndex;
 pub use partial::{partial_function, PartialFunctions};
-pub use value::{DropStack, ValueContext, ValueFrame};
+pub use value::{DropStack, ValueContext, ValueFrame, ValueStack};
+
+/// Selects the checked or wrapping form of an integer operation.
+macro_rules! arithmetic_mode {
+    ($mode:expr, $overflow:expr, $checked:expr, $wrapping:expr) => {
+		match $mode {
+			ArithmeticMode::Checked => $checked.ok_or_else($overflow)?,
+			ArithmeticMode::Wrapping => $wrapping,
+		}
+    };
+}
 
 mod context;
 mod evaluation;
@@ -1,25 +1,90 @@
-use crate::basic::{Direction, Item, Location, Reversibility, Value};
+use crate::basic::{Direction, Integer, Item, Location, Reversibility, Value};
 use crate::node::{Arithmetic, MutationKind};
 
-use super::{DropStack, EvaluationError, EvaluationItem, ValueContext};
+use super::{ArithmeticMode, DropStack, EvaluationError, EvaluationItem, ValueContext};
+
+macro_rules! restore {
+    ($stack:expr, $identifier:ident) => {
+		match $stack.restore() {
+			EvaluationItem::Item(Item::$identifier(saved)) => saved,
+			item => panic!("Invalid restored item: {:?}, for arithmetic", item),
+		}
+    };
+}
 
 macro_rules! arithmetic {
-    ($stack:expr, $reversibility:expr, $direction:expr,
+    ($stack:expr, $reversibility:expr, $mode:expr, $direction:expr,
     $arithmetic:expr, $mutable:expr, $item:expr, $identifier:ident) => {
 		if let EvaluationItem::Item(Item::$identifier(integer)) = $mutable {
 			if let EvaluationItem::Item(Item::$identifier(other)) = $item {
+				let initial = *integer;
+				let overflow = || EvaluationError::ArithmeticOverflow(format!("{} {}= {}", initial, $arithmetic, other));
 				return Ok(match $direction {
 					Direction::Advance => match $arithmetic {
-						Arithmetic::Add => *integer = integer.wrapping_add(other),
-						Arithmetic::Minus => *integer = integer.wrapping_sub(other),
+						Arithmetic::Add => *integer = arithmetic_mode!($mode, overflow,
+							integer.checked_add(other), integer.wrapping_add(other)),
+						Arithmetic::Minus => *integer = arithmetic_mode!($mode, overflow,
+							integer.checked_sub(other), integer.wrapping_sub(other)),
 						Arithmetic::Multiply => {
-							let value = integer.checked_mul(other)
-								.ok_or(EvaluationError::ArithmeticOverflow)?;
+							// Reversible products are checked as they must be divisible to be reversed.
+							let value = match $reversibility {
+								Reversibility::Reversible => integer.checked_mul(other).ok_or_else(overflow)?,
+								Reversibility::Entropic => arithmetic_mode!($mode, overflow,
+									integer.checked_mul(other), integer.wrapping_mul(other)),
+							};
 							if value == 0 && $reversibility == &Reversibility::Reversible {
 								$stack.drop(EvaluationItem::Item(Item::$identifier(*integer)))
 							}
 							*integer = value
 						}
+						Arithmetic::Divide | Arithmetic::Modulo => {
+							if other == 0 { return Err(EvaluationError::DivisionByZero); }
+							let quotient = arithmetic_mode!($mode, overflow,
+								integer.checked_div(other), integer.wrapping_div(other));
+							let remainder = integer.wrapping_sub(quotient.wrapping_mul(other));
+							let (value, lost) = match $arithmetic {
+								Arithmetic::Divide => (quotient, remainder),
+								_ => (remainder, quotient),
+							};
+
+							if $reversibility == &Reversibility::Reversible {
+								$stack.drop(EvaluationItem::Item(Item::$identifier(lost)))
+							}
+							*integer = value
+						}
+						Arithmetic::BitAnd | Arithmetic::BitOr => {
+							let (value, lost) = match $arithmetic {
+								Arithmetic::BitAnd => (*integer & other, *integer & !other),
+								_ => (*integer | other, *integer & other),
+							};
+
+							if $reversibility == &Reversibility::Reversible {
+								$stack.drop(EvaluationItem::Item(Item::$identifier(lost)))
+							}
+							*integer = value
+						}
+						Arithmetic::BitXor => *integer ^= other,
+						Arithmetic::ShiftLeft | Arithmetic::ShiftRight => {
+							// The bits shifted out are the difference from shifting back.
+							let shift = other as u32;
+							let (value, lost) = match $arithmetic {
+								Arithmetic::ShiftLeft => {
+									let value = arithmetic_mode!($mode, overflow,
+										integer.checked_shl(shift), integer.wrapping_shl(shift));
+									(value, *integer ^ value.wrapping_shr(shift))
+								}
+								_ => {
+									let value = arithmetic_mode!($mode, overflow,
+										integer.checked_shr(shift), integer.wrapping_shr(shift));
+									(value, *integer ^ value.wrapping_shl(shift))
+								}
+							};
+
+							if $reversibility == &Reversibility::Reversible {
+								$stack.drop(EvaluationItem::Item(Item::$identifier(lost)))
+							}
+							*integer = value
+						}
 					}
 					Direction::Reverse => match $arithmetic {
 						Arithmetic::Add => *integer = integer.wrapping_sub(other),
@@ -28,6 +93,17 @@ macro_rules! arithmetic {
 							0 => *$mutable = $stack.restore(),
 							_ => *integer /= other,
 						},
+						Arithmetic::Divide => *integer = integer.wrapping_mul(other)
+							.wrapping_add(restore!($stack, $identifier)),
+						Arithmetic::Modulo => *integer = restore!($stack, $identifier)
+							.wrapping_mul(other).wrapping_add(*integer),
+						Arithmetic::BitAnd => *integer |= restore!($stack, $identifier),
+						Arithmetic::BitOr => *integer = (*integer & !other) | restore!($stack, $identifier),
+						Arithmetic::BitXor => *integer ^= other,
+						Arithmetic::ShiftLeft => *integer = integer.wrapping_shr(other as u32)
+							^ restore!($stack, $identifier),
+						Arithmetic::ShiftRight => *integer = integer.wrapping_shl(other as u32)
+							^ restore!($stack, $identifier),
 					}
 				});
 			}
@@ -35,35 +111,178 @@ macro_rules! arithmetic {
     };
 }
 
-pub fn mutation(context: &mut ValueContext, reversibility: &Reversibility, direction: Direction,
-                mutation: &MutationKind, location: &Location, value: &Value) -> Result<(), EvaluationError> {
-	let item = context.value(value);
-	let mutable = context.values.location(location);
+pub fn mutation(context: &mut ValueContext, reversibility: &Reversibility, mode: ArithmeticMode,
+                direction: Direction, mutation: &MutationKind, location: &Location,
+                value: &Value) -> Result<(), EvaluationError> {
+	let item = context.value(value)?;
+	let mutable = context.values.location(location)?;
 	match mutation {
 		MutationKind::Arithmetic(operator) => arithmetic(&mut context.stack,
-			reversibility, direction, operator, mutable, item),
+			reversibility, mode, direction, operator, mutable, item),
 		MutationKind::Assign => Ok(*mutable = item),
 		MutationKind::Swap => match value {
 			Value::Item(_) => panic!("Cannot swap location: {}, with item immediate", location),
 			Value::Location(other) => {
 				let item = std::mem::replace(mutable, item);
-				std::mem::replace(context.location(other), item);
+				std::mem::replace(context.location(other)?, item);
 				Ok(())
 			}
 		},
 	}
 }
 
-fn arithmetic(stack: &mut DropStack, reversibility: &Reversibility, direction: Direction,
-              arithmetic: &Arithmetic, mutable: &mut EvaluationItem, item: EvaluationItem)
-              -> Result<(), EvaluationError> {
-	arithmetic!(stack, reversibility, direction, arithmetic, mutable, item, Unsigned8);
-	arithmetic!(stack, reversibility, direction, arithmetic, mutable, item, Unsigned16);
-	arithmetic!(stack, reversibility, direction, arithmetic, mutable, item, Unsigned32);
-	arithmetic!(stack, reversibility, direction, arithmetic, mutable, item, Unsigned64);
-	arithmetic!(stack, reversibility, direction, arithmetic, mutable, item, Signed8);
-	arithmetic!(stack, reversibility, direction, arithmetic, mutable, item, Signed16);
-	arithmetic!(stack, reversibility, direction, arithmetic, mutable, item, Signed32);
-	arithmetic!(stack, reversibility, direction, arithmetic, mutable, item, Signed64);
+/// Saves the item at a location before it is overwritten and restores it in reverse.
+pub fn implicit_drop(context: &mut ValueContext, direction: Direction,
+                     location: &Location) -> Result<(), EvaluationError> {
+	Ok(match direction {
+		Direction::Reverse => {
+			if !context.frame().items.contains_key(&location.variable) {
+				context.frame().items.insert(location.variable.clone(),
+					EvaluationItem::Item(Item::Uninitialised)).unwrap_none();
+			}
+
+			let item = context.stack.restore();
+			*context.location(location)? = item;
+		}
+		Direction::Advance => {
+			let item = context.location(location)?.clone();
+			context.stack.drop(item);
+		}
+	})
+}
+
+fn arithmetic(stack: &mut DropStack, reversibility: &Reversibility, mode: ArithmeticMode,
+              direction: Direction, arithmetic: &Arithmetic, mutable: &mut EvaluationItem,
+              item: EvaluationItem) -> Result<(), EvaluationError> {
+	arithmetic!(stack, reversibility, mode, direction, arithmetic, mutable, item, Unsigned8);
+	arithmetic!(stack, reversibility, mode, direction, arithmetic, mutable, item, Unsigned16);
+	arithmetic!(stack, reversibility, mode, direction, arithmetic, mutable, item, Unsigned32);
+	arithmetic!(stack, reversibility, mode, direction, arithmetic, mutable, item, Unsigned64);
+	arithmetic!(stack, reversibility, mode, direction, arithmetic, mutable, item, Signed8);
+	arithmetic!(stack, reversibility, mode, direction, arithmetic, mutable, item, Signed16);
+	arithmetic!(stack, reversibility, mode, direction, arithmetic, mutable, item, Signed32);
+	arithmetic!(stack, reversibility, mode, direction, arithmetic, mutable, item, Signed64);
+	arithmetic!(stack, reversibility, mode, direction, arithmetic, mutable, item, Unsigned128);
+	arithmetic!(stack, reversibility, mode, direction, arithmetic, mutable, item, Signed128);
+	if let EvaluationItem::Item(Item::Integer(integer)) = mutable {
+		if let EvaluationItem::Item(Item::Integer(other)) = &item {
+			return arbitrary(stack, reversibility, direction, arithmetic, integer, other);
+		}
+	}
 	panic!("Mutation arithmetic on invalid values")
 }
+
+/// Mutates an arbitrary precision integer. Shifting left is lossless
+/// whereas shifting right saves the bits that are shifted out.
+fn arbitrary(stack: &mut DropStack, reversibility: &Reversibility, direction: Direction,
+             arithmetic: &Arithmetic, integer: &mut Integer, other: &Integer) -> Result<(), EvaluationError> {
+	let is_reversible = reversibility == &Reversibility::Reversible;
+	let shift = |integer: &Integer| other.to_u32().ok_or_else(|| EvaluationError::ArithmeticOverflow(
+		format!("{} {}= {}", integer, arithmetic, other)));
+	let mut save = |lost: Integer| if is_reversible {
+		stack.drop(EvaluationItem::Item(Item::Integer(lost)));
+	};
+
+	*integer = match direction {
+		Direction::Advance => match arithmetic {
+			Arithmetic::Add => &*integer + other,
+			Arithmetic::Minus => &*integer - other,
+			Arithmetic::Multiply => {
+				let value = &*integer * other;
+				if value.is_zero() {
+					save(integer.clone());
+				}
+				value
+			}
+			Arithmetic::Divide | Arithmetic::Modulo => {
+				let (quotient, remainder) = integer.checked_div_rem(other)
+					.ok_or(EvaluationError::DivisionByZero)?;
+				match arithmetic {
+					Arithmetic::Divide => { save(remainder); quotient }
+					_ => { save(quotient); remainder }
+				}
+			}
+			Arithmetic::BitAnd => {
+				save(&*integer & &!other);
+				&*integer & other
+			}
+			Arithmetic::BitOr => {
+				save(&*integer & other);
+				&*integer | other
+			}
+			Arithmetic::BitXor => &*integer ^ other,
+			Arithmetic::ShiftLeft => &*integer << shift(integer)?,
+			Arithmetic::ShiftRight => {
+				let shift = shift(integer)?;
+				let value = &*integer >> shift;
+				save(&*integer - &(&value << shift));
+				value
+			}
+		},
+		Direction::Reverse => match arithmetic {
+			Arithmetic::Add => &*integer - other,
+			Arithmetic::Minus => &*integer + other,
+			Arithmetic::Multiply => match integer.is_zero() {
+				true => restore!(stack, Integer),
+				false => integer.checked_div_rem(other).unwrap().0,
+			},
+			Arithmetic::Divide => &(&*integer * other) + &restore!(stack, Integer),
+			Arithmetic::Modulo => &(&restore!(stack, Integer) * other) + integer,
+			Arithmetic::BitAnd => &*integer | &restore!(stack, Integer),
+			Arithmetic::BitOr => &(&*integer & &!other) | &restore!(stack, Integer),
+			Arithmetic::BitXor => &*integer ^ other,
+			Arithmetic::ShiftLeft => &*integer >> shift(integer)?,
+			Arithmetic::ShiftRight => &(&*integer << shift(integer)?) + &restore!(stack, Integer),
+		},
+	};
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::evaluation::EvaluationInstance;
+
+	use super::*;
+
+	type FixedItem = Item<EvaluationInstance>;
+
+	/// Provides the shifted item and the item restored by reversing the shift.
+	fn shift(arithmetic: Arithmetic, mode: ArithmeticMode, initial: FixedItem,
+	         shift: FixedItem) -> Result<(FixedItem, FixedItem), EvaluationError> {
+		let stack = &mut DropStack::default();
+		let reversible = &Reversibility::Reversible;
+		let mut item = EvaluationItem::Item(initial);
+		let other = EvaluationItem::Item(shift);
+		super::arithmetic(stack, reversible, mode, Direction::Advance,
+			&arithmetic, &mut item, other.clone())?;
+		let advanced = item.clone();
+		super::arithmetic(stack, reversible, mode, Direction::Reverse, &arithmetic, &mut item, other)?;
+		assert!(stack.items().is_empty());
+		match (advanced, item) {
+			(EvaluationItem::Item(advanced), EvaluationItem::Item(item)) => Ok((advanced, item)),
+			other => panic!("Invalid shifted items: {:?}", other),
+		}
+	}
+
+	#[test]
+	fn test_shift_assign() {
+		let checked = ArithmeticMode::Checked;
+		assert_eq!(shift(Arithmetic::ShiftLeft, checked, Item::Unsigned8(0b1110_0001), Item::Unsigned8(3))
+			.unwrap(), (Item::Unsigned8(0b0000_1000), Item::Unsigned8(0b1110_0001)));
+		assert_eq!(shift(Arithmetic::ShiftRight, checked, Item::Unsigned8(0b1110_0111), Item::Unsigned8(2))
+			.unwrap(), (Item::Unsigned8(0b0011_1001), Item::Unsigned8(0b1110_0111)));
+		assert_eq!(shift(Arithmetic::ShiftLeft, checked, Item::Signed8(64), Item::Signed8(1))
+			.unwrap(), (Item::Signed8(-128), Item::Signed8(64)));
+		assert_eq!(shift(Arithmetic::ShiftRight, checked, Item::Signed8(-7), Item::Signed8(2))
+			.unwrap(), (Item::Signed8(-2), Item::Signed8(-7)));
+	}
+
+	#[test]
+	fn test_shift_assign_overflow() {
+		let error = shift(Arithmetic::ShiftLeft, ArithmeticMode::Checked,
+			Item::Unsigned8(1), Item::Unsigned8(9));
+		assert!(error.is_err());
+		assert_eq!(shift(Arithmetic::ShiftLeft, ArithmeticMode::Wrapping, Item::Unsigned8(1),
+			Item::Unsigned8(9)).unwrap(), (Item::Unsigned8(2), Item::Unsigned8(1)));
+	}
+}
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::cell::UnsafeCell;
 use std::collections::HashMap;
 use std::ops::{Deref, DerefMut};
@@ -5,7 +6,7 @@ use std::ops::{Deref, DerefMut};
 use crate::basic::{BasicFunction, Branch, Item, Location, Projection, Value};
 use crate::node::Variable;
 
-use super::{EvaluationInstance, EvaluationItem, FrameIndex};
+use super::{EvaluationError, EvaluationInstance, EvaluationItem, FrameIndex};
 
 #[derive(Debug)]
 pub struct ValueContext {
@@ -44,40 +45,50 @@ pub struct ValueStack {
 }
 
 impl ValueStack {
-	pub fn item(&mut self, value: &Value) -> Item<EvaluationInstance> {
-		match self.value(value) {
-			EvaluationItem::Item(item) => item,
+	pub fn item(&mut self, value: &Value) -> Result<Item<EvaluationInstance>, EvaluationError> {
+		match self.value(value)? {
+			EvaluationItem::Item(item) => Ok(item),
 			_ => panic!("Value must be an item"),
 		}
 	}
 
-	pub fn value(&mut self, value: &Value) -> EvaluationItem {
-		match value {
+	pub fn value(&mut self, value: &Value) -> Result<EvaluationItem, EvaluationError> {
+		Ok(match value {
 			Value::Item(item) => EvaluationItem::item(item),
-			Value::Location(location) => self.location(location).clone(),
-		}
+			Value::Location(location) => self.location(location)?.clone(),
+		})
 	}
 
-	pub fn location(&mut self, location: &Location) -> &mut EvaluationItem {
+	pub fn location(&mut self, location: &Location) -> Result<&mut EvaluationItem, EvaluationError> {
 		let frame = self.frame_index();
 		self.dereference(frame, location)
 	}
 
-	pub fn dereference(&mut self, frame: FrameIndex, location: &Location) -> &mut EvaluationItem {
+	pub fn dereference(&mut self, frame: FrameIndex, location: &Location)
+	                   -> Result<&mut EvaluationItem, EvaluationError> {
+		let location = &self.resolve(frame, location);
 		let stack = UnsafeCell::new(self);
 		let frame = unsafe { stack.get().as_mut() }.unwrap().frames.get_mut(frame)
 			.unwrap_or_else(|| panic!("Frame: {}, does not exist in stack", frame));
 		let item = frame.items.get_mut(&location.variable).unwrap_or_else(||
 			panic!("Variable: {}, does not exist in frame", location.variable));
-		location.projections.iter().fold(item, |item, projection| match projection {
+		location.projections.iter().try_fold(item, |item, projection| Ok(match projection {
 			Projection::Field(field) => match item {
 				EvaluationItem::Item(item) => match item {
-					Item::Instance(instance) => instance.fields.get_mut(field)
-						.unwrap_or_else(|| panic!("Field: {}, does not exist on instance", field)),
+					Item::Instance(instance) => {
+						if !instance.fields.contains_key(field) && instance.type_resolution.is_array() {
+							let index = field.parse().expect("Array field must be an index");
+							return Err(EvaluationError::IndexBounds(index, instance.fields.len()));
+						}
+
+						instance.fields.get_mut(field).unwrap_or_else(||
+							panic!("Field: {}, does not exist on instance", field))
+					}
 					_ => panic!("Field access can only be performed on instance"),
 				},
 				EvaluationItem::Reference(_, _) => panic!("Field access cannot be performed on reference"),
 			},
+			Projection::Index(_) => unreachable!(),
 			Projection::Dereference => match item {
 				EvaluationItem::Item(_) => panic!("Dereference cannot be performed on item"),
 				EvaluationItem::Reference(frame, location) => {
@@ -86,10 +97,31 @@ impl ValueStack {
 					// is associated with the self parameter so it is not possible to
 					// obtain more than one mutable reference.
 					let stack = unsafe { stack.get().as_mut() }.unwrap();
-					stack.dereference(*frame, location)
+					stack.dereference(*frame, location)?
 				}
 			}
-		})
+		}))
+	}
+
+	/// Replaces index projections with fields using the index variables in the frame.
+	pub fn resolve<'a>(&self, frame: FrameIndex, location: &'a Location) -> Cow<'a, Location> {
+		let is_indexed = location.projections.iter().any(|projection| match projection {
+			Projection::Index(_) => true,
+			_ => false,
+		});
+		if !is_indexed { return Cow::Borrowed(location); }
+
+		let mut location = location.clone();
+		for projection in &mut location.projections {
+			if let Projection::Index(variable) = projection {
+				let index = match self.frames[frame].items.get(variable) {
+					Some(EvaluationItem::Item(Item::Unsigned64(index))) => *index,
+					_ => panic!("Index variable: {}, must be an unsigned integer", variable),
+				};
+				*projection = Projection::Field(index.to_string().into());
+			}
+		}
+		Cow::Owned(location)
 	}
 
 	pub fn frame_index(&mut self) -> usize {
@@ -101,7 +133,7 @@ impl ValueStack {
 	}
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct ValueFrame {
 	pub items: HashMap<Variable, EvaluationItem>,
 }
@@ -113,7 +145,7 @@ impl ValueFrame {
 		let type_resolution = function.parameter_type();
 		let fields = arguments.enumerate().map(|(index, item)|
 			(index.to_string().into(), item)).collect();
-		let item = Item::Instance(EvaluationInstance { type_resolution, fields });
+		let item = Item::Instance(EvaluationInstance { type_resolution, fields, variant: None });
 		frame.items.insert(Variable::new_temporary(0), EvaluationItem::Item(item));
 		frame
 	}
@@ -123,7 +155,7 @@ impl ValueFrame {
 		let type_resolution = function.parameter_type();
 		let fields = function.parameters.iter().enumerate().map(|(index, _)|
 			(index.to_string().into(), EvaluationItem::Item(Item::Uninitialised))).collect();
-		let item = Item::Instance(EvaluationInstance { type_resolution, fields });
+		let item = Item::Instance(EvaluationInstance { type_resolution, fields, variant: None });
 		frame.items.insert(Variable::new_temporary(0), EvaluationItem::Item(item));
 
 		match &function[&function.component.exit].advance.node {
@@ -151,4 +183,8 @@ impl DropStack {
 	pub fn restore(&mut self) -> EvaluationItem {
 		self.stack.pop().expect("Cannot restore from empty drop stack")
 	}
+
+	pub fn items(&self) -> &[EvaluationItem] {
+		&self.stack
+	}
 }
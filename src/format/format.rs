@@ -0,0 +1,346 @@
+use std::sync::Arc;
+
+use crate::context::Context;
+use crate::declaration::{Declaration, FunctionPath, InterfacePath, ModulePath, StructurePath};
+use crate::error::Diagnostic;
+use crate::lexer::{Lexer, Token};
+use crate::source::SourceKey;
+use crate::span::Spanned;
+
+use super::printer::{self, Printer};
+
+#[derive(Debug)]
+enum Item {
+	Inclusion,
+	Module(Arc<ModulePath>, bool),
+	Definition(Arc<ModulePath>, Declaration),
+	Function(Arc<FunctionPath>, Declaration),
+	Structure(Arc<StructurePath>, Declaration),
+	Interface(Arc<InterfacePath>, Declaration),
+}
+
+/// Formats every loaded source. Sources that change are written unless
+/// `is_check` is true, in which case their paths are printed instead.
+/// Sources with comments are skipped as comments cannot yet be preserved.
+/// Returns true if no diagnostics were emitted and, when checking, every
+/// source that was not skipped is formatted without changes.
+pub fn format(context: &Context, is_check: bool) -> bool {
+	let errors = std::mem::replace(context.errors.write().as_mut(), Vec::new());
+	if !errors.is_empty() {
+		errors.iter().for_each(|diagnostic| eprint!("{}", crate::error::string(context, diagnostic)));
+		return false;
+	}
+
+	let mut sources: Vec<_> = context.source_keys.clone().into_iter().collect();
+	sources.sort_by(|(left, _), (right, _)| left.cmp(right));
+
+	let mut success = true;
+	for (path, source_key) in sources {
+		let source = source_key.get(context);
+		let string = match source.read_string() {
+			Ok(string) => string,
+			Err(error) => {
+				eprintln!("{}", error);
+				success = false;
+				continue;
+			}
+		};
+
+		if has_comments(string, source_key) {
+			println!("Skipped: {}, contains comments", path.display());
+			continue;
+		}
+
+		let formatted = match source_format(context, source_key, string) {
+			Ok(formatted) => formatted,
+			Err(diagnostic) => {
+				eprint!("{}", crate::error::string(context, &diagnostic));
+				success = false;
+				continue;
+			}
+		};
+
+		if formatted == string {
+			continue;
+		}
+
+		match is_check {
+			true => {
+				println!("Unformatted: {}", path.display());
+				success = false;
+			}
+			false => match std::fs::write(path.as_ref(), formatted) {
+				Ok(()) => println!("Formatted: {}", path.display()),
+				Err(error) => {
+					eprintln!("File at: {}, could not be written: {}", path.display(), error);
+					success = false;
+				}
+			}
+		}
+	}
+	success
+}
+
+/// Prints the canonical form of a source.
+fn source_format(context: &Context, source_key: SourceKey, string: &str) -> Result<String, Diagnostic> {
+	let source = source_key.get(context);
+	let module_path = context.module_pending.clone().into_iter().find(|(_, pending)|
+		pending.expected_path == source.path || pending.expected_module_path.as_ref() == Some(&source.path))
+		.map(|(module_path, _)| module_path).unwrap_or_else(ModulePath::root);
+
+	let mut formatted = String::new();
+	let mut previous: Option<(usize, &Item)> = None;
+	let items = items(context, source_key, &module_path, string);
+	for (offset, depth, item) in &items {
+		if let Some((previous_depth, previous)) = previous {
+			formatted += match (previous, item) {
+				_ if *depth > previous_depth => "\n",
+				(Item::Inclusion, Item::Inclusion) => "\n",
+				(Item::Module(_, true), Item::Module(_, true)) => "\n",
+				_ => "\n\n",
+			};
+		}
+
+		formatted += &printer::tabs(*depth);
+		if string[*offset..].trim_start().starts_with("export ") {
+			formatted += "export ";
+		}
+
+		formatted += &match item {
+			Item::Inclusion => {
+				let line = string[*offset..].lines().next().unwrap_or_default();
+				let inclusion = &line[line.find("use").unwrap_or(0) + "use".len()..];
+				format!("use {}", inclusion.split_whitespace().collect::<Vec<_>>().join(" "))
+			}
+			Item::Module(module_path, true) => format!("module {}", module_path.identifier),
+			Item::Module(module_path, false) => format!("module {}:", module_path.identifier),
+			Item::Definition(module_path, declaration) => {
+				let definition = crate::parser::definition(context, module_path.clone(), declaration)?;
+				let StructurePath(path) = &definition.structure.node;
+				let interface = definition.interface.as_ref().map(|interface| {
+					let InterfacePath(path) = &interface.node;
+					format!("{} for ", printer::path(path))
+				}).unwrap_or_default();
+				format!("define {}{}{}", interface, printer::path(path), templates(&definition.templates))
+			}
+			Item::Function(path, declaration) => function(context, path, declaration, *depth, string)?,
+			Item::Structure(path, declaration) => {
+				let path = Spanned::new(path.clone(), declaration.span());
+				let structure = crate::parser::structure(context, &path)?;
+				let fields: String = printer::fields(&structure.fields).into_iter().map(|(field, ascription)| format!("\n{}{}: {},",
+					printer::tabs(depth + 1), field, printer::ascription(ascription))).collect();
+				let variants: String = structure.variants.iter().map(|variant| format!("\n{}| {}",
+					printer::tabs(depth + 1), printer::variant(variant))).collect();
+				let StructurePath(path) = path.node.as_ref();
+				format!("data {}{}{}{}", path.identifier, templates(&structure.templates), fields, variants)
+			}
+			Item::Interface(path, declaration) => {
+				let path = Spanned::new(path.clone(), declaration.span());
+				let interface = crate::parser::interface(context, &path)?;
+				let methods: String = interface.methods.iter().map(|(identifier, function_type)|
+					format!("\n{}{}", printer::tabs(depth + 1),
+						printer::signature(&identifier.node, function_type, string))).collect();
+				let InterfacePath(path) = path.node.as_ref();
+				format!("interface {}:{}", path.identifier, methods)
+			}
+		};
+		previous = Some((*depth, item));
+	}
+
+	formatted.push('\n');
+	Ok(formatted)
+}
+
+fn function(context: &Context, path: &Arc<FunctionPath>, declaration: &Declaration,
+            depth: usize, string: &str) -> Result<String, Diagnostic> {
+	let path = Spanned::new(path.clone(), declaration.span());
+	let function_type = Arc::new(crate::parser::function_type(context, &path)?);
+	let function = crate::parser::function_body(context, &path, function_type.clone())?;
+
+	let attribute = match context.declarations_test.read().contains(&path.node) {
+		true => format!("#test\n{}", printer::tabs(depth)),
+		false => String::new(),
+	};
+
+	let FunctionPath(path) = path.node.as_ref();
+	let printer = Printer { source: string, context: &function.context };
+	let signature = printer::signature(&path.identifier, &function_type, string);
+	Ok(format!("{}{}:{}", attribute, signature, printer.body(&function.expression, depth)))
+}
+
+/// Collects the items declared in a source with their line offset and indentation.
+fn items(context: &Context, source_key: SourceKey, module_path: &ModulePath,
+         string: &str) -> Vec<(usize, usize, Item)> {
+	let base = depth(module_path);
+	let relative = |module_path: &ModulePath| depth(module_path) - base;
+	let mut items = Vec::new();
+
+	for (path, declaration) in context.declarations_function.clone() {
+		if declaration.source == source_key {
+			let FunctionPath(function_path) = path.as_ref();
+			let depth = match context.node_definitions.get(&path) {
+				Some(definition) => relative(&definition.declaration) + 1,
+				None => relative(&function_path.module_path),
+			};
+			items.push((*declaration.line_offset, depth, Item::Function(path, declaration)));
+		}
+	}
+
+	for (path, declaration) in context.declarations_structure.clone() {
+		if declaration.source == source_key {
+			let StructurePath(structure_path) = path.as_ref();
+			let depth = relative(&structure_path.module_path);
+			items.push((*declaration.line_offset, depth, Item::Structure(path, declaration)));
+		}
+	}
+
+	for (path, declaration) in context.declarations_interface.clone() {
+		if declaration.source == source_key {
+			let InterfacePath(interface_path) = path.as_ref();
+			let depth = relative(&interface_path.module_path);
+			items.push((*declaration.line_offset, depth, Item::Interface(path, declaration)));
+		}
+	}
+
+	for (module_path, module) in context.module_contexts.read().iter() {
+		for definition in &module.definitions {
+			let declaration = definition.declaration.clone();
+			if declaration.source == source_key {
+				let item = Item::Definition(module_path.clone(), declaration.clone());
+				items.push((*declaration.line_offset, relative(module_path), item));
+			}
+		}
+
+		let mut inclusions = module.inclusions.iter();
+		if let Some(inclusion) = inclusions.next() {
+			let is_nested = !context.module_pending.contains_key(module_path);
+			if is_nested && inclusion.span.source == source_key {
+				let item = Item::Module(module_path.clone(), false);
+				let offset = line_offset(string, inclusion.span.byte_start);
+				items.push((offset, relative(module_path) - 1, item));
+			}
+		}
+
+		for inclusion in inclusions.filter(|inclusion| inclusion.span.source == source_key) {
+			let offset = line_offset(string, inclusion.span.byte_start);
+			items.push((offset, relative(module_path), Item::Inclusion));
+		}
+	}
+
+	for (module_path, pending) in context.module_pending.clone() {
+		let span = pending.declaration_span;
+		if span.source == source_key {
+			let depth = relative(&module_path) - 1;
+			items.push((span.byte_start, depth, Item::Module(module_path, true)));
+		}
+	}
+
+	items.sort_by_key(|(offset, _, _)| *offset);
+	items.dedup_by_key(|(offset, _, _)| *offset);
+	items
+}
+
+fn has_comments(string: &str, source_key: SourceKey) -> bool {
+	let lexer = &mut Lexer::new(string, 0, source_key);
+	while lexer.next().node != Token::End {}
+	!lexer.trivia().is_empty()
+}
+
+fn templates(templates: &[Spanned<Arc<str>>]) -> String {
+	match templates.is_empty() {
+		true => ":".to_owned(),
+		false => format!("<{}>:", templates.iter().map(|template|
+			format!("${}", template.node)).collect::<Vec<_>>().join(", ")),
+	}
+}
+
+fn depth(module_path: &ModulePath) -> usize {
+	module_path.parent.as_ref().map(|parent| depth(parent) + 1).unwrap_or(0)
+}
+
+fn line_offset(string: &str, byte_offset: usize) -> usize {
+	string[..byte_offset].rfind('\n').map(|index| index + 1).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const SOURCE: &str = "module inner:\n\texport fn one() -> u32:\n\t\t1\n\n\
+		export data Point:\n\tx: u32,\n\ty: u32,\n\n\
+		data Shape:\n\t| Circle: radius: u32\n\t| Square: width: u32, height: u32\n\t| Empty\n\n\
+		data Pair<$T>:\n\tleft: $T,\n\tright: $T,\n\n\
+		interface Area:\n\tfn area(&self) -> u32\n\n\
+		define Area for Point:\n\tfn area(&self) -> u32:\n\t\tself.x * self.y\n\n\
+		define Point:\n\texport fn scale(~&self, factor: u32):\n\t\t\
+		self.x *= factor\n\t\tself.y *= factor\n\n\
+		fn size(shape: Shape) -> u32:\n\tmatch shape:\n\t\tCircle(radius): radius\n\t\t\
+		Square(width, height): width * height\n\t\tEmpty: 0\n\n\
+		fn swap<$T>(pair: ~&Pair<$T>):\n\tpair.left <=> pair.right\n\n\
+		fn total<$T: Area>(shapes: &[$T; 2]) -> u32:\n\tshapes[0].area() + shapes[1].area()\n\n\
+		export fn point() -> Point:\n\tlet ~point = Point: x: 1, y: 2\n\tpoint.scale(2)\n\tpoint\n\n\
+		#test\nfn small() -> truth:\n\tlet point = point()\n\tif:\n\t\tpoint.x == 2: true\n\t\ttrue: false\n";
+
+	fn formatted(string: &str) -> String {
+		let context = &crate::source::context(string);
+		let source_key = context.source_keys.clone().into_iter().next().unwrap().1;
+		source_format(context, source_key, string).unwrap()
+	}
+
+	#[test]
+	fn test_integer_literals() {
+		let string = "fn f() -> u32:\n\tlet x = 0xff_ffu32\n\tx + 0b1010 + 1_000\n";
+		assert_eq!(formatted(string), string);
+	}
+
+	#[test]
+	fn test_round_trip() {
+		assert_eq!(formatted(SOURCE), SOURCE);
+	}
+
+	#[test]
+	fn test_canonical() {
+		let string = "data Point:\n\tx: u32,\n\ty: u32\n\n\n\n\
+			fn  f(x : u32,y: u32)->u32:\n\tlet point = Point: x: x, y: y,\n\tpoint.x  +  y\n";
+		let canonical = "data Point:\n\tx: u32,\n\ty: u32,\n\n\
+			fn f(x: u32, y: u32) -> u32:\n\tlet point = Point: x, y\n\tpoint.x + y\n";
+		assert_eq!(formatted(string), canonical);
+		assert_eq!(formatted(canonical), canonical);
+	}
+
+	#[test]
+	fn test_check() {
+		let unformatted = "fn  f() -> u32:\n\t1\n";
+		let source = crate::source::TemporarySource::new(unformatted);
+		assert!(!format(&source.context(), true));
+		assert_eq!(std::fs::read_to_string(&source.0).unwrap(), unformatted);
+		assert!(format(&source.context(), false));
+		assert_eq!(std::fs::read_to_string(&source.0).unwrap(), "fn f() -> u32:\n\t1\n");
+		assert!(format(&source.context(), true));
+	}
+
+	#[test]
+	fn test_comments() {
+		let commented = "// Comment\nfn  f() -> u32:\n\t1 /* Comment */\n";
+		let source = crate::source::TemporarySource::new(commented);
+		assert!(format(&source.context(), true));
+		assert!(format(&source.context(), false));
+		assert_eq!(std::fs::read_to_string(&source.0).unwrap(), commented);
+	}
+
+	#[test]
+	fn test_examples() {
+		let context = &Context::default();
+		let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("examples/mathematics/main.lx");
+		crate::declaration::module_root(context, root);
+		crate::node::load_definitions(context);
+		assert!(context.errors.read().is_empty());
+
+		for (path, source_key) in context.source_keys.clone() {
+			let source = source_key.get(context);
+			let string = source.read_string().unwrap();
+			assert!(!has_comments(string, source_key), "{}", path.display());
+			assert_eq!(source_format(context, source_key, string).unwrap(), string, "{}", path.display());
+		}
+	}
+}
@@ -0,0 +1,5 @@
+pub use format::format;
+pub use printer::{ascription, fields, path, signature, variant};
+
+mod format;
+mod printer;
@@ -0,0 +1,348 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::declaration::{DeclarationPath, FunctionPath, InterfacePath, StructurePath};
+use crate::node::{Ascription, AscriptionPattern, BindingPattern, BindingVariable, Bound, Expression,
+	ExpressionKey, Execution, FunctionContext, FunctionType, Mutability, Parameter, Pattern,
+	UnaryOperator, VariablePattern, Variant};
+use crate::span::Span;
+
+type Field<'a> = (&'a Arc<str>, &'a (Span, ExpressionKey));
+
+/// Maximum line width before structure literals are split over multiple lines.
+const LINE_LIMIT: usize = 100;
+/// Width of an indentation when measuring lines.
+const TAB_WIDTH: usize = 4;
+
+/// Prints canonical source for the expressions of a function.
+#[derive(Debug)]
+pub struct Printer<'a> {
+	pub source: &'a str,
+	pub context: &'a FunctionContext,
+}
+
+impl<'a> Printer<'a> {
+	/// Prints an expression that starts on a line with the given indentation.
+	/// Lines after the first are prefixed with their indentation.
+	pub fn expression(&self, expression: &ExpressionKey, indent: usize) -> String {
+		let expression = &self.context[expression];
+		match &expression.node {
+			Expression::Block(block) => block.iter().enumerate().map(|(index, statement)| {
+				let byte_start = self.context[statement].span.byte_start;
+				let separator = match index > 0 && blank_before(self.source, byte_start) {
+					true => "\n\n",
+					false => "\n",
+				};
+
+				let indent = indent + 1;
+				format!("{}{}{}", separator, tabs(indent), self.expression(statement, indent))
+			}).collect(),
+			Expression::Binding(pattern, ascription, value) => {
+				let ascription = ascription.as_ref().map(|ascription|
+					format!(": {}", self::ascription(ascription))).unwrap_or_default();
+				format!("let {}{} = {}", binding(&pattern.node), ascription,
+					self.expression(value, indent))
+			}
+			Expression::TerminationLoop(start, end, body) => {
+				let start = start.as_ref().map(|start|
+					format!("{} ", self.expression(start, indent))).unwrap_or_default();
+				format!("loop {}=> {}:{}", start, self.expression(end, indent), self.body(body, indent))
+			}
+			Expression::Mutation(kind, left, right) => format!("{} {} {}",
+				self.expression(left, indent), kind.node, self.expression(right, indent)),
+			Expression::ExplicitDrop(pattern, value) => format!("drop {} = {}",
+				variable(pattern), self.expression(value, indent)),
+			Expression::Unary(operator, expression) => {
+				let operator = match &operator.node {
+					UnaryOperator::Negate => "-".to_owned(),
+					UnaryOperator::Not => "not ".to_owned(),
+					UnaryOperator::Dereference => "*".to_owned(),
+					UnaryOperator::Reference(permission) => permission.to_string(),
+				};
+				format!("{}{}", operator, self.expression(expression, indent))
+			}
+			Expression::Binary(operator, left, right) => format!("{} {} {}",
+				self.expression(left, indent), operator.node, self.expression(right, indent)),
+			Expression::Conditional(branches) => {
+				let branch = |(start, end, expression): &(ExpressionKey, Option<ExpressionKey>, ExpressionKey),
+				              indent: usize| {
+					let end = end.as_ref().map(|end|
+						format!(" => {}", self.expression(end, indent))).unwrap_or_default();
+					format!("{}{}:{}", self.expression(start, indent), end, self.body(expression, indent))
+				};
+
+				if let [single] = branches.as_slice() {
+					let line = format!("if {}", branch(single, indent));
+					let first = line.lines().next().unwrap_or_default();
+					if indent * TAB_WIDTH + first.len() <= LINE_LIMIT {
+						return line;
+					}
+				}
+
+				let indent = indent + 1;
+				let branches: String = branches.iter().map(|element|
+					format!("\n{}{}", tabs(indent), branch(element, indent))).collect();
+				format!("if:{}", branches)
+			}
+			Expression::Match(value, arms) => {
+				let arms: String = arms.iter().map(|arm| {
+					let bindings: Vec<_> = arm.bindings.iter().map(|binding| {
+						let BindingVariable(variable, mutability) = &binding.node;
+						format!("{}{}", self::mutability(mutability), variable.0)
+					}).collect();
+
+					let bindings = match bindings.is_empty() {
+						false => format!("({})", bindings.join(", ")),
+						true => String::new(),
+					};
+
+					format!("\n{}{}{}:{}", tabs(indent + 1), arm.variant.node,
+						bindings, self.body(&arm.expression, indent + 1))
+				}).collect();
+				format!("match {}:{}", self.expression(value, indent), arms)
+			}
+			Expression::Field(expression, field) =>
+				format!("{}.{}", self.expression(expression, indent), field.node),
+			Expression::Index(expression, index) => format!("{}[{}]",
+				self.expression(expression, indent), self.expression(index, indent)),
+			Expression::MethodCall(expression, method, arguments) => format!("{}.{}({})",
+				self.expression(expression, indent), method.node, self.arguments(arguments, indent)),
+			Expression::FunctionCall(path, arguments, execution) => {
+				let execution = match execution {
+					Execution::Compile => "#",
+					Execution::Runtime => "",
+				};
+
+				let FunctionPath(path) = &path.node;
+				format!("{}{}({})", execution, self::path(path), self.arguments(arguments, indent))
+			}
+			Expression::Structure(path, fields) => {
+				let StructurePath(path) = &path.node;
+				self.literal(self::path(path), fields, indent)
+			}
+			Expression::Variant(path, variant, fields) => {
+				let StructurePath(path) = &path.node;
+				let path = format!("{}::{}", self::path(path), variant.node);
+				self.literal(path, fields, indent)
+			}
+			Expression::Array(elements) => format!("[{}]", self.arguments(elements, indent)),
+			Expression::Pattern(Pattern::Terminal(expression)) =>
+				format!("({})", self.expression(expression, indent)),
+			Expression::Pattern(pattern) => self::pattern(pattern,
+				&mut |expression| self.expression(expression, indent)),
+			Expression::Variable(variable) => variable.0.to_string(),
+			// Literals keep their radix, separators and suffix.
+			Expression::Integer(_, _) =>
+				self.source[expression.span.byte_start..expression.span.byte_end].to_owned(),
+			Expression::Truth(truth) => truth.to_string(),
+			Expression::Item(item) => item.to_string(),
+		}
+	}
+
+	/// Prints an expression that follows a separator.
+	pub fn body(&self, expression: &ExpressionKey, indent: usize) -> String {
+		match &self.context[expression].node {
+			Expression::Block(_) => self.expression(expression, indent),
+			_ => format!(" {}", self.expression(expression, indent)),
+		}
+	}
+
+	/// Prints a structure literal. Literals without fields are printed
+	/// without a separator if the path cannot be mistaken for a variable.
+	fn literal(&self, path: String, fields: &HashMap<Arc<str>, (Span, ExpressionKey)>,
+	           indent: usize) -> String {
+		if fields.is_empty() && path.contains("::") {
+			return path;
+		}
+
+		let mut fields: Vec<_> = fields.iter().collect();
+		fields.sort_by_key(|(_, (span, _))| span.byte_start);
+		format!("{}:{}", path, self.fields(&fields, indent))
+	}
+
+	fn arguments(&self, arguments: &[ExpressionKey], indent: usize) -> String {
+		arguments.iter().map(|argument| self.expression(argument, indent))
+			.collect::<Vec<_>>().join(", ")
+	}
+
+	/// Prints the fields of a structure literal on a single line if the line is
+	/// short enough and only the last field is a structure literal. Fields on
+	/// separate lines are terminated with commas unless they are structure literals.
+	fn fields(&self, fields: &[Field], indent: usize) -> String {
+		let is_structure = |expression: &ExpressionKey|
+			match self.context[expression].node {
+				Expression::Structure(_, _) | Expression::Variant(_, _, _) => true,
+				_ => false,
+			};
+
+		let field = |(field, (_, expression)): &Field, indent: usize| match &self.context[expression].node {
+			Expression::Variable(variable) if &variable.0 == *field => field.to_string(),
+			_ => format!("{}: {}", field, self.expression(expression, indent)),
+		};
+
+		let separator = |expression: &ExpressionKey| match is_structure(expression) {
+			true => "",
+			false => ",",
+		};
+
+		let inline = format!(" {}", fields.iter().map(|element| field(element, indent))
+			.collect::<Vec<_>>().join(", "));
+
+		let is_inline = !inline.contains('\n') && indent * TAB_WIDTH + inline.len() <= LINE_LIMIT &&
+			fields.iter().rev().skip(1).all(|(_, (_, expression))| !is_structure(expression));
+		match is_inline {
+			true => inline,
+			false => fields.iter().map(|element| {
+				let (_, (_, expression)) = element;
+				format!("\n{}{}{}", tabs(indent + 1), field(element, indent + 1), separator(expression))
+			}).collect(),
+		}
+	}
+}
+
+/// Prints the signature of a function without the trailing separator.
+/// Return types that are omitted in the source are not printed.
+pub fn signature(identifier: &str, function_type: &FunctionType, source: &str) -> String {
+	let parameters: Vec<_> = function_type.parameters.iter()
+		.map(|parameter| self::parameter(&parameter.node)).collect();
+	let return_type = &function_type.return_type;
+	let return_type = match source[return_type.span.byte_start..return_type.span.byte_end].trim() {
+		":" | "" => String::new(),
+		_ => format!(" -> {}", self::ascription(&return_type.node)),
+	};
+
+	let bounds: Vec<_> = function_type.bounds.iter().map(|Bound(template, interfaces)|
+		match interfaces.is_empty() {
+			true => format!("${}", template.node),
+			false => format!("${}: {}", template.node, interfaces.iter().map(|interface| {
+				let InterfacePath(path) = &interface.node;
+				self::path(path)
+			}).collect::<Vec<_>>().join(" + ")),
+		}).collect();
+	let bounds = match bounds.is_empty() {
+		true => String::new(),
+		false => format!("<{}>", bounds.join(", ")),
+	};
+	format!("fn {}{}({}){}", identifier, bounds, parameters.join(", "), return_type)
+}
+
+/// Provides the fields of a structure or variant in the order they are declared.
+pub fn fields(fields: &HashMap<Arc<str>, AscriptionPattern>) -> Vec<(&Arc<str>, &AscriptionPattern)> {
+	let byte_start = |ascription: &AscriptionPattern| {
+		let mut byte_start = usize::max_value();
+		ascription.traverse(&mut |ascription| byte_start = byte_start.min(ascription.span.byte_start));
+		byte_start
+	};
+
+	let mut fields: Vec<_> = fields.iter().collect();
+	fields.sort_by_key(|(_, ascription)| byte_start(ascription));
+	fields
+}
+
+/// Prints a variant of a sum type with its fields on a single line.
+pub fn variant(variant: &Variant) -> String {
+	let fields: Vec<_> = fields(&variant.fields).into_iter().map(|(field, ascription)|
+		format!("{}: {}", field, self::ascription(ascription))).collect();
+	match fields.is_empty() {
+		false => format!("{}: {}", variant.identifier.node, fields.join(", ")),
+		true => variant.identifier.node.to_string(),
+	}
+}
+
+pub fn parameter(parameter: &Parameter) -> String {
+	let Parameter(pattern, ascription) = parameter;
+	match pattern {
+		Pattern::Terminal(variable) if (variable.node.0).0.as_ref() == "self" => {
+			let BindingVariable(_, mutability) = &variable.node;
+			match ascription {
+				Pattern::Terminal(ascription) => match &ascription.node {
+					Ascription::Reference(permission, lifetime, _) => format!("{}{}self",
+						permission, lifetime.as_ref().map(|lifetime|
+							format!("'{} ", lifetime.node)).unwrap_or_default()),
+					_ => format!("{}self", self::mutability(mutability)),
+				},
+				_ => "self".to_owned(),
+			}
+		}
+		_ => format!("{}: {}", binding(pattern), self::ascription(ascription)),
+	}
+}
+
+pub fn ascription(ascription: &AscriptionPattern) -> String {
+	pattern(ascription, &mut |ascription| match &ascription.node {
+		Ascription::Template(template) => format!("${}", template),
+		Ascription::Reference(permission, lifetime, ascription) => format!("{}{}{}", permission,
+			lifetime.as_ref().map(|lifetime| format!("'{} ", lifetime.node)).unwrap_or_default(),
+			self::ascription(ascription)),
+		Ascription::Array(element, length) =>
+			format!("[{}; {}]", self::ascription(element), length),
+		Ascription::Structure(StructurePath(path), templates) =>
+			match templates.is_empty() {
+				true => self::path(path),
+				false => format!("{}<{}>", self::path(path), templates.iter()
+					.map(self::ascription).collect::<Vec<_>>().join(", ")),
+			},
+	})
+}
+
+/// Prints a declaration path without unresolved module elements.
+pub fn path(path: &DeclarationPath) -> String {
+	let mut elements = vec![path.identifier.to_string()];
+	let mut module_path = Some(&path.module_path);
+	while let Some(module) = module_path {
+		if !module.is_unresolved() {
+			elements.push(module.identifier.to_string());
+		}
+		module_path = module.parent.as_ref();
+	}
+
+	elements.reverse();
+	elements.join("::")
+}
+
+pub fn tabs(indent: usize) -> String {
+	"\t".repeat(indent)
+}
+
+fn binding(pattern: &BindingPattern) -> String {
+	self::pattern(pattern, &mut |variable| {
+		let BindingVariable(variable, mutability) = &variable.node;
+		format!("{}{}", self::mutability(mutability), variable.0)
+	})
+}
+
+fn variable(pattern: &VariablePattern) -> String {
+	self::pattern(pattern, &mut |variable| variable.node.0.to_string())
+}
+
+fn mutability(mutability: &Mutability) -> &'static str {
+	match mutability {
+		Mutability::Immutable => "",
+		Mutability::Mutable => "~",
+	}
+}
+
+fn pattern<T, F>(pattern: &Pattern<T>, terminal: &mut F) -> String where F: FnMut(&T) -> String {
+	match pattern {
+		Pattern::Wildcard => "_".to_owned(),
+		Pattern::Terminal(element) => terminal(element),
+		Pattern::Tuple(elements) => {
+			let trailing = if elements.len() == 1 { "," } else { "" };
+			let elements: Vec<_> = elements.iter().map(|element|
+				self::pattern(element, terminal)).collect();
+			format!("({}{})", elements.join(", "), trailing)
+		}
+	}
+}
+
+/// Checks if the line preceding the byte offset contains only whitespace.
+fn blank_before(source: &str, byte_offset: usize) -> bool {
+	let line_start = source[..byte_offset].rfind('\n').map(|index| index + 1).unwrap_or(0);
+	match line_start.checked_sub(1) {
+		None => false,
+		Some(line_end) => {
+			let previous_start = source[..line_end].rfind('\n').map(|index| index + 1).unwrap_or(0);
+			source[previous_start..line_end].trim().is_empty()
+		}
+	}
+}
@@ -30,11 +30,15 @@ impl TypeEngine {
 					_ => self.resolve(inference_type),
 				}
 			}
-			InferenceType::Template(_) => Ok(TypeResolution::Template),
+			InferenceType::Template(template) => Ok(TypeResolution::Template(template.clone())),
 			InferenceType::Reference(permission, inference) => {
 				let resolution = Box::new(self.resolve(inference.clone())?);
 				Ok(TypeResolution::Reference(*permission, resolution))
 			}
+			InferenceType::Array(element, length) => {
+				let resolution = Box::new(self.resolve(element.clone())?);
+				Ok(TypeResolution::Array(resolution, *length))
+			}
 			InferenceType::Instance(structure, variables) => {
 				let variables: Result<_, _> = variables.iter()
 					.map(|variable| self.resolve(variable.clone())).collect();
@@ -54,6 +58,13 @@ impl TypeEngine {
 					true => self.unify(left_inference.clone(), right_inference.clone())?,
 				}
 			}
+			(InferenceType::Array(left_element, left_length),
+				InferenceType::Array(right_element, right_length)) => {
+				match left_length == right_length {
+					false => return Err(InferenceError::Unification(left, right)),
+					true => self.unify(left_element.clone(), right_element.clone())?,
+				}
+			}
 			(InferenceType::Template(_), InferenceType::Template(_)) => {
 				if left != right {
 					return Err(InferenceError::TemplateUnification(left, right));
@@ -69,6 +80,8 @@ impl TypeEngine {
 				representative.occurs(*variable)?;
 				self.union(right, left);
 			}
+			(InferenceType::Array(_, _), _) | (_, InferenceType::Array(_, _)) =>
+				return Err(InferenceError::Unification(left, right)),
 			(_, InferenceType::Template(_)) => self.unify(right, left)?,
 			(_, InferenceType::Reference(_, _)) => self.unify(right, left)?,
 			(InferenceType::Template(template), InferenceType::Instance(structure, _)) =>
@@ -144,6 +157,18 @@ mod tests {
 		assert!(engine.unify(variable, other).is_err());
 	}
 
+	#[test]
+	fn test_array_unification() {
+		let mut engine = TypeEngine::default();
+		let array = Arc::new(InferenceType::Array(engine.new_variable_type(), 2));
+		assert!(engine.unify(array.clone(), Arc::new(InferenceType::Array(Truth.inference(), 2))).is_ok());
+		assert!(engine.unify(array.clone(), Arc::new(InferenceType::Array(Truth.inference(), 3))).is_err());
+		assert!(engine.unify(array.clone(), Truth.inference()).is_err());
+
+		let element = Box::new(TypeResolution::Instance(Truth.structure(), Vec::new()));
+		assert_eq!(engine.resolve(array), Ok(TypeResolution::Array(element, 2)));
+	}
+
 	#[test]
 	fn test_template_inference() {
 		let mut engine = TypeEngine::default();
@@ -9,17 +9,25 @@ use crate::error::Diagnostic;
 use crate::node::{ExpressionKey, FunctionContext, Variable};
 use crate::span::{Span, Spanned};
 
-use super::{InferenceType, TypeEngine, TypeResolution, TypeVariable};
+use super::{InferenceError, InferenceType, Instantiation, TypeEngine, TypeResolution, TypeVariable};
 
-pub type TypeContexts = CHashMap<Arc<FunctionPath>, Arc<TypeContext>>;
+pub type TypeContexts = CHashMap<(Arc<FunctionPath>, Instantiation), Arc<TypeContext>>;
 
 #[derive(Debug, Default)]
 pub struct Environment {
+	pub instantiation: Instantiation,
 	variables: HashMap<Variable, (TypeVariable, Span)>,
 	expressions: HashMap<ExpressionKey, Arc<InferenceType>>,
+	templates: HashMap<ExpressionKey, HashMap<Arc<str>, Arc<InferenceType>>>,
+	deferred: Option<Arc<str>>,
+	projections: Vec<Arc<InferenceType>>,
 }
 
 impl Environment {
+	pub fn new(instantiation: Instantiation) -> Self {
+		Environment { instantiation, ..Environment::default() }
+	}
+
 	pub fn variable(&mut self, variable: Variable, type_variable: TypeVariable, span: Span) {
 		if self.variables.insert(variable.clone(), (type_variable, span)).is_some() {
 			panic!("Variable: {}, already exists in inference environment", variable);
@@ -32,17 +40,59 @@ impl Environment {
 		}
 	}
 
+	/// Records the templates of the function invoked by a call expression.
+	pub fn instantiation(&mut self, expression: ExpressionKey,
+	                     templates: HashMap<Arc<str>, Arc<InferenceType>>) {
+		if self.templates.insert(expression, templates).is_some() {
+			panic!("Expression: {:?}, already has an instantiation", expression);
+		}
+	}
+
+	/// Defers the checking of the function until the template is instantiated.
+	/// The projected type is resolved when the function is instantiated.
+	pub fn defer(&mut self, template: Arc<str>, projection: Arc<InferenceType>) {
+		self.deferred.get_or_insert(template);
+		self.projections.push(projection);
+	}
+
+	/// Defers a type that is projected from a deferred type.
+	pub fn propagate(&mut self, projection: Arc<InferenceType>) {
+		self.projections.push(projection);
+	}
+
+	/// Checks whether the type is projected from a template.
+	pub fn is_deferred(&self, engine: &mut TypeEngine, inference: &Arc<InferenceType>) -> bool {
+		let inference = engine.find(inference.clone());
+		self.projections.iter().any(|projection| engine.find(projection.clone()) == inference)
+	}
+
+	/// Resolves the inference types. Types projected from
+	/// templates are resolved as the wildcard template.
 	pub fn context(self, function: &FunctionContext, engine: &mut TypeEngine)
 	               -> Result<TypeContext, Diagnostic> {
+		let projections: Vec<_> = self.projections.iter()
+			.map(|projection| engine.find(projection.clone())).collect();
 		let construct = &mut |engine: &mut TypeEngine, inference: Arc<InferenceType>, span|
-			engine.resolve(inference).map_err(|error| Diagnostic::new(Spanned::new(error, span)));
+			match engine.resolve(inference) {
+				Err(InferenceError::Unresolved(variable)) if projections.iter()
+					.any(|projection| projection.as_ref() == &InferenceType::Variable(variable)) =>
+					Ok(TypeResolution::Template("_".into())),
+				other => other.map_err(|error| Diagnostic::new(Spanned::new(error, span))),
+			};
 		let variables = self.variables.into_iter().map(|(variable, (type_variable, span))|
 			construct(engine, Arc::new(InferenceType::Variable(type_variable)), span)
 				.map(|resolution| (variable, resolution))).collect::<Result<_, _>>()?;
 		let expressions = self.expressions.into_iter().map(|(expression, inference)|
 			construct(engine, inference, function[&expression].span)
 				.map(|resolution| (expression, resolution))).collect::<Result<_, _>>()?;
-		Ok(TypeContext { variables, expressions })
+		let templates = self.templates.into_iter().map(|(expression, templates)| {
+			let span = function[&expression].span;
+			let instantiation = templates.into_iter().map(|(template, inference)|
+				construct(engine, inference, span).map(|resolution| (template, resolution)))
+				.collect::<Result<_, _>>()?;
+			Ok((expression, instantiation))
+		}).collect::<Result<_, _>>()?;
+		Ok(TypeContext { variables, expressions, templates, deferred: self.deferred })
 	}
 }
 
@@ -80,6 +130,25 @@ impl IndexMut<&ExpressionKey> for Environment {
 pub struct TypeContext {
 	variables: HashMap<Variable, TypeResolution>,
 	expressions: HashMap<ExpressionKey, TypeResolution>,
+	templates: HashMap<ExpressionKey, Instantiation>,
+	deferred: Option<Arc<str>>,
+}
+
+impl TypeContext {
+	/// Provides the template whose projections defer the checking of the function.
+	pub fn deferred(&self) -> Option<&Arc<str>> {
+		self.deferred.as_ref()
+	}
+
+	/// Whether the function invokes functions with templates.
+	pub fn is_instantiating(&self) -> bool {
+		!self.templates.is_empty()
+	}
+
+	/// Provides the template resolutions of the function invoked by a call expression.
+	pub fn instantiation(&self, expression: &ExpressionKey) -> Option<&Instantiation> {
+		self.templates.get(expression)
+	}
 }
 
 impl Index<&Variable> for TypeContext {
@@ -2,10 +2,11 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::context::Context;
+use crate::declaration::StructurePath;
 use crate::error::Diagnostic;
 use crate::intrinsic::Intrinsic;
 use crate::node::*;
-use crate::span::Spanned;
+use crate::span::{Span, Spanned};
 
 use super::{Environment, InferenceError, InferenceType, pattern, TypeEngine};
 
@@ -74,6 +75,20 @@ fn inference_type(context: &Context, function: &FunctionContext, environment: &m
 					Diagnostic::new(Spanned::new(error, span)))?;
 				Ok(inference_type)
 			})?,
+		Expression::Match(value, arms) => {
+			expression(context, function, environment, engine, value)?;
+			arms.iter().try_fold(engine.new_variable_type(), |inference_type, arm| {
+				arm.bindings.iter().for_each(|binding| {
+					let BindingVariable(variable, _) = &binding.node;
+					environment.variable(variable.clone(), engine.new_variable(), binding.span);
+				});
+
+				let expression_type = expression(context, function, environment, engine, &arm.expression)?;
+				engine.unify(inference_type.clone(), expression_type).map_err(|error|
+					Diagnostic::new(Spanned::new(error, span)))?;
+				Ok(inference_type)
+			})?
+		}
 		Expression::Mutation(_, mutable, expression_key) => {
 			let mutable = expression(context, function, environment, engine, mutable)?;
 			let expression = expression(context, function, environment, engine, expression_key)?;
@@ -92,6 +107,13 @@ fn inference_type(context: &Context, function: &FunctionContext, environment: &m
 			expression(context, function, environment, engine, expression_key)?;
 			engine.new_variable_type()
 		}
+		Expression::Index(expression_key, index) => {
+			expression(context, function, environment, engine, expression_key)?;
+			let index_type = expression(context, function, environment, engine, index)?;
+			engine.unify(index_type, Intrinsic::Unsigned64.inference()).map_err(|error|
+				Diagnostic::new(Spanned::new(error, function[index].span)))?;
+			engine.new_variable_type()
+		}
 		Expression::MethodCall(expression_key, _, arguments) => {
 			expression(context, function, environment, engine, expression_key)?;
 			arguments.iter().try_for_each(|argument| expression(context,
@@ -118,12 +140,22 @@ fn inference_type(context: &Context, function: &FunctionContext, environment: &m
 				})?;
 
 			let return_ascription = &function_type.return_type.node;
-			pattern::ascription(environment, engine, templates, return_ascription)
+			let return_type = pattern::ascription(environment, engine, templates, return_ascription);
+			let instantiation = pattern::instantiation(engine, &function_type, templates);
+			if !instantiation.is_empty() {
+				environment.instantiation(*expression_key, instantiation);
+			}
+			return_type
 		}
 		Expression::Unary(operator, expression_key) => {
 			let inference = expression(context, function, environment, engine, expression_key)?;
 			match operator.node {
 				UnaryOperator::Negate => inference,
+				UnaryOperator::Not => {
+					engine.unify(inference.clone(), Intrinsic::Truth.inference()).map_err(|error|
+						Diagnostic::new(Spanned::new(error, span)))?;
+					inference
+				}
 				UnaryOperator::Reference(permission) =>
 					Arc::new(InferenceType::Reference(permission, inference)),
 				UnaryOperator::Dereference => engine.new_variable_type(),
@@ -136,44 +168,87 @@ fn inference_type(context: &Context, function: &FunctionContext, environment: &m
 				Diagnostic::new(Spanned::new(error, span)))?;
 			match operator.node {
 				BinaryOperator::Arithmetic(_) => left,
+				BinaryOperator::And | BinaryOperator::Or => {
+					engine.unify(left.clone(), Intrinsic::Truth.inference()).map_err(|error|
+						Diagnostic::new(Spanned::new(error, span)))?;
+					left
+				}
 				_ => Intrinsic::Truth.inference(),
 			}
 		}
 		Expression::Structure(structure_path, expressions) => {
-			let templates = &mut HashMap::new();
 			let structure_path = structure_path.clone().map(Arc::new);
 			let structure = crate::node::structure(context, &structure_path)?;
-			structure.fields.iter().try_for_each(|(field, pattern)| {
-				let (span, expression_key) = expressions.get(field).ok_or({
-					let structure_path = structure_path.node.clone();
-					let error = InferenceError::MissingField(structure_path, field.clone());
-					Diagnostic::new(Spanned::new(error, span))
-				})?;
+			if !structure.variants.is_empty() {
+				let error = InferenceError::ExpectedVariant(structure_path.node);
+				return Err(Diagnostic::new(Spanned::new(error, span)));
+			}
 
-				let field_type = pattern::ascription(environment, engine, templates, pattern);
-				let expression_type = expression(context, function, environment, engine, expression_key)?;
-				engine.unify(field_type, expression_type).map_err(|error|
-					Diagnostic::new(Spanned::new(error, *span)))
+			instance(context, function, environment, engine, &structure_path,
+				&structure, &structure.fields, expressions, span)?
+		}
+		Expression::Variant(structure_path, variant, expressions) => {
+			let structure_path = structure_path.clone().map(Arc::new);
+			let structure = crate::node::structure(context, &structure_path)?;
+			let (_, fields) = structure.variant(&variant.node).ok_or_else(|| {
+				let error = InferenceError::UndefinedVariant(structure_path.node.clone(), variant.node.clone());
+				Diagnostic::new(Spanned::new(error, variant.span))
 			})?;
 
-			expressions.iter().find(|(field, _)|
-				!structure.fields.contains_key(field.as_ref())).map(|(field, (span, _))| {
-				let structure_path = structure_path.node.clone();
-				let error = InferenceError::UndefinedField(structure_path, field.clone());
-				Result::<!, _>::Err(Diagnostic::new(Spanned::new(error, *span)))
-			}).transpose()?;
-
-			let templates = structure.templates.iter().map(|template| templates.get(&template.node)
-				.cloned().unwrap_or_else(|| engine.new_variable_type())).collect();
-			Arc::new(InferenceType::Instance(structure_path.node.as_ref().clone(), templates))
+			instance(context, function, environment, engine, &structure_path,
+				&structure, &fields.fields, expressions, span)?
+		}
+		Expression::Array(elements) => {
+			let element_type = engine.new_variable_type();
+			elements.iter().try_for_each(|element| {
+				let expression_type = expression(context, function, environment, engine, element)?;
+				engine.unify(element_type.clone(), expression_type).map_err(|error|
+					Diagnostic::new(Spanned::new(error, function[element].span)))
+			})?;
+			Arc::new(InferenceType::Array(element_type, elements.len()))
 		}
 		Expression::Pattern(pattern) =>
 			pattern::expression_pattern(context, function, environment, engine, pattern)?,
 		Expression::Variable(variable) =>
 			Arc::new(InferenceType::Variable(environment[variable])),
-		Expression::Integer(_) => engine.new_variable_type(),
+		Expression::Integer(_, intrinsic) => match intrinsic {
+			Some(intrinsic) => intrinsic.inference(),
+			None => engine.new_variable_type(),
+		},
 		Expression::Truth(_) => Intrinsic::Truth.inference(),
 		Expression::Item(item) => item.type_resolution()
 			.expect("Item has no type resolution").inference(engine),
 	})
 }
+
+/// Infers the type of a structure literal with the fields of the structure or its variant.
+fn instance(context: &Context, function: &FunctionContext, environment: &mut Environment,
+            engine: &mut TypeEngine, structure_path: &Spanned<Arc<StructurePath>>,
+            structure: &Structure, fields: &HashMap<Arc<str>, AscriptionPattern>,
+            expressions: &HashMap<Arc<str>, (Span, ExpressionKey)>, span: Span)
+            -> Result<Arc<InferenceType>, Diagnostic> {
+	let templates = &mut HashMap::new();
+	fields.iter().try_for_each(|(field, pattern)| {
+		let (span, expression_key) = expressions.get(field).ok_or({
+			let structure_path = structure_path.node.clone();
+			let error = InferenceError::MissingField(structure_path, field.clone());
+			Diagnostic::new(Spanned::new(error, span))
+		})?;
+
+		let field_type = pattern::ascription(environment, engine, templates, pattern);
+		let expression_type = expression(context, function, environment, engine, expression_key)?;
+		engine.unify(field_type, expression_type).map_err(|error|
+			Diagnostic::new(Spanned::new(error, *span)))
+	})?;
+
+	expressions.iter().find(|(field, _)| !fields.contains_key(field.as_ref()))
+		.map(|(field, (span, _))| {
+			let structure_path = structure_path.node.clone();
+			let error = InferenceError::UndefinedField(structure_path, field.clone());
+			Result::<!, _>::Err(Diagnostic::new(Spanned::new(error, *span)))
+		}).transpose()?;
+
+	let templates = structure.templates.iter().map(|template| templates.get(&template.node)
+		.cloned().unwrap_or_else(|| engine.new_variable_type())).collect();
+	Ok(Arc::new(InferenceType::Instance(structure_path.node.as_ref().clone(), templates)))
+}
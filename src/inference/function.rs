@@ -1,25 +1,43 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-use crate::basic::Projection;
+use crate::basic::Item;
 use crate::context::Context;
 use crate::declaration::FunctionPath;
-use crate::error::Diagnostic;
+use crate::error::{CompileError, Diagnostic};
+use crate::intrinsic::Intrinsic;
 use crate::node::*;
 use crate::span::{Span, Spanned};
 
-use super::{Environment, InferenceError, InferenceType, pattern, TypeContext, TypeEngine};
+use super::{Environment, InferenceError, InferenceType, Instantiation, pattern, TypeContext,
+	TypeEngine, TypeResolution};
+
+/// The maximum number of nested instantiations from a single function.
+const INSTANTIATION_LIMIT: usize = 64;
 
 pub fn function(context: &Context, function_path: &Spanned<Arc<FunctionPath>>)
                 -> Result<Arc<TypeContext>, Diagnostic> {
-	if let Some(type_context) = context.type_contexts.get(&function_path.node) {
+	instance(context, function_path, &Instantiation::new())
+}
+
+/// Infers the types of a function with its templates substituted by the instantiation.
+/// Functions that project on uninstantiated templates are checked when instantiated.
+pub fn instance(context: &Context, function_path: &Spanned<Arc<FunctionPath>>,
+                instantiation: &Instantiation) -> Result<Arc<TypeContext>, Diagnostic> {
+	instantiate(context, function_path, instantiation, 0)
+}
+
+fn instantiate(context: &Context, function_path: &Spanned<Arc<FunctionPath>>,
+               instantiation: &Instantiation, depth: usize) -> Result<Arc<TypeContext>, Diagnostic> {
+	let context_key = (function_path.node.clone(), instantiation.clone());
+	if let Some(type_context) = context.type_contexts.get(&context_key) {
 		return Ok(type_context.clone());
 	}
 
 	let function = crate::node::function(context, function_path)?;
 	let function_type = &function.function_type;
 
-	let mut environment = Environment::default();
+	let mut environment = Environment::new(instantiation.clone());
 	let engine = &mut TypeEngine::default();
 	for (index, parameter) in function_type.parameters.iter().enumerate() {
 		let Parameter(binding, ascription) = &parameter.node;
@@ -46,27 +64,200 @@ pub fn function(context: &Context, function_path: &Spanned<Arc<FunctionPath>>)
 		Diagnostic::new(Spanned::new(error, function_type.return_type.span)))?;
 
 	let type_context = Arc::new(environment.context(&function.context, engine)?);
-	context.type_contexts.insert(function_path.node.clone(), type_context.clone());
-	Ok(type_context)
+	context.type_contexts.insert(context_key.clone(), type_context.clone());
+	if type_context.deferred().is_some() {
+		return Ok(type_context);
+	}
+
+	// Recursive instantiations use the inserted context.
+	let checks = arithmetic(&function.context, &type_context)
+		.and_then(|_| literals(&function.context, &type_context))
+		.and_then(|_| bounds(context, &function, &type_context))
+		.and_then(|_| instances(context, &function, &type_context, depth))
+		.and_then(|_| crate::node::references(context, &function.context, &type_context))
+		.and_then(|_| crate::borrow::function(context, &function, &type_context));
+	if checks.is_err() {
+		context.type_contexts.remove(&context_key);
+	}
+
+	checks.map(|_| type_context)
+}
+
+/// Checks that arithmetic operands, compound assignments and integer literals
+/// are integers. Templates are checked when the function is instantiated.
+fn arithmetic(function: &FunctionContext, type_context: &TypeContext) -> Result<(), Diagnostic> {
+	for (index, expression) in function.expressions.iter().enumerate() {
+		let expression_key = ExpressionKey(index);
+		let operand = match &expression.node {
+			Expression::Integer(_, _) => expression_key,
+			Expression::Binary(operator, _, _) => match operator.node {
+				BinaryOperator::Arithmetic(_) => expression_key,
+				_ => continue,
+			},
+			Expression::Unary(operator, _) => match operator.node {
+				UnaryOperator::Negate => expression_key,
+				_ => continue,
+			},
+			Expression::Mutation(mutation, mutable, _) => match mutation.node {
+				MutationKind::Arithmetic(_) => *mutable,
+				_ => continue,
+			},
+			_ => continue,
+		};
+
+		let resolution = &type_context[&operand];
+		let is_integer = resolution.intrinsic().map(|intrinsic| intrinsic.is_integer());
+		if resolution.is_concrete() && is_integer != Some(true) {
+			let error = InferenceError::ExpectedInteger(resolution.clone());
+			return Err(Diagnostic::new(Spanned::new(error, expression.span)));
+		}
+	}
+	Ok(())
+}
+
+/// Checks that integer literals are within the range of their types.
+fn literals(function: &FunctionContext, type_context: &TypeContext) -> Result<(), Diagnostic> {
+	let negated: HashSet<_> = function.expressions.iter().filter_map(|expression| match &expression.node {
+		Expression::Unary(operator, inner) => match operator.node {
+			UnaryOperator::Negate => Some(*inner),
+			_ => None,
+		},
+		_ => None,
+	}).collect();
+
+	for (index, expression) in function.expressions.iter().enumerate() {
+		let expression_key = ExpressionKey(index);
+		let (magnitude, is_negative) = match &expression.node {
+			Expression::Integer(_, _) if negated.contains(&expression_key) => continue,
+			Expression::Integer(integer, _) => (*integer, false),
+			Expression::Unary(_, inner) if negated.contains(inner) => match function[inner].node {
+				Expression::Integer(integer, _) => (integer, true),
+				_ => continue,
+			},
+			_ => continue,
+		};
+
+		let resolution = &type_context[&expression_key];
+		if let Some(intrinsic) = resolution.intrinsic() {
+			if Item::literal(intrinsic, magnitude, is_negative).is_none() {
+				let integer = format!("{}{}", if is_negative { "-" } else { "" }, magnitude).into();
+				let error = InferenceError::IntegerRange(integer, resolution.clone());
+				return Err(Diagnostic::new(Spanned::new(error, expression.span)));
+			}
+		}
+	}
+	Ok(())
+}
+
+/// Checks that the bound templates of invoked functions are
+/// instantiated with types that implement the interfaces.
+fn bounds(context: &Context, function: &NodeFunction, type_context: &TypeContext) -> Result<(), Diagnostic> {
+	for (index, expression) in function.context.expressions.iter().enumerate() {
+		let expression_key = ExpressionKey(index);
+		let instantiation = match type_context.instantiation(&expression_key) {
+			Some(instantiation) => instantiation,
+			None => continue,
+		};
+
+		let path = invocation(&function.context, type_context, &expression_key);
+		let function_type = crate::node::function_type(context, &path)?;
+		for Bound(template, interfaces) in &function_type.bounds {
+			let resolution = &instantiation[&template.node];
+			for interface in interfaces {
+				let is_implemented = match resolution {
+					TypeResolution::Instance(structure, _) => context.node_implementations
+						.contains_key(&(structure.clone(), interface.node.clone())),
+					TypeResolution::Template(template) => function.function_type
+						.bounds(template).any(|bound| bound.node == interface.node),
+					TypeResolution::Reference(_, _) | TypeResolution::Array(_, _) => false,
+				};
+
+				if !is_implemented {
+					let error = InferenceError::Unimplemented(resolution.clone(), interface.node.clone());
+					return Err(Diagnostic::new(Spanned::new(error, expression.span)));
+				}
+			}
+		}
+	}
+	Ok(())
+}
+
+/// Checks the invoked functions with the instantiations that have no templates.
+/// Instantiations that nest beyond the limit are assumed to never terminate.
+fn instances(context: &Context, function: &NodeFunction, type_context: &TypeContext,
+             depth: usize) -> Result<(), Diagnostic> {
+	for (index, expression) in function.context.expressions.iter().enumerate() {
+		let expression_key = ExpressionKey(index);
+		let instantiation = match type_context.instantiation(&expression_key) {
+			Some(instantiation) if instantiation.values().all(TypeResolution::is_concrete) => instantiation,
+			_ => continue,
+		};
+
+		let path = invocation(&function.context, type_context, &expression_key);
+		if depth == INSTANTIATION_LIMIT {
+			let error = InferenceError::InstantiationDepth(path.node);
+			return Err(Diagnostic::new(Spanned::new(error, expression.span)));
+		}
+
+		// Only the outermost instantiation is noted for nested instantiations.
+		let is_nested = depth > 0;
+		instantiate(context, &path, instantiation, depth + 1).map_err(|diagnostic| match diagnostic.error.node {
+			CompileError::Inference(InferenceError::InstantiationDepth(_)) if is_nested => diagnostic,
+			_ => diagnostic.note(format!("Instantiated from: {}", expression.span.location(context))),
+		})?;
+	}
+	Ok(())
+}
+
+/// Provides the path of the function invoked by a call expression.
+fn invocation(function: &FunctionContext, type_context: &TypeContext,
+              expression: &ExpressionKey) -> Spanned<Arc<FunctionPath>> {
+	match &function[expression].node {
+		Expression::FunctionCall(path, _, _) => path.clone().map(Arc::new),
+		Expression::MethodCall(receiver, method, _) => {
+			let mut resolution = &type_context[receiver];
+			while let TypeResolution::Reference(_, inner) = resolution {
+				resolution = inner;
+			}
+
+			match resolution {
+				TypeResolution::Instance(structure, _) => method.clone().map(|method|
+					Arc::new(FunctionPath::method(structure.clone(), method))),
+				_ => unreachable!(),
+			}
+		}
+		_ => unreachable!(),
+	}
 }
 
 /// Resolves field and method call and dereference types.
 fn projection(context: &Context, function: &FunctionContext, environment: &mut Environment,
               engine: &mut TypeEngine) -> Result<(), Diagnostic> {
+	let matches: HashMap<_, _> = function.expressions.iter().enumerate()
+		.filter_map(|(index, expression)| match &expression.node {
+			Expression::Match(value, _) => Some((*value, ExpressionKey(index))),
+			_ => None,
+		}).collect();
+
 	for (index, expression) in function.expressions.iter().enumerate() {
 		let expression_key = ExpressionKey(index);
 		let expression_span = expression.span;
 		match &expression.node {
 			Expression::Field(receiver, field) => projection_field(context, function,
 				environment, engine, &expression_key, expression_span, receiver, &field)?,
+			Expression::Index(receiver, _) => projection_index(environment,
+				engine, &expression_key, expression_span, receiver)?,
 			Expression::MethodCall(receiver, method, arguments) => projection_method(context, function,
 				environment, engine, &expression_key, expression_span, receiver, method, arguments)?,
 			Expression::Unary(operator, expression) => match operator.node {
 				UnaryOperator::Dereference => {
 					let inference = engine.find(environment[expression].clone());
+					let is_deferred = environment.is_deferred(engine, &inference);
 					match &*inference {
 						InferenceType::Reference(_, inference) =>
 							engine.unify(environment[&expression_key].clone(), inference.clone()),
+						InferenceType::Variable(_) if is_deferred =>
+							Ok(environment.propagate(environment[&expression_key].clone())),
 						_ => Err(InferenceError::Dereference(inference)),
 					}.map_err(|error| Diagnostic::new(Spanned::new(error, expression_span)))?;
 				}
@@ -74,16 +265,85 @@ fn projection(context: &Context, function: &FunctionContext, environment: &mut E
 			}
 			_ => (),
 		}
+
+		// Arm bindings must be resolved before the projections within the arms.
+		if let Some(expression) = matches.get(&expression_key) {
+			projection_match(context, function, environment, engine, expression)?;
+		}
 	}
 	Ok(())
 }
 
+fn projection_match(context: &Context, function: &FunctionContext, environment: &mut Environment,
+                    engine: &mut TypeEngine, expression: &ExpressionKey) -> Result<(), Diagnostic> {
+	let (value, arms) = match &function[expression].node {
+		Expression::Match(value, arms) => (value, arms),
+		_ => unreachable!(),
+	};
+
+	let span = function[value].span;
+	let inference = engine.find(environment[value].clone());
+	let inference = dereference(engine, inference);
+	let is_deferred = environment.is_deferred(engine, &inference);
+	match &*inference {
+		InferenceType::Instance(path, inferences) => {
+			let path = Spanned::new(Arc::new(path.clone()), span);
+			let structure = crate::node::structure(context, &path)?;
+			let templates = &mut Iterator::zip(structure.templates.iter(), inferences.iter())
+				.map(|(template, inference)| (template.node.clone(), inference.clone()))
+				.collect();
+
+			for arm in arms {
+				let (_, variant) = structure.variant(&arm.variant.node).ok_or_else(|| {
+					let error = InferenceError::UndefinedVariant(path.node.clone(), arm.variant.node.clone());
+					Diagnostic::new(Spanned::new(error, arm.variant.span))
+				})?;
+
+				for binding in &arm.bindings {
+					let BindingVariable(variable, _) = &binding.node;
+					let Variable(field, _) = variable;
+					let field_type = variant.fields.get(field).ok_or_else(|| {
+						let error = InferenceError::UndefinedField(path.node.clone(), field.clone());
+						Diagnostic::new(Spanned::new(error, binding.span))
+					}).map(|pattern| super::pattern::ascription(environment, engine, templates, pattern))?;
+
+					let variable = Arc::new(InferenceType::Variable(environment[variable]));
+					engine.unify(variable, field_type).map_err(|error|
+						Diagnostic::new(Spanned::new(error, binding.span)))?;
+				}
+			}
+
+			match structure.variants.iter().find(|variant| !arms.iter()
+				.any(|arm| arm.variant.node == variant.identifier.node)) {
+				Some(variant) => Err(InferenceError::MissingVariant(path.node.clone(),
+					variant.identifier.node.clone())),
+				None => Ok(()),
+			}
+		}
+		InferenceType::Variable(_) if is_deferred => {
+			arms.iter().flat_map(|arm| arm.bindings.iter()).for_each(|binding| {
+				let BindingVariable(variable, _) = &binding.node;
+				let variable = Arc::new(InferenceType::Variable(environment[variable]));
+				environment.propagate(variable);
+			});
+			Ok(())
+		}
+		InferenceType::Variable(variable) =>
+			Err(InferenceError::Unresolved(*variable)),
+		InferenceType::Template(template) =>
+			Err(InferenceError::TemplateMatch(template.clone())),
+		InferenceType::Array(_, _) => Err(InferenceError::ArrayMatch(inference.clone())),
+		InferenceType::Reference(_, _) => unreachable!(),
+	}.map_err(|error| Diagnostic::new(Spanned::new(error, function[expression].span)))
+}
+
 fn projection_field(context: &Context, function: &FunctionContext, environment: &mut Environment,
                     engine: &mut TypeEngine, expression: &ExpressionKey, expression_span: Span,
                     receiver: &ExpressionKey, field: &Spanned<Arc<str>>) -> Result<(), Diagnostic> {
 	let span = function[receiver].span;
 	let inference = engine.find(environment[receiver].clone());
 	let inference = dereference(engine, inference);
+	let is_deferred = environment.is_deferred(engine, &inference);
 	match &*inference {
 		InferenceType::Instance(path, inferences) => {
 			let path = Spanned::new(Arc::new(path.clone()), span);
@@ -98,64 +358,141 @@ fn projection_field(context: &Context, function: &FunctionContext, environment:
 			}).map(|pattern| super::pattern::ascription(environment, engine, templates, pattern))?;
 			engine.unify(environment[expression].clone(), field_type)
 		}
+		InferenceType::Variable(_) if is_deferred =>
+			Ok(environment.propagate(environment[expression].clone())),
 		InferenceType::Variable(variable) =>
 			Err(InferenceError::Unresolved(*variable)),
-		InferenceType::Template(_) => {
-			let projection = Projection::Field(field.node.clone());
-			Err(InferenceError::TemplateProjection(projection))
-		}
+		InferenceType::Template(template) =>
+			Ok(environment.defer(template.clone(), environment[expression].clone())),
+		InferenceType::Array(_, _) => Err(InferenceError::ArrayField(field.node.clone())),
 		InferenceType::Reference(_, _) => unreachable!(),
 	}.map_err(|error| Diagnostic::new(Spanned::new(error, expression_span)))
 }
 
+fn projection_index(environment: &mut Environment, engine: &mut TypeEngine,
+                    expression: &ExpressionKey, expression_span: Span,
+                    receiver: &ExpressionKey) -> Result<(), Diagnostic> {
+	let inference = engine.find(environment[receiver].clone());
+	let inference = dereference(engine, inference);
+	let is_deferred = environment.is_deferred(engine, &inference);
+	match &*inference {
+		InferenceType::Array(element, _) =>
+			engine.unify(environment[expression].clone(), element.clone()),
+		InferenceType::Variable(_) if is_deferred =>
+			Ok(environment.propagate(environment[expression].clone())),
+		InferenceType::Variable(variable) =>
+			Err(InferenceError::Unresolved(*variable)),
+		_ => Err(InferenceError::Index(inference.clone())),
+	}.map_err(|error| Diagnostic::new(Spanned::new(error, expression_span)))
+}
+
+/// Checks that a receiver can be borrowed uniquely if the method requires it.
+/// Receivers that are not references must be mutable locations.
+fn receiver_permission(function: &FunctionContext, environment: &Environment, engine: &mut TypeEngine,
+                       receiver: &ExpressionKey, ascription: &Arc<InferenceType>) -> Result<(), Diagnostic> {
+	if let InferenceType::Reference(Permission::Unique, _) = engine.find(ascription.clone()).as_ref() {
+		let inference = engine.find(environment[receiver].clone());
+		return match (inference.as_ref(), function.receivers.get(receiver)) {
+			(InferenceType::Reference(Permission::Unique, _), _) => Ok(()),
+			(_, Some(error)) => Err(crate::node::mutation(error.clone())),
+			(InferenceType::Reference(Permission::Shared, _), None) => {
+				let error = InferenceError::Unification(inference.clone(), ascription.clone());
+				Err(Diagnostic::new(Spanned::new(error, function[receiver].span)))
+			}
+			_ => Ok(()),
+		};
+	}
+	Ok(())
+}
+
 fn projection_method(context: &Context, function: &FunctionContext, environment: &mut Environment,
                      engine: &mut TypeEngine, expression: &ExpressionKey, expression_span: Span,
                      receiver: &ExpressionKey, method: &Spanned<Arc<str>>,
                      arguments: &[ExpressionKey]) -> Result<(), Diagnostic> {
 	let inference = engine.find(environment[receiver].clone());
 	let inference = dereference(engine, inference);
-	match &*inference {
+	let is_deferred = environment.is_deferred(engine, &inference);
+	let mut templates = HashMap::new();
+	let function_type = match &*inference {
+		InferenceType::Instance(path, _) if path.is_string() && &*method.node == "length" => {
+			if !arguments.is_empty() {
+				let error = InferenceError::FunctionArity(arguments.len() + 1, 1);
+				return Err(Diagnostic::new(Spanned::new(error, method.span)));
+			}
+			return engine.unify(environment[expression].clone(), Intrinsic::Unsigned64.inference())
+				.map_err(|error| Diagnostic::new(Spanned::new(error, expression_span)));
+		}
 		InferenceType::Instance(path, _) => {
 			let function_path = method.clone().map(|method|
 				Arc::new(FunctionPath::method(path.clone(), method)));
-			let function_type = match context.declarations_function.contains_key(&function_path.node) {
-				true => crate::node::function_type(context, &function_path),
+			match context.declarations_function.contains_key(&function_path.node) {
+				true => crate::node::visible_method(context, &function.function_path, &function_path)
+					.and_then(|_| crate::node::function_type(context, &function_path)),
 				false => {
 					let error = InferenceError::UndefinedMethod(path.clone(), method.node.clone());
 					Err(Diagnostic::new(Spanned::new(error, method.span)))
 				}
-			}?;
+			}?
+		}
+		InferenceType::Template(template) => {
+			// Methods on templates are dispatched through the bounds of the function.
+			let path = Spanned::new(function.function_path.clone(), expression_span);
+			let function_type = crate::node::function_type(context, &path)?;
+			templates.insert("Self".into(), inference.clone());
+			crate::node::template_method(context, &function_type, template, &method.node)?
+				.ok_or_else(|| {
+					let error = InferenceError::TemplateMethodCall(method.node.clone());
+					Diagnostic::new(Spanned::new(error, expression_span))
+				})?
+		}
+		InferenceType::Variable(_) if is_deferred => {
+			// Arguments are checked against the method when the function is instantiated.
+			arguments.iter().for_each(|argument| environment.propagate(environment[argument].clone()));
+			return Ok(environment.propagate(environment[expression].clone()));
+		}
+		InferenceType::Variable(variable) => {
+			let error = InferenceError::Unresolved(*variable);
+			return Err(Diagnostic::new(Spanned::new(error, expression_span)));
+		}
+		InferenceType::Array(_, _) => {
+			let error = InferenceError::ArrayMethodCall(method.node.clone());
+			return Err(Diagnostic::new(Spanned::new(error, method.span)));
+		}
+		InferenceType::Reference(_, _) => unreachable!(),
+	};
 
-			if arguments.len() + 1 != function_type.parameters.len() {
-				let error = InferenceError::FunctionArity(arguments.len() + 1, function_type.parameters.len());
-				return Err(Diagnostic::new(Spanned::new(error, method.span)));
-			}
+	if arguments.len() + 1 != function_type.parameters.len() {
+		let error = InferenceError::FunctionArity(arguments.len() + 1, function_type.parameters.len());
+		return Err(Diagnostic::new(Spanned::new(error, method.span)));
+	}
 
-			let templates = &mut HashMap::new();
-			let Parameter(_, ascription) = &function_type.parameters.first().unwrap().node;
+	let templates = &mut templates;
+	let Parameter(_, ascription) = &function_type.parameters.first().unwrap().node;
+	let ascription = pattern::ascription(environment, engine, templates, ascription);
+	receiver_permission(function, environment, engine, receiver, &ascription)?;
+	let ascription = dereference(engine, ascription);
+	engine.unify(inference, ascription).map_err(|error|
+		Diagnostic::new(Spanned::new(error, function[receiver].span)))?;
+
+	Iterator::zip(arguments.iter(), function_type.parameters.iter().skip(1))
+		.try_for_each(|(argument, parameter)| {
+			let Parameter(_, ascription) = &parameter.node;
 			let ascription = pattern::ascription(environment, engine, templates, ascription);
-			let ascription = dereference(engine, ascription);
-			engine.unify(inference, ascription).map_err(|error|
-				Diagnostic::new(Spanned::new(error, function[receiver].span)))?;
-
-			Iterator::zip(arguments.iter(), function_type.parameters.iter().skip(1))
-				.try_for_each(|(argument, parameter)| {
-					let Parameter(_, ascription) = &parameter.node;
-					let ascription = pattern::ascription(environment, engine, templates, ascription);
-					engine.unify(environment[argument].clone(), ascription).map_err(|error|
-						Diagnostic::new(Spanned::new(error, function[argument].span)))
-				})?;
+			engine.unify(environment[argument].clone(), ascription).map_err(|error|
+				Diagnostic::new(Spanned::new(error, function[argument].span)))
+		})?;
 
-			let return_type = pattern::ascription(environment,
-				engine, templates, &function_type.return_type.node);
-			engine.unify(environment[expression].clone(), return_type)
-		}
-		InferenceType::Variable(variable) =>
-			Err(InferenceError::Unresolved(*variable)),
-		InferenceType::Template(_) =>
-			Err(InferenceError::TemplateMethodCall(method.node.clone())),
-		InferenceType::Reference(_, _) => unreachable!(),
-	}.map_err(|error| Diagnostic::new(Spanned::new(error, expression_span)))
+	// Dispatched methods are instantiated when the template is instantiated.
+	let is_dispatched = templates.contains_key("Self");
+	let instantiation = pattern::instantiation(engine, &function_type, templates);
+	if !is_dispatched && !instantiation.is_empty() {
+		environment.instantiation(*expression, instantiation);
+	}
+
+	let return_type = pattern::ascription(environment,
+		engine, templates, &function_type.return_type.node);
+	engine.unify(environment[expression].clone(), return_type)
+		.map_err(|error| Diagnostic::new(Spanned::new(error, expression_span)))
 }
 
 fn dereference(engine: &mut TypeEngine, mut inference: Arc<InferenceType>) -> Arc<InferenceType> {
@@ -164,3 +501,125 @@ fn dereference(engine: &mut TypeEngine, mut inference: Arc<InferenceType>) -> Ar
 	}
 	inference
 }
+
+#[cfg(test)]
+mod tests {
+	use crate::error::CompileError;
+	use crate::source::{context, function_path};
+
+	use super::*;
+
+	fn error(source: &str) -> Option<InferenceError> {
+		let context = &context(source);
+		match super::function(context, &function_path("f")) {
+			Ok(_) => None,
+			Err(diagnostic) => match diagnostic.error.node {
+				CompileError::Inference(error) => Some(error),
+				other => panic!("Expected inference error, instead got: {:?}", other),
+			},
+		}
+	}
+
+	#[test]
+	fn test_literal_range() {
+		let resolution = |intrinsic: Intrinsic| TypeResolution::Instance(intrinsic.structure(), Vec::new());
+		assert_eq!(error("fn f() -> u8:\n\t255\n"), None);
+		assert_eq!(error("fn f() -> u8:\n\t256\n"),
+			Some(InferenceError::IntegerRange("256".into(), resolution(Intrinsic::Unsigned8))));
+		assert_eq!(error("fn f() -> i8:\n\t-128\n"), None);
+		assert_eq!(error("fn f() -> i8:\n\t-129\n"),
+			Some(InferenceError::IntegerRange("-129".into(), resolution(Intrinsic::Signed8))));
+		assert_eq!(error("fn f() -> u128:\n\t340282366920938463463374607431768211455\n"), None);
+		assert_eq!(error("fn f() -> i128:\n\t-170141183460469231731687303715884105728\n"), None);
+		assert_eq!(error("fn f() -> u32:\n\t-1\n"),
+			Some(InferenceError::IntegerRange("-1".into(), resolution(Intrinsic::Unsigned32))));
+	}
+
+	#[test]
+	fn test_missing_variant() {
+		let shape = "data Shape:\n\t| Circle: radius: u32\n\t| Empty\n\n";
+		assert_eq!(error(&format!("{}fn f(shape: Shape) -> u32:\n\tmatch shape:\n\t\t\
+			Circle(radius): radius\n\t\tEmpty: 0\n", shape)), None);
+		match error(&format!("{}fn f(shape: Shape) -> u32:\n\tmatch shape:\n\t\t\
+			Circle(radius): radius\n", shape)) {
+			Some(InferenceError::MissingVariant(_, variant)) => assert_eq!(variant.as_ref(), "Empty"),
+			other => panic!("Expected missing variant error, instead got: {:?}", other),
+		}
+	}
+
+	#[test]
+	fn test_unimplemented() {
+		let shape = "interface Shape:\n\tfn area(&self) -> u32\n\n\
+			data Square:\n\tside: u32,\n\ndata Circle:\n\tradius: u32,\n\n\
+			define Shape for Square:\n\tfn area(&self) -> u32:\n\t\tself.side * self.side\n\n\
+			fn area<$T: Shape>(shape: &$T) -> u32:\n\tshape.area()\n\n";
+		assert_eq!(error(&format!("{}fn f() -> u32:\n\tarea(&Square: side: 2)\n", shape)), None);
+		match error(&format!("{}fn f() -> u32:\n\tarea(&Circle: radius: 2)\n", shape)) {
+			Some(InferenceError::Unimplemented(resolution, interface)) => {
+				assert_eq!(resolution.to_string(), "crate::Circle");
+				assert_eq!(interface.to_string(), "crate::Shape");
+			}
+			other => panic!("Expected unimplemented error, instead got: {:?}", other),
+		}
+	}
+
+	#[test]
+	fn test_deferred() {
+		let width = "fn f<$T>(shape: &$T) -> u32:\n\tlet width = shape.width\n\twidth + 1\n";
+		assert_eq!(error(width), None);
+		match error("fn f<$T>(shape: &$T) -> u32:\n\tlet value = 1\n\tshape.width\n") {
+			Some(InferenceError::Unresolved(_)) => (),
+			other => panic!("Expected unresolved error, instead got: {:?}", other),
+		}
+	}
+
+	#[test]
+	fn test_arithmetic() {
+		let truth = TypeResolution::Instance(Intrinsic::Truth.structure(), Vec::new());
+		assert_eq!(error("fn f() -> truth:\n\ttrue + false\n"),
+			Some(InferenceError::ExpectedInteger(truth.clone())));
+		let increment = "fn increment<$T>(value: $T) -> $T:\n\tvalue + 1\n\n";
+		assert_eq!(error(&format!("{}fn f() -> u32:\n\tincrement(1)\n", increment)), None);
+		assert_eq!(error(&format!("{}fn f() -> truth:\n\tincrement(true)\n", increment)),
+			Some(InferenceError::ExpectedInteger(truth.clone())));
+		let add = "fn add<$T>(~x: $T, y: $T) -> $T:\n\tx += y\n\tx\n\n";
+		assert_eq!(error(&format!("{}fn f() -> u32:\n\tadd(1, 2)\n", add)), None);
+		assert_eq!(error(&format!("{}fn f() -> truth:\n\tadd(true, false)\n", add)),
+			Some(InferenceError::ExpectedInteger(truth)));
+	}
+
+	#[test]
+	fn test_compound_assignment() {
+		let truth = TypeResolution::Instance(Intrinsic::Truth.structure(), Vec::new());
+		for operator in &["+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=", "<<=", ">>="] {
+			let integer = format!("fn f(~t: u8, u: u8) -> u8:\n\tt {} u\n\tt\n", operator);
+			assert_eq!(error(&integer), None);
+			let source = format!("fn f(~t: truth, u: truth) -> truth:\n\tt {} u\n\tt\n", operator);
+			assert_eq!(error(&source), Some(InferenceError::ExpectedInteger(truth.clone())));
+		}
+	}
+
+	#[test]
+	fn test_instantiation_depth() {
+		let nest = "fn nest<$T>(value: $T, depth: u32) -> u32:\n\tif:\n\t\t\
+			depth == 0: 0\n\t\ttrue: nest(&value, depth - 1)\n\n";
+		match error(&format!("{}fn f() -> u32:\n\tnest(1u32, 2)\n", nest)) {
+			Some(InferenceError::InstantiationDepth(function)) =>
+				assert_eq!(function.to_string(), "crate::nest"),
+			other => panic!("Expected instantiation depth error, instead got: {:?}", other),
+		}
+	}
+
+	#[test]
+	fn test_template_field() {
+		let context = &context("data Square:\n\twidth: u32,\n\n\
+			data Rectangle:\n\twidth: u32,\n\theight: u32,\n\n\
+			fn width<$T>(shape: &$T) -> u32:\n\tshape.width\n\n\
+			fn f() -> u32:\n\tlet square = Square: width: 3\n\t\
+			let rectangle = Rectangle: width: 4, height: 1\n\twidth(&square) + width(&rectangle)\n");
+		let item = crate::evaluation::function(context, &function_path("f"), Vec::new()).unwrap();
+		assert_eq!(item.collapse().unwrap(), Item::Unsigned32(7));
+		// The deferred function is checked with each structure.
+		assert_eq!(context.type_contexts.len(), 4);
+	}
+}
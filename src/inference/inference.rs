@@ -1,8 +1,8 @@
+use std::collections::BTreeMap;
 use std::fmt;
 use std::sync::Arc;
 
-use crate::basic::Projection;
-use crate::declaration::{ModulePath, StructurePath};
+use crate::declaration::{FunctionPath, InterfacePath, ModulePath, StructurePath};
 use crate::error::CompileError;
 use crate::intrinsic::Intrinsic;
 use crate::node::Permission;
@@ -18,11 +18,23 @@ pub enum InferenceError {
 	UndefinedField(Arc<StructurePath>, Arc<str>),
 	UndefinedMethod(StructurePath, Arc<str>),
 	MissingField(Arc<StructurePath>, Arc<str>),
+	UndefinedVariant(Arc<StructurePath>, Arc<str>),
+	MissingVariant(Arc<StructurePath>, Arc<str>),
+	ExpectedVariant(Arc<StructurePath>),
+	TemplateMatch(Arc<str>),
+	ArrayMatch(Arc<InferenceType>),
+	ArrayField(Arc<str>),
+	ArrayMethodCall(Arc<str>),
 	ResolvedTemplate(Arc<str>, StructurePath),
-	TemplateProjection(Projection),
 	TemplateMethodCall(Arc<str>),
 	TemplateUnification(Arc<InferenceType>, Arc<InferenceType>),
+	Unimplemented(TypeResolution, InterfacePath),
+	Uninstantiated(Arc<str>),
+	InstantiationDepth(Arc<FunctionPath>),
 	Dereference(Arc<InferenceType>),
+	Index(Arc<InferenceType>),
+	IntegerRange(Arc<str>, TypeResolution),
+	ExpectedInteger(TypeResolution),
 }
 
 impl fmt::Display for InferenceError {
@@ -42,16 +54,40 @@ impl fmt::Display for InferenceError {
 				write!(f, "Method: {}, is not defined on structure: {}", method, structure),
 			InferenceError::MissingField(structure, field) =>
 				write!(f, "Structure: {}, is missing field: {}", structure, field),
+			InferenceError::UndefinedVariant(structure, variant) =>
+				write!(f, "Variant: {}, is not defined on structure: {}", variant, structure),
+			InferenceError::MissingVariant(structure, variant) =>
+				write!(f, "Match on structure: {}, is missing variant: {}", structure, variant),
+			InferenceError::ExpectedVariant(structure) =>
+				write!(f, "Structure: {}, must be constructed with a variant", structure),
+			InferenceError::TemplateMatch(template) =>
+				write!(f, "Match cannot be performed on template: {}", template),
+			InferenceError::ArrayMatch(inference) =>
+				write!(f, "Match cannot be performed on array: {}", inference),
+			InferenceError::ArrayField(field) =>
+				write!(f, "Field: {}, cannot be accessed on an array", field),
+			InferenceError::ArrayMethodCall(identifier) =>
+				write!(f, "Method call: {}, cannot be performed on an array", identifier),
 			InferenceError::ResolvedTemplate(template, structure) =>
 				write!(f, "Template: {}, cannot be resolved to a structure: {}", template, structure),
-			InferenceError::TemplateProjection(projection) =>
-				write!(f, "Projection: {:?}, cannot be performed on a template", projection),
 			InferenceError::TemplateMethodCall(identifier) =>
 				write!(f, "Method call: {}, cannot be performed on a template", identifier),
 			InferenceError::TemplateUnification(left, right) =>
 				write!(f, "Templates: {}, and: {}, cannot match", left, right),
+			InferenceError::Unimplemented(resolution, interface) =>
+				write!(f, "Type: {}, does not implement interface: {}", resolution, interface),
+			InferenceError::Uninstantiated(template) =>
+				write!(f, "Template: ${}, has no instantiation", template),
+			InferenceError::InstantiationDepth(function) =>
+				write!(f, "Instantiations of function: {}, exceed the nesting limit", function),
 			InferenceError::Dereference(inference) =>
 				write!(f, "Dereference is not valid for type: {}", inference),
+			InferenceError::Index(inference) =>
+				write!(f, "Index is not valid for type: {}", inference),
+			InferenceError::IntegerRange(integer, resolution) =>
+				write!(f, "Integer: {}, is out of range for type: {}", integer, resolution),
+			InferenceError::ExpectedInteger(resolution) =>
+				write!(f, "Arithmetic is not valid for type: {}", resolution),
 		}
 	}
 }
@@ -76,6 +112,8 @@ impl fmt::Display for TypeVariable {
 pub enum InferenceType {
 	Instance(StructurePath, Vec<Arc<InferenceType>>),
 	Reference(Permission, Arc<InferenceType>),
+	/// Contains the element type and the length of the array.
+	Array(Arc<InferenceType>, usize),
 	Variable(TypeVariable),
 	Template(Arc<str>),
 }
@@ -85,7 +123,8 @@ impl InferenceType {
 		match self {
 			InferenceType::Instance(_, variables) => variables.iter()
 				.try_for_each(|type_variable| type_variable.occurs(variable)),
-			InferenceType::Reference(_, inference) => inference.occurs(variable),
+			InferenceType::Reference(_, inference) |
+			InferenceType::Array(inference, _) => inference.occurs(variable),
 			InferenceType::Template(_) => Ok(()),
 			InferenceType::Variable(type_variable) => {
 				match type_variable == &variable {
@@ -104,6 +143,7 @@ impl fmt::Display for InferenceType {
 			InferenceType::Template(variable) => write!(f, "${}", variable),
 			InferenceType::Reference(permission, inference) =>
 				write!(f, "{}{}", permission, inference),
+			InferenceType::Array(element, length) => write!(f, "[{}; {}]", element, length),
 			InferenceType::Instance(structure, variables) => {
 				write!(f, "{}", structure)?;
 				variables.split_last().map(|(last, slice)| {
@@ -116,17 +156,30 @@ impl fmt::Display for InferenceType {
 	}
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// The resolutions of the templates of a function at a call site.
+pub type Instantiation = BTreeMap<Arc<str>, TypeResolution>;
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub enum TypeResolution {
 	Instance(StructurePath, Vec<TypeResolution>),
 	Reference(Permission, Box<TypeResolution>),
-	Template,
+	/// Contains the element type and the length of the array.
+	Array(Box<TypeResolution>, usize),
+	Template(Arc<str>),
 }
 
 impl TypeResolution {
+	pub fn is_array(&self) -> bool {
+		match self {
+			TypeResolution::Array(_, _) => true,
+			_ => false,
+		}
+	}
+
 	pub fn intrinsic(&self) -> Option<Intrinsic> {
 		match self {
-			TypeResolution::Template | TypeResolution::Reference(_, _) => None,
+			TypeResolution::Template(_) | TypeResolution::Reference(_, _) |
+			TypeResolution::Array(_, _) => None,
 			TypeResolution::Instance(StructurePath(path), parameters) => {
 				let is_intrinsic = path.module_path == ModulePath::intrinsic();
 				match is_intrinsic && parameters.is_empty() {
@@ -137,11 +190,24 @@ impl TypeResolution {
 		}
 	}
 
+	/// Whether the resolution contains no templates.
+	pub fn is_concrete(&self) -> bool {
+		match self {
+			TypeResolution::Template(_) => false,
+			TypeResolution::Reference(_, resolution) |
+			TypeResolution::Array(resolution, _) => resolution.is_concrete(),
+			TypeResolution::Instance(_, resolutions) =>
+				resolutions.iter().all(TypeResolution::is_concrete),
+		}
+	}
+
 	pub fn inference(&self, engine: &mut TypeEngine) -> Arc<InferenceType> {
 		match self {
-			TypeResolution::Template => engine.new_variable_type(),
+			TypeResolution::Template(_) => engine.new_variable_type(),
 			TypeResolution::Reference(permission, resolution) =>
 				Arc::new(InferenceType::Reference(*permission, resolution.inference(engine))),
+			TypeResolution::Array(element, length) =>
+				Arc::new(InferenceType::Array(element.inference(engine), *length)),
 			TypeResolution::Instance(structure, resolutions) => {
 				let inferences = resolutions.iter().map(|resolution|
 					resolution.inference(engine)).collect();
@@ -154,11 +220,12 @@ impl TypeResolution {
 impl fmt::Display for TypeResolution {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		match self {
-			TypeResolution::Template => write!(f, "$"),
+			TypeResolution::Template(template) => write!(f, "${}", template),
 			TypeResolution::Reference(permission, resolution) => match permission {
 				Permission::Shared => write!(f, "&{}", resolution),
 				Permission::Unique => write!(f, "~&{}", resolution),
 			}
+			TypeResolution::Array(element, length) => write!(f, "[{}; {}]", element, length),
 			TypeResolution::Instance(structure, resolutions) => {
 				write!(f, "{}", structure)?;
 				resolutions.split_last().map(|(last, slice)| {
@@ -2,9 +2,9 @@ use engine::TypeEngine;
 pub use environment::{TypeContext, TypeContexts};
 use environment::Environment;
 use expression::expression;
-pub use function::function;
+pub use function::{function, instance};
 use inference::{InferenceType, TypeVariable};
-pub use inference::{InferenceError, TypeResolution};
+pub use inference::{InferenceError, Instantiation, TypeResolution};
 
 mod inference;
 mod function;
@@ -4,8 +4,8 @@ use std::sync::Arc;
 use crate::context::Context;
 use crate::error::Diagnostic;
 use crate::intrinsic::Intrinsic;
-use crate::node::{Ascription, AscriptionPattern, BindingPattern, BindingVariable,
-	ExpressionPattern, FunctionContext, Pattern, VariablePattern};
+use crate::node::{Ascription, AscriptionPattern, BindingPattern, BindingVariable, Bound,
+	ExpressionPattern, FunctionContext, FunctionType, Pattern, VariablePattern};
 
 use super::{Environment, InferenceType, TypeEngine};
 
@@ -64,6 +64,10 @@ pub fn ascription(environment: &mut Environment, engine: &mut TypeEngine,
 					ascription(environment, engine, templates, template)).collect();
 				Arc::new(InferenceType::Instance(structure.clone(), templates))
 			}
+			Ascription::Array(element, length) => {
+				let element = ascription(environment, engine, templates, element);
+				Arc::new(InferenceType::Array(element, *length))
+			}
 		}
 		Pattern::Tuple(patterns) => {
 			let ascription_types = patterns.iter().map(|pattern|
@@ -73,15 +77,31 @@ pub fn ascription(environment: &mut Environment, engine: &mut TypeEngine,
 	}
 }
 
+/// Takes the inference types of the templates of a function at a call site.
+/// Bound templates that do not appear in the function signature are left unresolved.
+pub fn instantiation(engine: &mut TypeEngine, function_type: &FunctionType,
+                     templates: &HashMap<Arc<str>, Arc<InferenceType>>)
+                     -> HashMap<Arc<str>, Arc<InferenceType>> {
+	let mut instantiation = templates.clone();
+	function_type.bounds.iter().for_each(|Bound(template, _)| {
+		instantiation.entry(template.node.clone())
+			.or_insert_with(|| engine.new_variable_type());
+	});
+	instantiation
+}
+
 /// Constructs an inference type from an ascription.
-/// Templates are constructed as template inference types.
+/// Templates are substituted by the instantiation or otherwise
+/// constructed as template inference types.
 pub fn template_type(environment: &mut Environment, engine: &mut TypeEngine,
                      ascription: &AscriptionPattern) -> Arc<InferenceType> {
 	match ascription {
 		Pattern::Wildcard => engine.new_variable_type(),
 		Pattern::Terminal(terminal) => match &terminal.node {
-			Ascription::Template(template) =>
-				Arc::new(InferenceType::Template(template.clone())),
+			Ascription::Template(template) => match environment.instantiation.get(template) {
+				Some(resolution) => resolution.inference(engine),
+				None => Arc::new(InferenceType::Template(template.clone())),
+			},
 			Ascription::Reference(permission, _, reference) => {
 				let ascription = template_type(environment, engine, reference);
 				Arc::new(InferenceType::Reference(*permission, ascription))
@@ -91,6 +111,10 @@ pub fn template_type(environment: &mut Environment, engine: &mut TypeEngine,
 					template_type(environment, engine, template)).collect();
 				Arc::new(InferenceType::Instance(structure.clone(), templates))
 			}
+			Ascription::Array(element, length) => {
+				let element = template_type(environment, engine, element);
+				Arc::new(InferenceType::Array(element, *length))
+			}
 		}
 		Pattern::Tuple(patterns) => {
 			let ascription_types = patterns.iter().map(|pattern|
@@ -0,0 +1,310 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::basic::{Instance, Item};
+use crate::context::Context;
+use crate::declaration::{FunctionPath, ModulePath, StructurePath};
+use crate::error::Diagnostic;
+use crate::evaluation::EvaluationError;
+use crate::inference::{InferenceError, TypeResolution};
+use crate::intrinsic::Intrinsic;
+use crate::lexer::{Lexer, Token};
+use crate::node::{Ascription, AscriptionPattern, Expression, ExpressionKey,
+	ExpressionPattern, FunctionContext, Pattern, UnaryOperator};
+use crate::source::SourceKey;
+use crate::span::{Span, Spanned};
+
+#[derive(Debug)]
+pub struct FunctionCall {
+	pub path: Spanned<Arc<FunctionPath>>,
+	pub function: FunctionContext,
+	pub arguments: Vec<ExpressionKey>,
+}
+
+/// Parses a function path with optional parenthesized arguments.
+pub fn function_call(string: &str) -> Result<FunctionCall, Diagnostic> {
+	let lexer = &mut Lexer::new(string, 0, SourceKey::INTERNAL);
+	let path = crate::parser::path(lexer)?.map(|mut path| {
+		path.module_path = path.module_path.tail();
+		Arc::new(FunctionPath(path))
+	});
+
+	let mut arguments = Vec::new();
+	let mut function = FunctionContext::new(path.node.clone());
+	if lexer.peek().node == Token::ParenthesisOpen {
+		crate::parser::list(lexer.consume(), Token::ParenthesisClose, Token::ListSeparator,
+			&mut |lexer| Ok(arguments.push(crate::parser::root_value(&mut function, lexer)
+				.map_err(|diagnostic| diagnostic.note("In parsing an argument"))?)))?;
+		crate::parser::expect(lexer, Token::ParenthesisClose)?;
+	}
+
+	crate::parser::expect(lexer, Token::End)?;
+	Ok(FunctionCall { path, function, arguments })
+}
+
+impl FunctionCall {
+	/// Converts the argument literals into items of the parameter types.
+	pub fn items(&self, context: &Context, string: &str,
+	             parameters: &[TypeResolution]) -> Result<Vec<Item>, Diagnostic> {
+		if self.arguments.len() != parameters.len() {
+			let error = InferenceError::FunctionArity(self.arguments.len(), parameters.len());
+			return Err(Diagnostic::new(Spanned::new(error, self.path.span)));
+		}
+
+		Iterator::zip(self.arguments.iter(), parameters.iter()).map(|(argument, parameter)| {
+			let span = self.function[argument].span;
+			item(context, &self.function, argument, parameter).map_err(|diagnostic| diagnostic
+				.note(format!("In argument: {}", &string[span.byte_start..span.byte_end])))
+		}).collect()
+	}
+}
+
+fn item(context: &Context, function: &FunctionContext, expression: &ExpressionKey,
+        resolution: &TypeResolution) -> Result<Item, Diagnostic> {
+	let span = function[expression].span;
+	match &function[expression].node {
+		Expression::Integer(integer, _) => integer_item(resolution, *integer, false, span),
+		Expression::Unary(operator, expression) => match (&operator.node, &function[expression].node) {
+			(UnaryOperator::Negate, Expression::Integer(integer, _)) =>
+				integer_item(resolution, *integer, true, span),
+			_ => Err(Diagnostic::new(Spanned::new(EvaluationError::ArgumentLiteral, span))),
+		},
+		Expression::Truth(truth) => match resolution.intrinsic() {
+			Some(Intrinsic::Truth) => Ok(Item::Truth(*truth)),
+			_ => Err(type_error(resolution, span)),
+		},
+		Expression::Item(item) => match item.type_resolution().as_ref() == Some(resolution) {
+			true => Ok(item.clone()),
+			false => Err(type_error(resolution, span)),
+		},
+		Expression::Pattern(pattern) => pattern_item(context, function, pattern, resolution, span),
+		Expression::Array(elements) => match resolution {
+			TypeResolution::Array(element_resolution, length) if *length == elements.len() => {
+				let mut instance = Instance::new(resolution.clone());
+				for (index, element) in elements.iter().enumerate() {
+					let item = item(context, function, element, element_resolution)?;
+					instance.fields.insert(index.to_string().into(), item);
+				}
+				Ok(Item::Instance(instance))
+			}
+			_ => Err(type_error(resolution, span)),
+		},
+		Expression::Structure(structure_path, fields) => {
+			let (structure_path, variant) = {
+				let module_contexts = context.module_contexts.read();
+				let module_context = module_contexts.get(&ModulePath::root()).unwrap();
+				match crate::node::resolve_variant(context, module_context, structure_path) {
+					Some(path) => {
+						let StructurePath(declaration_path) = &structure_path.node;
+						(path, Some(declaration_path.identifier.clone()))
+					}
+					None => {
+						let mut structure_path = structure_path.clone();
+						crate::node::resolve_structure_path(context,
+							module_context, &mut structure_path)?;
+						(structure_path, None)
+					}
+				}
+			};
+
+			instance_item(context, function, resolution, structure_path, variant, fields, span)
+		}
+		Expression::Variant(structure_path, variant, fields) => {
+			let mut structure_path = structure_path.clone();
+			{
+				let module_contexts = context.module_contexts.read();
+				let module_context = module_contexts.get(&ModulePath::root()).unwrap();
+				crate::node::resolve_structure_path(context, module_context, &mut structure_path)?;
+			}
+
+			instance_item(context, function, resolution, structure_path,
+				Some(variant.node.clone()), fields, span)
+		}
+		_ => Err(Diagnostic::new(Spanned::new(EvaluationError::ArgumentLiteral, span))),
+	}
+}
+
+/// Constructs a structure or variant instance from the fields of a literal.
+fn instance_item(context: &Context, function: &FunctionContext, resolution: &TypeResolution,
+                 structure_path: Spanned<StructurePath>, variant: Option<Arc<str>>,
+                 fields: &HashMap<Arc<str>, (Span, ExpressionKey)>, span: Span) -> Result<Item, Diagnostic> {
+	let (path, templates) = match resolution {
+		TypeResolution::Instance(path, templates) if path == &structure_path.node =>
+			(path, templates),
+		_ => return Err(type_error(resolution, span)),
+	};
+
+	let path = Spanned::new(Arc::new(path.clone()), structure_path.span);
+	let structure = crate::node::structure(context, &path)?;
+	let templates = Iterator::zip(structure.templates.iter(), templates.iter())
+		.map(|(template, resolution)| (template.node.clone(), resolution.clone())).collect();
+
+	let mut instance = Instance::new(resolution.clone());
+	let ascriptions = match variant {
+		None if structure.variants.is_empty() => &structure.fields,
+		None => {
+			let error = InferenceError::ExpectedVariant(path.node.clone());
+			return Err(Diagnostic::new(Spanned::new(error, span)));
+		}
+		Some(variant) => match structure.variant(&variant) {
+			Some((index, other)) => {
+				instance.variant = Some((index, variant));
+				&other.fields
+			}
+			None => {
+				let error = InferenceError::UndefinedVariant(path.node.clone(), variant);
+				return Err(Diagnostic::new(Spanned::new(error, span)));
+			}
+		},
+	};
+
+	for (field, ascription) in ascriptions {
+		let (_, expression) = fields.get(field).ok_or_else(|| {
+			let error = InferenceError::MissingField(path.node.clone(), field.clone());
+			Diagnostic::new(Spanned::new(error, span))
+		})?;
+
+		let resolution = ascription_resolution(ascription, &templates);
+		let item = item(context, function, expression, &resolution)?;
+		instance.fields.insert(field.clone(), item);
+	}
+
+	match fields.iter().find(|(field, _)| !ascriptions.contains_key(*field)) {
+		None => Ok(Item::Instance(instance)),
+		Some((field, (span, _))) => {
+			let error = InferenceError::UndefinedField(path.node.clone(), field.clone());
+			Err(Diagnostic::new(Spanned::new(error, *span)))
+		}
+	}
+}
+
+fn integer_item(resolution: &TypeResolution, magnitude: u128, is_negative: bool,
+                span: Span) -> Result<Item, Diagnostic> {
+	resolution.intrinsic().and_then(|intrinsic| Item::literal(intrinsic, magnitude, is_negative))
+		.ok_or_else(|| type_error(resolution, span))
+}
+
+fn pattern_item(context: &Context, function: &FunctionContext, pattern: &ExpressionPattern,
+                resolution: &TypeResolution, span: Span) -> Result<Item, Diagnostic> {
+	match pattern {
+		Pattern::Wildcard => Err(Diagnostic::new(Spanned::new(EvaluationError::ArgumentLiteral, span))),
+		Pattern::Terminal(expression) => item(context, function, expression, resolution),
+		Pattern::Tuple(patterns) => match resolution {
+			TypeResolution::Instance(path, resolutions) if path == &Intrinsic::Tuple.structure()
+				&& resolutions.len() == patterns.len() => {
+				let mut instance = Instance::new(resolution.clone());
+				for (index, (pattern, resolution)) in patterns.iter().zip(resolutions).enumerate() {
+					let item = pattern_item(context, function, pattern, resolution, span)?;
+					instance.fields.insert(index.to_string().into(), item);
+				}
+				Ok(Item::Instance(instance))
+			}
+			_ => Err(type_error(resolution, span)),
+		}
+	}
+}
+
+/// Constructs the type resolution of an ascription with the template resolutions substituted.
+pub fn ascription_resolution(pattern: &AscriptionPattern,
+                             templates: &HashMap<Arc<str>, TypeResolution>) -> TypeResolution {
+	match pattern {
+		Pattern::Wildcard => TypeResolution::Template("_".into()),
+		Pattern::Terminal(terminal) => match &terminal.node {
+			Ascription::Template(template) => templates.get(template)
+				.cloned().unwrap_or_else(|| TypeResolution::Template(template.clone())),
+			Ascription::Reference(permission, _, pattern) => TypeResolution::Reference(*permission,
+				Box::new(ascription_resolution(pattern, templates))),
+			Ascription::Structure(structure, patterns) => TypeResolution::Instance(structure.clone(),
+				patterns.iter().map(|pattern| ascription_resolution(pattern, templates)).collect()),
+			Ascription::Array(pattern, length) => TypeResolution::Array(Box::new(
+				ascription_resolution(pattern, templates)), *length),
+		},
+		Pattern::Tuple(patterns) => TypeResolution::Instance(Intrinsic::Tuple.structure(),
+			patterns.iter().map(|pattern| ascription_resolution(pattern, templates)).collect()),
+	}
+}
+
+fn type_error(resolution: &TypeResolution, span: Span) -> Diagnostic {
+	Diagnostic::new(Spanned::new(EvaluationError::ArgumentType(resolution.clone()), span))
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::basic::Reversibility;
+	use crate::source::{context, function_path};
+
+	use super::*;
+
+	const SOURCE: &str = "data Point:\n\tx: u32,\n\ty: i8,\n\n\
+		data Shape:\n\t| Circle: radius: u32\n\t| Empty\n\n\
+		fn f(values: [u32; 2], label: string, shape: Shape, point: Point) -> u32:\n\t0\n";
+
+	fn items(context: &Context, string: &str) -> Result<Vec<Item>, Diagnostic> {
+		let function = crate::basic::function(context, &function_path("f"), Reversibility::Entropic)?;
+		function_call(string)?.items(context, string, &function.parameters)
+	}
+
+	fn field<'a>(item: &'a Item, field: &str) -> &'a Item {
+		match item {
+			Item::Instance(instance) => &instance.fields[field],
+			other => panic!("Expected instance item, instead got: {}", other),
+		}
+	}
+
+	#[test]
+	fn test_function_call() {
+		let call = function_call("crate::f([1, 2], \"label\", Shape::Empty, Point: x: 1, y: -2)").unwrap();
+		assert_eq!(call.path.node.to_string(), "crate::f");
+		assert_eq!(call.arguments.len(), 4);
+		match &call.function[&call.arguments[0]].node {
+			Expression::Array(elements) => assert_eq!(elements.len(), 2),
+			other => panic!("Expected array expression, instead got: {:?}", other),
+		}
+
+		match &call.function[&call.arguments[1]].node {
+			Expression::Item(Item::String(string)) => assert_eq!(string.as_ref(), "label"),
+			other => panic!("Expected string expression, instead got: {:?}", other),
+		}
+
+		match &call.function[&call.arguments[3]].node {
+			Expression::Structure(_, fields) => assert_eq!(fields.len(), 2),
+			other => panic!("Expected structure expression, instead got: {:?}", other),
+		}
+		assert!(function_call("crate::f(1").is_err());
+	}
+
+	#[test]
+	fn test_items() {
+		let context = &context(SOURCE);
+		// Structure arguments that are followed by other arguments are parenthesized.
+		let arguments = items(context, "crate::f([1, 2], \"label\", \
+			(Shape::Circle: radius: 5), Point: x: 1, y: -2)").unwrap();
+		assert_eq!(field(&arguments[0], "1"), &Item::Unsigned32(2));
+		assert_eq!(arguments[1], Item::String("label".into()));
+		match &arguments[2] {
+			Item::Instance(instance) => assert_eq!(instance.variant, Some((0, "Circle".into()))),
+			other => panic!("Expected instance item, instead got: {}", other),
+		}
+		assert_eq!(field(&arguments[2], "radius"), &Item::Unsigned32(5));
+		assert_eq!(field(&arguments[3], "x"), &Item::Unsigned32(1));
+		assert_eq!(field(&arguments[3], "y"), &Item::Signed8(-2));
+
+		let arguments = items(context, "crate::f([3, 4], \"\", Shape::Empty, Point: x: 0, y: 0)");
+		match &arguments.unwrap()[2] {
+			Item::Instance(instance) => assert_eq!(instance.variant, Some((1, "Empty".into()))),
+			other => panic!("Expected instance item, instead got: {}", other),
+		}
+	}
+
+	#[test]
+	fn test_item_errors() {
+		let context = &context(SOURCE);
+		for string in &["crate::f([1], \"\", Shape::Empty, Point: x: 0, y: 0)",
+			"crate::f([1, 2], 'a', Shape::Empty, Point: x: 0, y: 0)",
+			"crate::f([1, 2], \"\", Shape::Empty, Point: x: 0, y: 256)",
+			"crate::f([1, 2], \"\", (Point: x: 0, y: 0), Shape::Empty)"] {
+			assert!(items(context, string).is_err(), "{}", string);
+		}
+		assert!(items(context, "crate::f([1, 2])").is_err());
+	}
+}
@@ -0,0 +1,161 @@
+use std::io::{self, Write};
+
+use crate::context::Context;
+use crate::span::Spanned;
+
+use super::Commands;
+
+/// Executes the commands specified by the arguments without the interface.
+/// Returns true if no diagnostics were emitted.
+pub fn batch(context: &Context, arguments: &[String]) -> bool {
+	let (output, errors) = (io::stdout(), io::stderr());
+	execute(context, arguments, &mut output.lock(), &mut errors.lock()).unwrap_or_else(|error| {
+		eprintln!("Output could not be written: {}", error);
+		false
+	})
+}
+
+fn execute(context: &Context, arguments: &[String], output: &mut dyn Write,
+           errors: &mut dyn Write) -> io::Result<bool> {
+	let commands = Commands::new();
+	let json = arguments.iter().any(|argument| argument == "--json");
+	let mut success = flush(context, json, errors)?;
+	let mut arguments = arguments.iter().peekable();
+	while let Some(argument) = arguments.next() {
+		let command = match argument.starts_with("--") {
+			true => &argument[2..],
+			false => {
+				writeln!(errors, "Expected command flag, instead got: {}", argument)?;
+				return Ok(false);
+			}
+		};
+
+		let mut string = String::new();
+		while let Some(argument) = arguments.peek() {
+			if argument.starts_with("--") { break; }
+			string.push_str(arguments.next().unwrap());
+			string.push(' ');
+		}
+
+		match command {
+			"json" => continue,
+			"check" => check(context),
+			"test" => {
+				let (report, passed) = super::test::test(context, string.trim());
+				writeln!(output, "{}", report)?;
+				success &= passed;
+			}
+			_ => match commands.command(command) {
+				None => {
+					writeln!(errors, "Invalid command: {}", command)?;
+					return Ok(false);
+				}
+				Some(command) => {
+					let result = command.execute(context, string.trim());
+					if let Some(string) = context.emit(result) {
+						writeln!(output, "{}", string)?;
+					}
+				}
+			}
+		}
+		success &= flush(context, json, errors)?;
+	}
+	Ok(success)
+}
+
+/// Lowers all declared functions and structures to emit their diagnostics.
+pub fn check(context: &Context) {
+	let mut structures: Vec<_> = context.declarations_structure.clone().into_iter()
+		.map(|(path, declaration)| Spanned::new(path, declaration.span())).collect();
+	structures.sort_by_key(|path| path.node.to_string());
+	for structure_path in &structures {
+		context.emit(crate::node::structure(context, structure_path));
+	}
+
+	let mut functions: Vec<_> = context.declarations_function.clone().into_iter()
+		.map(|(path, declaration)| Spanned::new(path, declaration.span())).collect();
+	functions.sort_by_key(|path| path.node.to_string());
+	for function_path in &functions {
+		context.emit(crate::evaluation::partial_function(context, function_path));
+	}
+}
+
+/// Writes and clears all emitted diagnostics. Returns true if none were present.
+/// Diagnostics are written as JSON objects on each line if `json` is true.
+fn flush(context: &Context, json: bool, errors: &mut dyn Write) -> io::Result<bool> {
+	let diagnostics = std::mem::replace(context.errors.write().as_mut(), Vec::new());
+	for diagnostic in &diagnostics {
+		match json {
+			false => write!(errors, "{}", crate::error::string(context, diagnostic))?,
+			true => write!(errors, "{}", crate::error::json(context, diagnostic))?,
+		}
+	}
+	Ok(diagnostics.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::source::context;
+
+	use super::*;
+
+	const SOURCE: &str = "fn one() -> u32:\n\t1\n\n\
+		fn add(x: u8) -> u8:\n\tx + 1\n\n\
+		#test\nfn passing() -> truth:\n\tone() == 1\n\n\
+		#test\nfn failing() -> truth:\n\tone() == 2\n";
+
+	/// Provides the success, output and error output of the arguments.
+	fn run(context: &Context, arguments: &[&str]) -> (bool, String, String) {
+		let arguments: Vec<_> = arguments.iter().map(ToString::to_string).collect();
+		let (mut output, mut errors) = (Vec::new(), Vec::new());
+		let success = execute(context, &arguments, &mut output, &mut errors).unwrap();
+		(success, String::from_utf8(output).unwrap(), String::from_utf8(errors).unwrap())
+	}
+
+	#[test]
+	fn test_check() {
+		assert_eq!(run(&context(SOURCE), &["--check"]), (true, String::new(), String::new()));
+
+		let invalid = &context("fn f() -> u32:\n\ttrue\n");
+		let (success, output, errors) = run(invalid, &["--check"]);
+		assert!(!success && output.is_empty());
+		assert!(errors.starts_with("[Error]"), "{}", errors);
+
+		let (success, _, errors) = run(invalid, &["--check", "--json"]);
+		assert!(!success);
+		assert!(errors.lines().all(|line| line.starts_with("{\"category\":\"Inference\"")), "{}", errors);
+	}
+
+	#[test]
+	fn test_test() {
+		let context = &context(SOURCE);
+		let (success, output, errors) = run(context, &["--test"]);
+		assert!(!success && errors.is_empty());
+		assert!(output.starts_with("test crate::failing ... failed\n"), "{}", output);
+		assert!(output.ends_with("test crate::passing ... ok\n1 passed, 1 failed\n"), "{}", output);
+
+		let (success, output, _) = run(context, &["--test", "crate::pass"]);
+		assert!(success);
+		assert_eq!(output, "test crate::passing ... ok\n1 passed, 0 failed\n");
+	}
+
+	#[test]
+	fn test_command() {
+		let context = &context(SOURCE);
+		let arguments = &["--evaluate", "crate::one()", "--evaluate", "crate::add(2)"];
+		assert_eq!(run(context, arguments), (true, "1\n3\n".to_owned(), String::new()));
+
+		let (success, output, errors) = run(context, &["--evaluate", "crate::add(255)"]);
+		assert!(!success && output.is_empty());
+		assert!(errors.contains("overflow"), "{}", errors);
+		assert!(run(context, &["--json", "--evaluate", "crate::add(255)"]).2.starts_with("{"));
+	}
+
+	#[test]
+	fn test_invalid() {
+		let context = &context(SOURCE);
+		assert_eq!(run(context, &["--unknown"]), (false, String::new(), "Invalid command: unknown\n".to_owned()));
+		assert_eq!(run(context, &["check"]), (false, String::new(),
+			"Expected command flag, instead got: check\n".to_owned()));
+	}
+}
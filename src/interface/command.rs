@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 
 use crate::context::Context;
+use crate::declaration::ModulePath;
 use crate::error::Diagnostic;
+use crate::evaluation::ArithmeticMode;
 use crate::extension::StringExtension;
 
 pub trait Command: std::fmt::Debug {
@@ -18,12 +20,21 @@ impl Commands {
 	pub fn new() -> Self {
 		let mut commands: HashMap<_, Box<dyn Command>> = HashMap::new();
 		commands.insert("context", Box::new(CommandContext));
+		commands.insert("reload", Box::new(CommandReload));
 		commands.insert("basic", Box::new(super::function::CommandBasic));
 		commands.insert("evaluate", Box::new(super::function::CommandEvaluate));
 		commands.insert("cycle", Box::new(super::function::CommandCycle));
+		commands.insert("debug", Box::new(super::function::CommandDebug));
+		commands.insert("verify", Box::new(super::verify::CommandVerify));
+		commands.insert("test", Box::new(super::test::CommandTest));
+		commands.insert("arithmetic", Box::new(CommandArithmetic));
 		Self { commands }
 	}
 
+	pub fn command(&self, command: &str) -> Option<&dyn Command> {
+		self.commands.get(command).map(|command| command.as_ref())
+	}
+
 	pub fn execute(&self, context: &Context, string: &str) -> String {
 		let split = string.find(char::is_whitespace).unwrap_or(string.len());
 		let (command, arguments) = string.split_at(split);
@@ -59,6 +70,59 @@ impl Command for CommandContext {
 	}
 }
 
+#[derive(Debug)]
+struct CommandReload;
+
+impl Command for CommandReload {
+	fn execute(&self, context: &Context, _: &str) -> Result<String, Diagnostic> {
+		let root_path = match context.module_pending.get(&ModulePath::root()) {
+			Some(pending) => pending.expected_path.as_ref().clone(),
+			None => return Ok("Root module has not been loaded".to_owned()),
+		};
+
+		context.clear();
+		crate::declaration::module_root(context, root_path.clone());
+		crate::node::load_definitions(context);
+		super::check(context);
+
+		let errors = std::mem::replace(context.errors.write().as_mut(), Vec::new());
+		Ok(match errors.is_empty() {
+			true => format!("Reloaded: {}", root_path.display()),
+			false => errors.iter().map(|diagnostic|
+				crate::error::string(context, diagnostic)).collect(),
+		})
+	}
+
+	fn symbols(&self, _: &Context, _: &str) -> Vec<String> {
+		Vec::new()
+	}
+}
+
+#[derive(Debug)]
+struct CommandArithmetic;
+
+impl Command for CommandArithmetic {
+	fn execute(&self, context: &Context, string: &str) -> Result<String, Diagnostic> {
+		let mode = match string {
+			"" => return Ok(format!("Arithmetic mode: {}", context.arithmetic.read())),
+			"checked" => ArithmeticMode::Checked,
+			"wrapping" => ArithmeticMode::Wrapping,
+			_ => return Ok("Expected argument of 'checked' or 'wrapping'".to_owned()),
+		};
+
+		// Compile time evaluations depend on the arithmetic mode.
+		*context.arithmetic.write() = mode;
+		context.partial_functions.clear();
+		context.basic_functions.clear();
+		Ok(format!("Arithmetic mode: {}", mode))
+	}
+
+	fn symbols(&self, _: &Context, string: &str) -> Vec<String> {
+		["checked", "wrapping"].iter().filter(|mode| mode.prefix_equal(string))
+			.map(ToString::to_string).collect()
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -70,4 +134,36 @@ mod tests {
 		assert_eq!(commands.symbols(context, "con"), &["context"]);
 		assert!(commands.symbols(context, "context").is_empty());
 	}
+
+	#[test]
+	fn test_arithmetic() {
+		let commands = Commands::new();
+		let context = &Context::default();
+		assert_eq!(commands.execute(context, "arithmetic"), "Arithmetic mode: checked");
+		assert_eq!(commands.execute(context, "arithmetic wrapping"), "Arithmetic mode: wrapping");
+		assert_eq!(*context.arithmetic.read(), ArithmeticMode::Wrapping);
+		assert_eq!(commands.execute(context, "arithmetic saturating"),
+			"Expected argument of 'checked' or 'wrapping'");
+		assert_eq!(commands.symbols(context, "arithmetic w"), &["wrapping"]);
+	}
+
+	#[test]
+	fn test_reload() {
+		let commands = Commands::new();
+		let context = &Context::default();
+		assert_eq!(commands.execute(context, "reload"), "Root module has not been loaded");
+
+		let source = crate::source::TemporarySource::new("fn f() -> u32:\n\t1\n");
+		let context = &source.context();
+		assert_eq!(commands.execute(context, "evaluate crate::f()"), "1");
+		source.write("fn f() -> u32:\n\tg()\n\nfn g() -> u32:\n\t2\n");
+		assert_eq!(commands.execute(context, "evaluate crate::f()"), "1");
+		assert_eq!(commands.execute(context, "reload"), format!("Reloaded: {}", source.0.display()));
+		assert_eq!(commands.execute(context, "evaluate crate::f()"), "2");
+
+		source.write("fn f() -> u32:\n\th()\n");
+		let errors = commands.execute(context, "reload");
+		assert!(errors.contains("has no matching resolutions"), "{}", errors);
+		assert!(context.errors.read().is_empty());
+	}
 }
@@ -0,0 +1,375 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use tui::backend::Backend;
+use tui::layout::{Constraint, Layout, Rect};
+use tui::style::{Color, Style};
+use tui::widgets::{Block, Borders, Paragraph, Text, Widget};
+use tui::Frame;
+
+use crate::basic::{BasicFunction, Direction, Reversibility};
+use crate::context::Context;
+use crate::error::Diagnostic;
+use crate::evaluation::{EvaluationContext, EvaluationItem, FunctionFrame, ValueFrame};
+use crate::node::Variable;
+use crate::source::SourceKey;
+use crate::span::Span;
+
+/// Maximum number of steps evaluated when resuming to a breakpoint.
+const STEP_LIMIT: usize = 1 << 20;
+
+type Line = (SourceKey, usize);
+
+#[derive(Debug)]
+pub struct Debugger<'a> {
+	context: &'a Context,
+	function: Arc<BasicFunction>,
+	evaluation: EvaluationContext<'a>,
+	breakpoints: HashSet<Line>,
+	complete: Option<(Direction, EvaluationItem)>,
+	direction: Direction,
+	message: Option<String>,
+}
+
+impl<'a> Debugger<'a> {
+	pub fn new(context: &'a Context, string: &str) -> Result<Self, Diagnostic> {
+		let call = super::argument::function_call(string)?;
+		let function = crate::basic::function(context, &call.path, Reversibility::Reversible)?;
+		let items = call.items(context, string, &function.parameters)?;
+		let frame = ValueFrame::advance(&function, items.iter().map(EvaluationItem::item));
+		let evaluation = EvaluationContext::new(context, Reversibility::Reversible,
+			FunctionFrame::new(function.clone(), Direction::Advance), frame)?;
+		Ok(Debugger {
+			context,
+			function,
+			evaluation,
+			breakpoints: HashSet::new(),
+			complete: None,
+			direction: Direction::Advance,
+			message: None,
+		})
+	}
+
+	/// Evaluates a single statement or branch.
+	pub fn step(&mut self, direction: Direction) {
+		self.message = None;
+		self.direction = direction;
+		if let Err(diagnostic) = self.transition(direction) {
+			self.message = Some(crate::error::string(self.context, &diagnostic));
+		}
+	}
+
+	/// Evaluates until a breakpoint is reached or the function returns.
+	pub fn resume(&mut self, direction: Direction) {
+		self.message = None;
+		self.direction = direction;
+		for _ in 0..STEP_LIMIT {
+			let line = self.line();
+			match self.transition(direction) {
+				Ok(false) => return,
+				Ok(true) if self.complete.is_some() => return,
+				Ok(true) => match self.line() {
+					Some(current) if Some(current) != line &&
+						self.breakpoints.contains(&current) => return,
+					_ => (),
+				},
+				Err(diagnostic) => {
+					let message = crate::error::string(self.context, &diagnostic);
+					return self.message = Some(message);
+				}
+			}
+		}
+		self.message = Some(format!("Evaluation step limit: {}, reached", STEP_LIMIT));
+	}
+
+	/// Toggles a breakpoint on the source line of the current statement.
+	pub fn breakpoint(&mut self) {
+		if let Some(line) = self.line() {
+			if !self.breakpoints.remove(&line) {
+				self.breakpoints.insert(line);
+			}
+		}
+	}
+
+	pub fn summary(&self) -> String {
+		match &self.complete {
+			Some((Direction::Advance, item)) => format!("Returned: {:?}", item),
+			Some((Direction::Reverse, item)) => format!("Reversed parameters: {:?}", item),
+			None => format!("Suspended at: {}", self.span()
+				.map(|span| span.location(self.context))
+				.unwrap_or_else(|| "<Unknown location>".to_owned())),
+		}
+	}
+
+	/// Advances the evaluation by one step. Returns false if the function
+	/// has already returned in the given direction.
+	fn transition(&mut self, direction: Direction) -> Result<bool, Diagnostic> {
+		if let Some((complete, item)) = self.complete.take() {
+			if complete == direction {
+				self.complete = Some((complete, item));
+				return Ok(false);
+			}
+
+			let frame = match complete {
+				Direction::Advance => ValueFrame::reverse(&self.function, item),
+				Direction::Reverse => {
+					let mut frame = ValueFrame::default();
+					frame.items.insert(Variable::new_temporary(0), item);
+					frame
+				}
+			};
+
+			self.evaluation.values.frames.push(frame);
+			self.evaluation.functions.push(FunctionFrame::new(self.function.clone(), direction));
+			return Ok(true);
+		}
+
+		if let Some(item) = self.evaluation.step(direction)? {
+			self.complete = Some((direction, item));
+		}
+		Ok(true)
+	}
+
+	fn span(&self) -> Option<Span> {
+		self.evaluation.functions.last()
+			.map(|frame| frame.span(self.direction))
+	}
+
+	fn line(&self) -> Option<Line> {
+		let span = self.span()?;
+		if span.source == SourceKey::INTERNAL {
+			return None;
+		}
+
+		let source = span.source.get(self.context);
+		let string = source.read_string().ok()?;
+		Some((span.source, string[..span.byte_start].matches('\n').count()))
+	}
+
+	pub fn render<B: Backend>(&self, frame: &mut Frame<B>, area: Rect) {
+		let chunks = Layout::default()
+			.direction(tui::layout::Direction::Horizontal)
+			.constraints([
+				Constraint::Ratio(2, 3),
+				Constraint::Ratio(1, 3)
+			].as_ref())
+			.split(area);
+		let (left, right) = (chunks[0], chunks[1]);
+
+		let chunks = Layout::default()
+			.direction(tui::layout::Direction::Vertical)
+			.constraints([
+				Constraint::Min(0),
+				Constraint::Ratio(1, 3),
+				Constraint::Length(3)
+			].as_ref())
+			.split(left);
+		let scroll = self.line().map(|(_, line)| line as u16)
+			.unwrap_or(0).saturating_sub(chunks[0].height / 2);
+		let source = self.source();
+		Paragraph::new(source.iter())
+			.block(Block::default()
+				.title("Source")
+				.borders(Borders::ALL))
+			.scroll(scroll)
+			.render(frame, chunks[0]);
+		let node = self.node();
+		Paragraph::new(node.iter())
+			.block(Block::default()
+				.title("Node")
+				.borders(Borders::ALL))
+			.render(frame, chunks[1]);
+		let status = Text::raw(self.message.clone().unwrap_or_else(|| match self.complete {
+			Some(_) => self.summary(),
+			None => "[n] Step  [p] Reverse  [c] Continue  [r] Reverse continue  \
+				[b] Breakpoint  [q] Exit".to_owned(),
+		}));
+		Paragraph::new(std::iter::once(&status))
+			.block(Block::default()
+				.title("Status")
+				.borders(Borders::ALL))
+			.render(frame, chunks[2]);
+
+		let chunks = Layout::default()
+			.direction(tui::layout::Direction::Vertical)
+			.constraints([
+				Constraint::Ratio(2, 3),
+				Constraint::Ratio(1, 3)
+			].as_ref())
+			.split(right);
+		let values = Text::raw(self.values());
+		Paragraph::new(std::iter::once(&values))
+			.block(Block::default()
+				.title("Values")
+				.borders(Borders::ALL))
+			.render(frame, chunks[0]);
+		let stack = Text::raw(self.evaluation.values.stack.items().iter().rev()
+			.map(|item| format!("{:?}\n", item)).collect::<String>());
+		Paragraph::new(std::iter::once(&stack))
+			.block(Block::default()
+				.title("Drop stack")
+				.borders(Borders::ALL))
+			.render(frame, chunks[1]);
+	}
+
+	/// Renders the source lines with the current statement highlighted.
+	fn source(&self) -> Vec<Text<'static>> {
+		let span = match self.span() {
+			Some(span) if span.source != SourceKey::INTERNAL => span,
+			_ => return vec![Text::raw("<Internal compiler source>")],
+		};
+
+		let source = span.source.get(self.context);
+		let string = match source.read_string() {
+			Ok(string) => string,
+			Err(_) => return vec![Text::raw("<Failure to read source>")],
+		};
+
+		let highlight = Style::default().bg(Color::White).fg(Color::Black);
+		let current = string[..span.byte_start].matches('\n').count();
+		let mut offset = 0;
+		let mut text = Vec::new();
+		for (index, line) in string.split('\n').enumerate() {
+			let breakpoint = match self.breakpoints.contains(&(span.source, index)) {
+				true => '*',
+				false => ' ',
+			};
+
+			let marker = if index == current { '>' } else { ' ' };
+			text.push(Text::raw(format!("{}{}{:>4} | ", breakpoint, marker, index + 1)));
+			let (start, end) = (offset, offset + line.len());
+			match span.byte_start < end && span.byte_end > start {
+				false => text.push(Text::raw(format!("{}\n", line))),
+				true => {
+					let highlight_start = span.byte_start.max(start) - start;
+					let highlight_end = span.byte_end.min(end) - start;
+					text.push(Text::raw(line[..highlight_start].to_owned()));
+					text.push(Text::styled(line[highlight_start..highlight_end].to_owned(), highlight));
+					text.push(Text::raw(format!("{}\n", &line[highlight_end..])));
+				}
+			}
+			offset = end + 1;
+		}
+		text
+	}
+
+	/// Renders the current node with the next statement marked.
+	fn node(&self) -> Vec<Text<'static>> {
+		let frame = match self.evaluation.functions.last() {
+			Some(frame) => frame,
+			None => return vec![Text::raw(self.summary())],
+		};
+
+		let (target, index) = frame.position();
+		let node = &frame.function()[&target];
+		let current = match self.direction {
+			Direction::Advance => index + 1,
+			Direction::Reverse => index,
+		};
+
+		let highlight = Style::default().bg(Color::White).fg(Color::Black);
+		let mut text = vec![Text::raw(format!("Frame: {}, node: {}\n",
+			self.evaluation.functions.len() - 1, target))];
+		let lines = std::iter::once(format!("-{}", node.reverse))
+			.chain(node.statements.iter().map(|statement| format!(" {}", statement)))
+			.chain(std::iter::once(format!("+{}", node.advance)));
+		for (line, string) in lines.enumerate() {
+			text.push(match line == current {
+				true => Text::styled(format!("{}\n", string), highlight),
+				false => Text::raw(format!("{}\n", string)),
+			});
+		}
+		text
+	}
+
+	fn values(&self) -> String {
+		let mut string = String::new();
+		for (index, frame) in self.evaluation.values.frames.iter().enumerate().rev() {
+			string += &format!("Frame {}\n", index);
+			let mut items: Vec<_> = frame.items.iter().map(|(variable, item)|
+				format!("  {}: {:?}\n", variable, item)).collect();
+			items.sort();
+			items.into_iter().for_each(|item| string += &item);
+		}
+		string
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::source::context;
+
+	use super::*;
+
+	const SOURCE: &str = "fn f(~x: u32) -> u32:\n\tx += 1\n\tx += 2\n\tx\n";
+
+	/// Steps until the function returns in the direction. Provides the number of steps.
+	fn complete(debugger: &mut Debugger, direction: Direction) -> usize {
+		let mut steps = 0;
+		while debugger.complete.as_ref().map(|(complete, _)| complete) != Some(&direction) {
+			debugger.step(direction);
+			assert!(debugger.message.is_none(), "{:?}", debugger.message);
+			steps += 1;
+		}
+		steps
+	}
+
+	#[test]
+	fn test_step() {
+		let context = &context(SOURCE);
+		let mut debugger = Debugger::new(context, "crate::f(1)").unwrap();
+		assert!(debugger.summary().starts_with("Suspended at:"), "{}", debugger.summary());
+		assert!(complete(&mut debugger, Direction::Advance) > 1);
+		assert!(debugger.summary().starts_with("Returned:") && debugger.summary().contains('4'));
+
+		// Stepping after returning does not evaluate further.
+		let summary = debugger.summary();
+		debugger.step(Direction::Advance);
+		assert_eq!(debugger.summary(), summary);
+
+		complete(&mut debugger, Direction::Reverse);
+		let summary = debugger.summary();
+		assert!(summary.starts_with("Reversed parameters:") && summary.contains('1'), "{}", summary);
+	}
+
+	#[test]
+	fn test_resume() {
+		let context = &context(SOURCE);
+		let mut debugger = Debugger::new(context, "crate::f(1)").unwrap();
+		debugger.resume(Direction::Advance);
+		assert!(debugger.message.is_none());
+		assert!(debugger.summary().starts_with("Returned:"), "{}", debugger.summary());
+		debugger.resume(Direction::Reverse);
+		assert!(debugger.summary().starts_with("Reversed parameters:"), "{}", debugger.summary());
+	}
+
+	#[test]
+	fn test_breakpoint() {
+		let context = &context(SOURCE);
+		let mut debugger = Debugger::new(context, "crate::f(1)").unwrap();
+		while debugger.line().map(|(_, line)| line) != Some(2) {
+			debugger.step(Direction::Advance);
+		}
+
+		debugger.breakpoint();
+		debugger.resume(Direction::Advance);
+		assert!(debugger.summary().starts_with("Returned:"), "{}", debugger.summary());
+		debugger.resume(Direction::Reverse);
+		assert_eq!(debugger.line().map(|(_, line)| line), Some(2));
+		assert!(debugger.complete.is_none());
+
+		debugger.breakpoint();
+		debugger.resume(Direction::Reverse);
+		assert!(debugger.summary().starts_with("Reversed parameters:"), "{}", debugger.summary());
+	}
+
+	#[test]
+	fn test_step_limit() {
+		let context = &context("fn spin(~x: truth, ~y: truth) -> truth:\n\t\
+			loop x == x => x != x:\n\t\tx <=> y\n\tx\n");
+		let mut debugger = Debugger::new(context, "crate::spin(true, false)").unwrap();
+		debugger.resume(Direction::Advance);
+		assert_eq!(debugger.message, Some(format!("Evaluation step limit: {}, reached", STEP_LIMIT)));
+		assert!(debugger.complete.is_none());
+	}
+}
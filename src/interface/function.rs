@@ -1,14 +1,10 @@
-use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::basic::{Direction, Item, Reversibility};
+use crate::basic::Reversibility;
 use crate::context::Context;
 use crate::declaration::FunctionPath;
 use crate::error::Diagnostic;
-use crate::evaluation::{EvaluationContext, EvaluationInstance,
-	EvaluationItem, FunctionFrame, ValueFrame};
 use crate::extension::StringExtension;
-use crate::node::Variable;
 use crate::span::{Span, Spanned};
 
 use super::Command;
@@ -47,14 +43,12 @@ pub struct CommandEvaluate;
 
 impl Command for CommandEvaluate {
 	fn execute(&self, context: &Context, string: &str) -> Result<String, Diagnostic> {
-		let path = function_path(string)?;
-		let function_type = crate::node::function_type(context, &path)?;
-		match function_type.parameters.is_empty() {
-			false => Ok("Evaluated functions must have zero arity".to_owned()),
-			true => crate::evaluation::function(context, &path, Vec::new())
-				.and_then(|item| Ok(item.collapse().map_err(|error|
-					Diagnostic::new(Spanned::new(error, Span::INTERNAL)))?.to_string()))
-		}
+		let call = super::argument::function_call(string)?;
+		let function = crate::basic::function(context, &call.path, Reversibility::Entropic)?;
+		let arguments = call.items(context, string, &function.parameters)?;
+		crate::evaluation::function(context, &call.path, arguments)
+			.and_then(|item| Ok(item.collapse().map_err(|error|
+				Diagnostic::new(Spanned::new(error, Span::INTERNAL)))?.to_string()))
 	}
 
 	fn symbols(&self, context: &Context, string: &str) -> Vec<String> {
@@ -67,27 +61,25 @@ pub struct CommandCycle;
 
 impl Command for CommandCycle {
 	fn execute(&self, context: &Context, string: &str) -> Result<String, Diagnostic> {
-		let path = function_path(string)?;
-		let function_type = crate::node::function_type(context, &path)?;
-		match function_type.parameters.is_empty() {
-			false => Ok("Evaluated functions must have zero arity".to_owned()),
-			true => {
-				let function = crate::basic::function(context, &path, Reversibility::Reversible)?;
-				let (type_resolution, fields) = (function.parameter_type(), HashMap::new());
-				let item = Item::Instance(EvaluationInstance { type_resolution, fields });
-
-				let mut frame = ValueFrame::default();
-				frame.items.insert(Variable::new_temporary(0), EvaluationItem::Item(item));
-				let mut context = EvaluationContext::new(context, Reversibility::Reversible,
-					FunctionFrame::new(function.clone(), Direction::Advance), frame)?;
-				let item = context.resume(Direction::Advance)?;
-
-				context.values.frames.push(ValueFrame::reverse(&function, item));
-				context.functions.push(FunctionFrame::new(function, Direction::Reverse));
-				context.resume(Direction::Reverse).and_then(|item| Ok(item.collapse().map_err(|error|
-					Diagnostic::new(Spanned::new(error, Span::INTERNAL)))?.to_string()))
-			}
-		}
+		let call = super::argument::function_call(string)?;
+		let function = crate::basic::function(context, &call.path, Reversibility::Reversible)?;
+		let arguments = call.items(context, string, &function.parameters)?;
+		crate::evaluation::cycle(context, &call.path, arguments)
+			.and_then(|item| Ok(item.collapse().map_err(|error|
+				Diagnostic::new(Spanned::new(error, Span::INTERNAL)))?.to_string()))
+	}
+
+	fn symbols(&self, context: &Context, string: &str) -> Vec<String> {
+		function_candidates(context, string)
+	}
+}
+
+#[derive(Debug)]
+pub struct CommandDebug;
+
+impl Command for CommandDebug {
+	fn execute(&self, _: &Context, _: &str) -> Result<String, Diagnostic> {
+		Ok("Debugging is only available in the interactive interface".to_owned())
 	}
 
 	fn symbols(&self, context: &Context, string: &str) -> Vec<String> {
@@ -95,7 +87,7 @@ impl Command for CommandCycle {
 	}
 }
 
-fn function_path(string: &str) -> Result<Spanned<Arc<FunctionPath>>, Diagnostic> {
+pub fn function_path(string: &str) -> Result<Spanned<Arc<FunctionPath>>, Diagnostic> {
 	let lexer = &mut crate::lexer::Lexer::new(string,
 		0, crate::source::SourceKey::INTERNAL);
 	Ok(crate::parser::path(lexer)?.map(|mut path| {
@@ -104,7 +96,7 @@ fn function_path(string: &str) -> Result<Spanned<Arc<FunctionPath>>, Diagnostic>
 	}))
 }
 
-fn function_candidates(context: &Context, path: &str) -> Vec<String> {
+pub fn function_candidates(context: &Context, path: &str) -> Vec<String> {
 	context.declarations_function.clone().into_iter()
 		.map(|(function_path, _)| function_path.to_string())
 		.filter(|candidate| candidate.as_str().prefix_equal(path))
@@ -1,3 +1,5 @@
+use std::time::{Duration, Instant};
+
 use termion::event::Key;
 use termion::input::TermRead;
 use termion::raw::{IntoRawMode, RawTerminal};
@@ -7,12 +9,20 @@ use tui::layout::{Constraint, Direction, Layout};
 use tui::style::{Color, Style};
 use tui::widgets::{Block, Borders, List, Paragraph, SelectableList, Text, Widget};
 
+use crate::basic::Direction as Evaluation;
 use crate::context::Context;
 
 use super::Commands;
+use super::debug::Debugger;
 
 type Terminal = tui::Terminal<TermionBackend<AlternateScreen<RawTerminal<std::io::Stdout>>>>;
 type InterfaceResult = std::result::Result<(), Box<dyn std::error::Error>>;
+type Keys = Box<dyn Iterator<Item=std::io::Result<Key>>>;
+
+/// Interval between checks for modified sources.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Interval between reads of pending input.
+const INPUT_INTERVAL: Duration = Duration::from_millis(20);
 
 #[derive(Debug, Default)]
 struct Interface {
@@ -23,7 +33,9 @@ struct Interface {
 	scroll: u16,
 }
 
-pub fn interface(context: &Context) -> InterfaceResult {
+/// Runs the interactive interface. Modified sources are
+/// reloaded while the interface is open if `is_watch` is true.
+pub fn interface(context: &Context, is_watch: bool) -> InterfaceResult {
 	let commands = Commands::new();
 	let mut interface = Interface::default();
 	interface.history.push("<initialise>".to_owned());
@@ -38,20 +50,64 @@ pub fn interface(context: &Context) -> InterfaceResult {
 	let output = std::io::stdout().into_raw_mode()?;
 	let backend = TermionBackend::new(AlternateScreen::from(output));
 	let mut terminal = Terminal::new(backend)?;
-	let mut input = std::io::stdin().keys();
+	let mut input: Keys = match is_watch {
+		false => Box::new(std::io::stdin().keys()),
+		true => Box::new(termion::async_stdin().keys()),
+	};
+
+	let mut debugger: Option<Debugger> = None;
+	let mut poll = Instant::now();
 	loop {
+		if let Some(session) = debugger.as_mut() {
+			terminal.draw(|mut frame| {
+				let area = frame.size();
+				session.render(&mut frame, area);
+			})?;
+
+			match input.next().transpose()? {
+				None => std::thread::sleep(INPUT_INTERVAL),
+				Some(key) => match key {
+					Key::Ctrl('c') => return Ok(()),
+					Key::Esc | Key::Char('q') => {
+						*interface.captures.last_mut().unwrap() = session.summary();
+						debugger = None;
+					}
+					Key::Char('n') | Key::Right => session.step(Evaluation::Advance),
+					Key::Char('p') | Key::Left => session.step(Evaluation::Reverse),
+					Key::Char('c') => session.resume(Evaluation::Advance),
+					Key::Char('r') => session.resume(Evaluation::Reverse),
+					Key::Char('b') => session.breakpoint(),
+					_ => (),
+				}
+			}
+			continue;
+		}
+
+		if is_watch && poll.elapsed() >= POLL_INTERVAL {
+			poll = Instant::now();
+			reload(context, &mut interface);
+		}
+
 		render(&mut terminal, context, &interface, &commands)?;
 		terminal.set_cursor(1 + interface.command.len() as u16, 1)?;
 
-		let key = input.next();
-		if let Some(key) = key.transpose()? {
-			match key {
+		match input.next().transpose()? {
+			None => std::thread::sleep(INPUT_INTERVAL),
+			Some(key) => match key {
 				Key::Ctrl('c') => return Ok(()),
 				Key::Ctrl('u') => interface.command.clear(),
 				Key::Char('\n') => {
 					let command = std::mem::replace(&mut interface.command, String::new());
-					let capture = commands.execute(context, &command)
-						.replace('\t', &" ".repeat(4));
+					let capture = match command.starts_with("debug ") {
+						false => commands.execute(context, &command),
+						true => match Debugger::new(context, command["debug".len()..].trim()) {
+							Err(diagnostic) => crate::error::string(context, &diagnostic),
+							Ok(session) => {
+								debugger = Some(session);
+								"<Debug session>".to_owned()
+							}
+						},
+					}.replace('\t', &" ".repeat(4));
 
 					interface.history.push(command);
 					interface.captures.push(capture);
@@ -76,6 +132,27 @@ pub fn interface(context: &Context) -> InterfaceResult {
 	}
 }
 
+/// Reloads the modules with modified sources and captures the emitted diagnostics.
+fn reload(context: &Context, interface: &mut Interface) {
+	let modules = crate::reload::modified(context);
+	if modules.is_empty() {
+		return;
+	}
+
+	let invalidation = crate::reload::reload(context, &modules);
+	super::check(context);
+	let modules: Vec<_> = modules.iter().map(ToString::to_string).collect();
+	let mut capture = format!("Reloaded modules: {}\nInvalidated functions: {}, structures: {}\n",
+		modules.join(", "), invalidation.functions.len(), invalidation.structures.len());
+	let errors = std::mem::replace(context.errors.write().as_mut(), Vec::new());
+	errors.iter().for_each(|diagnostic| capture += &crate::error::string(context, diagnostic));
+
+	interface.history.push("<reload>".to_owned());
+	interface.captures.push(capture.replace('\t', &" ".repeat(4)));
+	interface.selected = interface.history.len() - 1;
+	interface.scroll = 0;
+}
+
 fn render(terminal: &mut Terminal, context: &Context,
           interface: &Interface, commands: &Commands) -> InterfaceResult {
 	Ok(terminal.draw(|mut frame| {
@@ -1,6 +1,13 @@
 use command::{Commands, Command};
+pub use batch::{batch, check};
+pub use function::function_candidates;
 pub use interface::interface;
 
 mod interface;
+mod batch;
+mod debug;
+mod verify;
+mod test;
 mod function;
-mod command;
\ No newline at end of file
+mod argument;
+mod command;
@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use crate::basic::Item;
+use crate::context::Context;
+use crate::declaration::FunctionPath;
+use crate::error::Diagnostic;
+use crate::evaluation::{EvaluationError, EvaluationItem};
+use crate::extension::StringExtension;
+use crate::intrinsic::Intrinsic;
+use crate::span::Spanned;
+
+use super::Command;
+
+#[derive(Debug)]
+pub struct CommandTest;
+
+impl Command for CommandTest {
+	fn execute(&self, context: &Context, string: &str) -> Result<String, Diagnostic> {
+		let (report, _) = test(context, string);
+		Ok(report)
+	}
+
+	fn symbols(&self, context: &Context, string: &str) -> Vec<String> {
+		context.declarations_test.read().iter().map(ToString::to_string)
+			.filter(|candidate| candidate.as_str().prefix_equal(string)).collect()
+	}
+}
+
+/// Evaluates every test with a path prefixed by the filter.
+/// Returns the report and true if all tests passed.
+pub fn test(context: &Context, filter: &str) -> (String, bool) {
+	let mut tests: Vec<_> = context.declarations_test.read().iter()
+		.filter(|path| path.to_string().as_str().prefix_equal(filter))
+		.filter_map(|path| context.declarations_function.get(path)
+			.map(|declaration| Spanned::new(path.clone(), declaration.span())))
+		.collect();
+	tests.sort_by_key(|path| path.node.to_string());
+
+	let mut report = String::new();
+	let (mut passed, mut failed) = (0, 0);
+	for path in &tests {
+		match run(context, path) {
+			Ok(()) => {
+				report += &format!("test {} ... ok\n", path.node);
+				passed += 1;
+			}
+			Err(diagnostic) => {
+				report += &format!("test {} ... failed\n", path.node);
+				report += &crate::error::string(context, &diagnostic);
+				failed += 1;
+			}
+		}
+	}
+
+	report += &format!("{} passed, {} failed", passed, failed);
+	(report, failed == 0)
+}
+
+fn run(context: &Context, path: &Spanned<Arc<FunctionPath>>) -> Result<(), Diagnostic> {
+	let function = crate::node::function(context, path)?;
+	if let Some(parameter) = function.function_type.parameters.first() {
+		let error = EvaluationError::TestParameters;
+		return Err(Diagnostic::new(Spanned::new(error, parameter.span)));
+	}
+
+	let type_context = crate::inference::function(context, path)?;
+	let resolution = &type_context[&function.expression];
+	if resolution.intrinsic() != Some(Intrinsic::Truth) {
+		let error = EvaluationError::TestReturn(resolution.clone());
+		return Err(Diagnostic::new(Spanned::new(error, function.function_type.return_type.span)));
+	}
+
+	match crate::evaluation::function(context, path, Vec::new())? {
+		EvaluationItem::Item(Item::Truth(true)) => Ok(()),
+		EvaluationItem::Item(Item::Truth(false)) => {
+			let error = EvaluationError::TestFailure;
+			let span = function.context[&function.expression].span;
+			Err(Diagnostic::new(Spanned::new(error, span)))
+		}
+		item => panic!("Test evaluated to non-truth item: {:?}", item),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::error::CompileError;
+	use crate::source::{context, function_path};
+
+	use super::*;
+
+	fn error(context: &Context, identifier: &str) -> EvaluationError {
+		let path = function_path(identifier);
+		let declaration = context.declarations_function.get(&path.node).unwrap().span();
+		match run(context, &Spanned::new(path.node, declaration)).unwrap_err().error.node {
+			CompileError::Evaluation(error) => error,
+			other => panic!("Expected evaluation error, instead got: {:?}", other),
+		}
+	}
+
+	#[test]
+	fn test_discovery() {
+		let context = &context("#test\nfn first() -> truth:\n\ttrue\n\n\
+			fn helper() -> truth:\n\ttrue\n\n#test\nfn second() -> truth:\n\thelper()\n\n\
+			module nested:\n\t#test\n\tfn third() -> truth:\n\t\ttrue\n");
+		let mut tests: Vec<_> = context.declarations_test.read().iter().map(ToString::to_string).collect();
+		tests.sort();
+		assert_eq!(tests, &["crate::first", "crate::nested::third", "crate::second"]);
+
+		let (report, passed) = test(context, "crate::nested");
+		assert_eq!(report, "test crate::nested::third ... ok\n1 passed, 0 failed");
+		assert!(passed);
+		assert_eq!(test(context, "crate::missing"), ("0 passed, 0 failed".to_owned(), true));
+	}
+
+	#[test]
+	fn test_report() {
+		let context = &context("fn two() -> u32:\n\t1 + 1\n\n\
+			#test\nfn passing() -> truth:\n\ttwo() == 2\n\n\
+			#test\nfn failing() -> truth:\n\ttwo() == 3\n");
+		let (report, passed) = test(context, "");
+		let lines: Vec<_> = report.lines().collect();
+		assert!(!passed);
+		assert_eq!(lines[0], "test crate::failing ... failed");
+		assert!(lines[1].contains(&EvaluationError::TestFailure.to_string()), "{}", report);
+		assert_eq!(&lines[lines.len() - 2..], &["test crate::passing ... ok", "1 passed, 1 failed"]);
+	}
+
+	#[test]
+	fn test_signature() {
+		let context = &context("#test\nfn integer() -> u32:\n\t1\n\n\
+			#test\nfn parameter(x: u32) -> truth:\n\tx == 1\n");
+		match error(context, "integer") {
+			EvaluationError::TestReturn(resolution) => assert_eq!(resolution.to_string(), "intrinsic::u32"),
+			other => panic!("Expected test return error, instead got: {:?}", other),
+		}
+
+		match error(context, "parameter") {
+			EvaluationError::TestParameters => (),
+			other => panic!("Expected test parameters error, instead got: {:?}", other),
+		}
+		assert_eq!(test(context, "").0.matches("... failed").count(), 2);
+	}
+}
@@ -0,0 +1,400 @@
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+
+use crate::basic::{BasicFunction, Compound, Direction, Instance, Item, Location,
+	NodeTarget, Reversibility, Statement, Value};
+use crate::context::Context;
+use crate::declaration::FunctionPath;
+use crate::error::{CompileError, Diagnostic};
+use crate::evaluation::{EvaluationContext, EvaluationError, EvaluationItem,
+	FunctionFrame, ValueFrame, ValueStack};
+use crate::inference::{InferenceError, TypeResolution};
+use crate::intrinsic::Intrinsic;
+use crate::node::Variable;
+use crate::span::{Span, Spanned};
+
+use super::Command;
+
+/// Maximum number of argument combinations that are evaluated exhaustively.
+const EXHAUSTIVE_LIMIT: usize = 1 << 12;
+/// Number of argument combinations generated for large parameter types.
+const SAMPLES: usize = 64;
+/// Maximum number of steps in each direction before a case is skipped or fails.
+const STEP_LIMIT: usize = 1 << 16;
+
+#[derive(Debug)]
+pub struct CommandVerify;
+
+impl Command for CommandVerify {
+	fn execute(&self, context: &Context, string: &str) -> Result<String, Diagnostic> {
+		let mut functions: Vec<_> = match string.is_empty() {
+			false => vec![super::function::function_path(string)?],
+			true => context.declarations_function.clone().into_iter()
+				.map(|(path, declaration)| Spanned::new(path, declaration.span())).collect(),
+		};
+
+		functions.sort_by_key(|path| path.node.to_string());
+		let hook = std::panic::take_hook();
+		std::panic::set_hook(Box::new(|_| ()));
+		let string = functions.iter().map(|path| match verify(context, path) {
+			Ok(string) => format!("{}: {}\n", path.node, string),
+			Err(diagnostic) => format!("{}: Failed\n{}", path.node,
+				crate::error::string(context, &diagnostic)),
+		}).collect();
+		std::panic::set_hook(hook);
+		Ok(string)
+	}
+
+	fn symbols(&self, context: &Context, string: &str) -> Vec<String> {
+		super::function::function_candidates(context, string)
+	}
+}
+
+#[derive(Debug)]
+enum Case {
+	Pass,
+	Skip,
+	Fail(Diagnostic),
+}
+
+/// Evaluates the reversible function forwards then backwards on generated
+/// arguments and checks that the original arguments are recovered.
+/// Arguments outside the domain of the function are skipped.
+fn verify(context: &Context, path: &Spanned<Arc<FunctionPath>>) -> Result<String, Diagnostic> {
+	let function = match crate::basic::function(context, path, Reversibility::Reversible) {
+		Err(diagnostic) => match diagnostic.error.node {
+			CompileError::Inference(InferenceError::Uninstantiated(_)) =>
+				return Ok("Skipped with uninstantiated templates".to_owned()),
+			_ => return Err(diagnostic),
+		},
+		Ok(function) => function,
+	};
+
+	let arguments = match arguments(context, &function.parameters)? {
+		Some(arguments) => arguments,
+		None => return Ok("Skipped with unsupported parameter types".to_owned()),
+	};
+
+	let (mut passed, mut skipped) = (0, 0);
+	for arguments in arguments {
+		match isolate(|| case(context, &function, &arguments))? {
+			Case::Pass => passed += 1,
+			Case::Skip => skipped += 1,
+			Case::Fail(diagnostic) => {
+				let arguments: Vec<_> = arguments.iter().map(ToString::to_string).collect();
+				return Err(diagnostic.note(format!("In function: {}", path.node))
+					.note(format!("With arguments: ({})", arguments.join(", "))));
+			}
+		}
+	}
+	Ok(format!("{} passed, {} skipped", passed, skipped))
+}
+
+/// Fails the case if its evaluation panics instead of aborting the verification.
+fn isolate<F>(case: F) -> Result<Case, Diagnostic> where F: FnOnce() -> Result<Case, Diagnostic> {
+	std::panic::catch_unwind(AssertUnwindSafe(case)).unwrap_or_else(|panic| {
+		let message = panic.downcast_ref::<String>().cloned()
+			.or_else(|| panic.downcast_ref::<&str>().map(ToString::to_string))
+			.unwrap_or_default();
+		let error = EvaluationError::InternalPanic(message);
+		Ok(Case::Fail(Diagnostic::new(Spanned::new(error, Span::INTERNAL))))
+	})
+}
+
+/// Evaluates a single argument combination. Arguments for which forward
+/// evaluation fails or does not complete are skipped.
+fn case(context: &Context, function: &Arc<BasicFunction>,
+        arguments: &[Item]) -> Result<Case, Diagnostic> {
+	let frame = ValueFrame::advance(function, arguments.iter().map(EvaluationItem::item));
+	let parameters = frame.items[&Variable::new_temporary(0)].clone();
+	let mut evaluation = EvaluationContext::new(context, Reversibility::Reversible,
+		FunctionFrame::new(function.clone(), Direction::Advance), frame)?;
+	let item = match resume(&mut evaluation, Direction::Advance) {
+		Ok(item) => item,
+		Err(_) => return Ok(Case::Skip),
+	};
+
+	evaluation.values.frames.push(ValueFrame::reverse(function, item));
+	evaluation.functions.push(FunctionFrame::new(function.clone(), Direction::Reverse));
+	let reversed = match resume(&mut evaluation, Direction::Reverse) {
+		Ok(item) if item == parameters => return Ok(Case::Pass),
+		Ok(item) => item.collapse().map(|item| item.to_string())
+			.unwrap_or_else(|_| format!("{:?}", item)),
+		Err(diagnostic) => return Ok(Case::Fail(diagnostic.note("In reverse evaluation"))),
+	};
+
+	let span = divergence(context, function, arguments).unwrap_or(Span::INTERNAL);
+	let error = EvaluationError::ReversalMismatch;
+	Ok(Case::Fail(Diagnostic::new(Spanned::new(error, span))
+		.note(format!("Reversed parameters: {}", reversed))))
+}
+
+/// Evaluates until the function returns or the step limit is exceeded.
+fn resume(evaluation: &mut EvaluationContext, direction: Direction)
+          -> Result<EvaluationItem, Diagnostic> {
+	for _ in 0..STEP_LIMIT {
+		if let Some(item) = evaluation.step(direction)? {
+			return Ok(item);
+		}
+	}
+
+	let error = EvaluationError::StepLimit(STEP_LIMIT);
+	Err(Diagnostic::new(Spanned::new(error, Span::INTERNAL)))
+}
+
+#[derive(Debug)]
+struct Snapshot {
+	positions: Vec<(NodeTarget, usize)>,
+	frames: Vec<ValueFrame>,
+}
+
+impl Snapshot {
+	fn new(evaluation: &EvaluationContext) -> Self {
+		let positions = evaluation.functions.iter().map(FunctionFrame::position).collect();
+		Snapshot { positions, frames: evaluation.values.frames.clone() }
+	}
+}
+
+/// Locates the first statement whose reversal does not restore
+/// the state that preceded its evaluation.
+fn divergence(context: &Context, function: &Arc<BasicFunction>, arguments: &[Item]) -> Option<Span> {
+	let frame = ValueFrame::advance(function, arguments.iter().map(EvaluationItem::item));
+	let mut evaluation = EvaluationContext::new(context, Reversibility::Reversible,
+		FunctionFrame::new(function.clone(), Direction::Advance), frame).ok()?;
+
+	let mut snapshots = vec![Snapshot::new(&evaluation)];
+	let item = loop {
+		match evaluation.step(Direction::Advance).ok()? {
+			Some(item) => break item,
+			None => snapshots.push(Snapshot::new(&evaluation)),
+		}
+	};
+
+	snapshots.pop();
+	evaluation.values.frames.push(ValueFrame::reverse(function, item));
+	evaluation.functions.push(FunctionFrame::new(function.clone(), Direction::Reverse));
+	for expected in snapshots.iter().rev() {
+		let frame = evaluation.functions.last()?;
+		let span = frame.span(Direction::Reverse);
+		let location = target(frame);
+		match evaluation.step(Direction::Reverse) {
+			Ok(None) => (),
+			Ok(Some(_)) | Err(_) => return Some(span),
+		}
+
+		let positions: Vec<_> = evaluation.functions.iter().map(FunctionFrame::position).collect();
+		if positions != expected.positions {
+			return Some(span);
+		}
+
+		if let Some(location) = location {
+			let expected = &mut ValueStack { frames: expected.frames.clone() };
+			if !restored(&mut evaluation.values, expected, &location) {
+				return Some(span);
+			}
+		}
+	}
+	None
+}
+
+/// Provides the location that is restored by reversing the previous statement.
+fn target(frame: &FunctionFrame) -> Option<Location> {
+	let (node, index) = frame.position();
+	let node = &frame.function()[&node];
+	match node.direction {
+		Direction::Reverse => None,
+		Direction::Advance => match &node.statements.get(index.checked_sub(1)?)?.node {
+			Statement::Binding(_, Compound::Value(Value::Location(location))) |
+			Statement::Mutation(_, location, _) |
+			Statement::ImplicitDrop(location) => Some(location.clone()),
+			Statement::Binding(_, _) => None,
+		}
+	}
+}
+
+/// Checks that the location holds its value from before the statement.
+/// Locations that cannot be read are not restored.
+fn restored(values: &mut ValueStack, expected: &mut ValueStack, location: &Location) -> bool {
+	let is_present = |stack: &mut ValueStack| stack.frame().items.contains_key(&location.variable);
+	if !is_present(expected) || !is_present(values) {
+		return false;
+	}
+
+	let expected = match expected.location(location) {
+		Ok(expected) => expected.clone(),
+		Err(_) => return false,
+	};
+
+	expected == EvaluationItem::Item(Item::Uninitialised) ||
+		values.location(location).map(|item| item == &expected).unwrap_or(false)
+}
+
+/// Generates argument combinations for the parameters. Returns `None` if
+/// a parameter type cannot be generated.
+fn arguments(context: &Context, parameters: &[TypeResolution])
+             -> Result<Option<Vec<Vec<Item>>>, Diagnostic> {
+	let domains: Option<Vec<_>> = parameters.iter().map(domain).collect();
+	if let Some(domains) = domains {
+		let combinations = domains.iter().try_fold(1usize, |product, domain|
+			product.checked_mul(domain.len()));
+		if let Some(combinations) = combinations.filter(|count| *count <= EXHAUSTIVE_LIMIT) {
+			return Ok(Some((0..combinations).map(|mut index| domains.iter().map(|domain| {
+				let item = domain[index % domain.len()].clone();
+				index /= domain.len();
+				item
+			}).collect()).collect()));
+		}
+	}
+
+	let random = &mut Random(0x2545_f491_4f6c_dd1d);
+	let mut arguments = Vec::new();
+	for _ in 0..SAMPLES {
+		let mut items = Vec::new();
+		for parameter in parameters {
+			match generate(context, parameter, random)? {
+				Some(item) => items.push(item),
+				None => return Ok(None),
+			}
+		}
+		arguments.push(items);
+	}
+	Ok(Some(arguments))
+}
+
+/// Provides every value of a parameter type if the type is small.
+fn domain(parameter: &TypeResolution) -> Option<Vec<Item>> {
+	Some(match parameter.intrinsic()? {
+		Intrinsic::Truth => vec![Item::Truth(false), Item::Truth(true)],
+		Intrinsic::Unsigned8 => (0..=u8::max_value()).map(Item::Unsigned8).collect(),
+		Intrinsic::Signed8 => (i8::min_value()..=i8::max_value()).map(Item::Signed8).collect(),
+		Intrinsic::Unit => vec![Item::Unit],
+		_ => return None,
+	})
+}
+
+/// Generates a printable ASCII character.
+fn character(random: &mut Random) -> char {
+	(b' ' + (random.next() % 95) as u8) as char
+}
+
+fn generate(context: &Context, parameter: &TypeResolution,
+            random: &mut Random) -> Result<Option<Item>, Diagnostic> {
+	let (path, templates) = match parameter {
+		TypeResolution::Instance(path, templates) => (path, templates),
+		TypeResolution::Array(element, length) => {
+			let mut instance = Instance::new(parameter.clone());
+			for index in 0..*length {
+				match generate(context, element, random)? {
+					Some(item) => instance.fields.insert(index.to_string().into(), item),
+					None => return Ok(None),
+				};
+			}
+			return Ok(Some(Item::Instance(instance)));
+		}
+		TypeResolution::Reference(_, _) | TypeResolution::Template(_) => return Ok(None),
+	};
+
+	if let Some(intrinsic) = parameter.intrinsic() {
+		return Ok(match intrinsic {
+			Intrinsic::Truth => Some(Item::Truth(random.next() % 2 == 0)),
+			Intrinsic::Unit => Some(Item::Unit),
+			Intrinsic::Character => Some(Item::Character(character(random))),
+			Intrinsic::String => {
+				let length = random.next() % 8;
+				Some(Item::String((0..length).map(|_| character(random)).collect::<String>().into()))
+			}
+			intrinsic => Item::wrapping_integer(intrinsic, random.integer() as i128),
+		});
+	}
+
+	let mut instance = Instance::new(parameter.clone());
+	if path == &Intrinsic::Tuple.structure() {
+		for (index, template) in templates.iter().enumerate() {
+			match generate(context, template, random)? {
+				Some(item) => instance.fields.insert(index.to_string().into(), item),
+				None => return Ok(None),
+			};
+		}
+		return Ok(Some(Item::Instance(instance)));
+	}
+
+	let path = Spanned::new(Arc::new(path.clone()), Span::INTERNAL);
+	let structure = crate::node::structure(context, &path)?;
+	let templates = Iterator::zip(structure.templates.iter(), templates.iter())
+		.map(|(template, resolution)| (template.node.clone(), resolution.clone())).collect();
+	let mut fields = &structure.fields;
+	if !structure.variants.is_empty() {
+		let index = random.next() as usize % structure.variants.len();
+		let variant = &structure.variants[index];
+		instance.variant = Some((index, variant.identifier.node.clone()));
+		fields = &variant.fields;
+	}
+
+	for (field, ascription) in fields {
+		let resolution = super::argument::ascription_resolution(ascription, &templates);
+		match generate(context, &resolution, random)? {
+			Some(item) => instance.fields.insert(field.clone(), item),
+			None => return Ok(None),
+		};
+	}
+	Ok(Some(Item::Instance(instance)))
+}
+
+/// Deterministic xorshift generator so that failures are reproducible.
+#[derive(Debug)]
+struct Random(u64);
+
+impl Random {
+	fn next(&mut self) -> u64 {
+		let Random(state) = self;
+		*state ^= *state << 13;
+		*state ^= *state >> 7;
+		*state ^= *state << 17;
+		*state
+	}
+
+	/// Generates an integer that is small with even probability.
+	fn integer(&mut self) -> u64 {
+		match self.next() % 2 == 0 {
+			true => self.next() % 16,
+			false => self.next(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::source::context;
+
+	use super::*;
+
+	#[test]
+	fn test_verify() {
+		let context = &context("fn increment(~x: u8) -> u8:\n\tx += 1\n\tx\n\n\
+			fn identity<$T>(value: $T) -> $T:\n\tvalue\n");
+		assert_eq!(CommandVerify.execute(context, "crate::increment").unwrap(),
+			"crate::increment: 255 passed, 1 skipped\n");
+		assert_eq!(CommandVerify.execute(context, "crate::identity").unwrap(),
+			"crate::identity: Skipped with uninstantiated templates\n");
+	}
+
+	#[test]
+	fn test_forward_step_limit() {
+		let context = &context("fn spin(~x: truth, ~y: truth) -> truth:\n\t\
+			loop x == x => x != x:\n\t\tx <=> y\n\tx\n");
+		assert_eq!(CommandVerify.execute(context, "crate::spin").unwrap(),
+			"crate::spin: 0 passed, 4 skipped\n");
+	}
+
+	#[test]
+	fn test_isolate() {
+		match isolate(|| panic!("Invalid state")) {
+			Ok(Case::Fail(diagnostic)) => match diagnostic.error.node {
+				CompileError::Evaluation(EvaluationError::InternalPanic(message)) =>
+					assert_eq!(message, "Invalid state"),
+				other => panic!("Expected internal panic, instead got: {:?}", other),
+			},
+			other => panic!("Expected failed case, instead got: {:?}", other),
+		}
+	}
+}
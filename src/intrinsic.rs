@@ -1,16 +1,21 @@
 use crate::declaration::{DeclarationPath, ModulePath, StructurePath};
 
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub enum Intrinsic {
 	Unsigned8,
 	Unsigned16,
 	Unsigned32,
 	Unsigned64,
+	Unsigned128,
 	Signed8,
 	Signed16,
 	Signed32,
 	Signed64,
+	Signed128,
+	Integer,
 	Truth,
+	Character,
+	String,
 	Tuple,
 	Unit,
 }
@@ -23,17 +28,32 @@ impl Intrinsic {
 		})
 	}
 
+	pub fn is_integer(&self) -> bool {
+		match self {
+			Intrinsic::Unsigned8 | Intrinsic::Unsigned16 | Intrinsic::Unsigned32 |
+			Intrinsic::Unsigned64 | Intrinsic::Unsigned128 | Intrinsic::Signed8 |
+			Intrinsic::Signed16 | Intrinsic::Signed32 | Intrinsic::Signed64 |
+			Intrinsic::Signed128 | Intrinsic::Integer => true,
+			_ => false,
+		}
+	}
+
 	pub fn parse(string: &str) -> Option<Self> {
 		Some(match string {
 			"u8" => Intrinsic::Unsigned8,
 			"u16" => Intrinsic::Unsigned16,
 			"u32" => Intrinsic::Unsigned32,
 			"u64" => Intrinsic::Unsigned64,
+			"u128" => Intrinsic::Unsigned128,
 			"i8" => Intrinsic::Signed8,
 			"i16" => Intrinsic::Signed16,
 			"i32" => Intrinsic::Signed32,
 			"i64" => Intrinsic::Signed64,
+			"i128" => Intrinsic::Signed128,
+			"int" => Intrinsic::Integer,
 			"truth" => Intrinsic::Truth,
+			"char" => Intrinsic::Character,
+			"string" => Intrinsic::String,
 			_ => return None,
 		})
 	}
@@ -44,11 +64,16 @@ impl Intrinsic {
 			Intrinsic::Unsigned16 => "u16",
 			Intrinsic::Unsigned32 => "u32",
 			Intrinsic::Unsigned64 => "u64",
+			Intrinsic::Unsigned128 => "u128",
 			Intrinsic::Signed8 => "i8",
 			Intrinsic::Signed16 => "i16",
 			Intrinsic::Signed32 => "i32",
 			Intrinsic::Signed64 => "i64",
+			Intrinsic::Signed128 => "i128",
+			Intrinsic::Integer => "int",
 			Intrinsic::Truth => "truth",
+			Intrinsic::Character => "char",
+			Intrinsic::String => "string",
 			Intrinsic::Tuple => "tuple",
 			Intrinsic::Unit => "unit",
 		}
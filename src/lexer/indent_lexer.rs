@@ -5,16 +5,18 @@ use crate::source::SourceKey;
 use crate::span::{Span, Spanned};
 
 use super::lexer_tokenize::LexerTokenize;
-use super::token::{LexerToken, Token};
+use super::token::{Comment, LexerToken, Token};
 
 type LexerTokenizer<'a> = Peekable<LexerTokenize<'a>>;
 
 /// Resolves indentation into semantic blocks.
 /// Emits `End` tokens upon stream exhaustion.
+/// Lines containing only comments are treated as blank lines.
 #[derive(Debug, Clone)]
 pub struct IndentLexer<'a> {
 	lexer: LexerTokenizer<'a>,
 	end_token: Spanned<LexerToken>,
+	trivia: Vec<Spanned<Comment>>,
 
 	base_indent: usize,
 	indent_level: usize,
@@ -24,12 +26,12 @@ pub struct IndentLexer<'a> {
 }
 
 impl<'a> IndentLexer<'a> {
-	pub fn new(string: &'a str, source_key: SourceKey) -> Self {
+	/// Creates a lexer for a string that starts after the given indentation.
+	pub fn new(string: &'a str, source_key: SourceKey, mut base_indent: usize) -> Self {
 		let mut lexer = LexerTokenize::new(string, source_key).peekable();
 		let end_span = Span::new(source_key, string.len(), string.len() + 1);
 		let end_token = Spanned::new(LexerToken::Token(Token::End), end_span);
 
-		let mut base_indent = 0;
 		while let Some(Spanned { node: LexerToken::Indent, .. }) = lexer.peek() {
 			base_indent += 1;
 			lexer.next();
@@ -38,6 +40,7 @@ impl<'a> IndentLexer<'a> {
 		IndentLexer {
 			lexer,
 			end_token,
+			trivia: Vec::new(),
 			base_indent,
 			indent_level: base_indent,
 			current_indent: base_indent,
@@ -46,6 +49,11 @@ impl<'a> IndentLexer<'a> {
 		}
 	}
 
+	/// Takes the comments that have been passed over.
+	pub fn trivia(&mut self) -> Vec<Spanned<Comment>> {
+		std::mem::take(&mut self.trivia)
+	}
+
 	pub fn next(&mut self) -> Spanned<Token> {
 		if let Some(token) = self.resolve_indent() {
 			return token;
@@ -65,6 +73,7 @@ impl<'a> IndentLexer<'a> {
 						self.lexer.next();
 					}
 					LexerToken::Token(Token::LineBreak) => return self.next(),
+					LexerToken::Comment(_) => self.comment(),
 					_ => break,
 				}
 			}
@@ -87,12 +96,20 @@ impl<'a> IndentLexer<'a> {
 				}
 				_ => return Spanned::new(token, lexer_token.span),
 			}
+			LexerToken::Comment(comment) =>
+				self.trivia.push(Spanned::new(comment, lexer_token.span)),
 			LexerToken::Indent => (),
 		}
 
 		self.next()
 	}
 
+	fn comment(&mut self) {
+		if let Some(Spanned { node: LexerToken::Comment(comment), span }) = self.lexer.next() {
+			self.trivia.push(Spanned::new(comment, span));
+		}
+	}
+
 	fn resolve_indent(&mut self) -> Option<Spanned<Token>> {
 		let token = match usize::cmp(&self.indent_level, &self.current_indent) {
 			Ordering::Less => {
@@ -120,7 +137,7 @@ mod tests {
 
 	#[test]
 	fn test_indentation() {
-		let tokens = collect(IndentLexer::new("\t\t\n\t\t\t\t:\n\t\t", SourceKey::INTERNAL));
+		let tokens = collect(IndentLexer::new("\t\t\n\t\t\t\t:\n\t\t", SourceKey::INTERNAL, 0));
 		assert_eq!(&tokens, &[Token::LineBreak, Token::BlockOpen, Token::BlockOpen,
 			Token::Separator, Token::LineBreak, Token::BlockClose, Token::BlockClose,
 			Token::BlockClose, Token::BlockClose, Token::End]);
@@ -128,12 +145,36 @@ mod tests {
 
 	#[test]
 	fn test_blank_line() {
-		let tokens = collect(IndentLexer::new("\t\n\n\t\t:\n", SourceKey::INTERNAL));
+		let tokens = collect(IndentLexer::new("\t\n\n\t\t:\n", SourceKey::INTERNAL, 0));
 		assert_eq!(&tokens, &[Token::LineBreak, Token::LineBreak, Token::BlockOpen,
 			Token::Separator, Token::LineBreak, Token::BlockClose,
 			Token::BlockClose, Token::End]);
 	}
 
+	#[test]
+	fn test_comment_line() {
+		let string = "\t:\n// comment\n\t:";
+		let tokens = collect(IndentLexer::new(string, SourceKey::INTERNAL, 0));
+		assert_eq!(&tokens, &[Token::Separator, Token::LineBreak, Token::LineBreak,
+			Token::Separator, Token::BlockClose, Token::End]);
+
+		let mut lexer = IndentLexer::new(string, SourceKey::INTERNAL, 0);
+		while lexer.next().node != Token::End {}
+		let trivia = lexer.trivia();
+		assert_eq!(trivia.len(), 1);
+		assert_eq!(trivia[0].node, Comment::Line(" comment".into()));
+		assert_eq!((trivia[0].span.byte_start, trivia[0].span.byte_end), (3, 13));
+	}
+
+	#[test]
+	fn test_base_indentation() {
+		let string = ":\n\t\t:\n\t:";
+		let tokens = collect(IndentLexer::new(string, SourceKey::INTERNAL, 1));
+		assert_eq!(&tokens, &[Token::Separator, Token::LineBreak, Token::BlockOpen,
+			Token::Separator, Token::LineBreak, Token::BlockClose, Token::Separator,
+			Token::BlockClose, Token::End]);
+	}
+
 	fn collect(mut lexer: IndentLexer) -> Vec<Token> {
 		let mut tokens = Vec::new();
 		loop {
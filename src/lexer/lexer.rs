@@ -1,10 +1,42 @@
+use std::fmt;
+use std::sync::Arc;
+
 use crate::declaration::Declaration;
-use crate::error::Diagnostic;
+use crate::error::{CompileError, Diagnostic};
 use crate::source::{Source, SourceKey};
 use crate::span::Spanned;
 
 use super::space_lexer::SpaceLexer;
-use super::Token;
+use super::{Comment, Token};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LexerError {
+	MalformedInteger(Arc<str>),
+	IntegerRange(Arc<str>),
+	InvalidEscape(Arc<str>),
+	UnterminatedString(Arc<str>),
+}
+
+impl fmt::Display for LexerError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			LexerError::MalformedInteger(literal) =>
+				write!(f, "Integer literal: {}, is malformed", literal),
+			LexerError::IntegerRange(literal) =>
+				write!(f, "Integer literal: {}, is out of range", literal),
+			LexerError::InvalidEscape(literal) =>
+				write!(f, "Literal: {}, contains an invalid escape sequence", literal),
+			LexerError::UnterminatedString(literal) =>
+				write!(f, "String literal: {}, is not terminated", literal),
+		}
+	}
+}
+
+impl From<LexerError> for CompileError {
+	fn from(error: LexerError) -> Self {
+		CompileError::Lexer(error)
+	}
+}
 
 /// Adds one token lookahead.
 #[derive(Debug, Clone)]
@@ -17,9 +49,14 @@ pub struct Lexer<'a> {
 impl<'a> Lexer<'a> {
 	/// Creates a new `Lexer` instance.
 	/// `byte_offset` specifies where to start lexing.
+	/// Blocks are relative to the indentation of the line containing the offset.
 	pub fn new(string: &'a str, byte_offset: usize, source_key: SourceKey) -> Self {
+		let line_start = string[..byte_offset].rfind('\n').map(|index| index + 1).unwrap_or(0);
+		let base_indent = string[line_start..byte_offset].chars()
+			.take_while(|character| character == &'\t').count();
+
 		Lexer {
-			lexer: SpaceLexer::new(&string[byte_offset..], source_key),
+			lexer: SpaceLexer::new(&string[byte_offset..], source_key, base_indent),
 			token: None,
 			byte_offset,
 		}
@@ -37,6 +74,17 @@ impl<'a> Lexer<'a> {
 		self
 	}
 
+	/// Takes the comments that have been passed over including
+	/// any comments before the token in lookahead.
+	pub fn trivia(&mut self) -> Vec<Spanned<Comment>> {
+		let byte_offset = self.byte_offset;
+		self.lexer.trivia().into_iter().map(|mut comment| {
+			comment.span.byte_start += byte_offset;
+			comment.span.byte_end += byte_offset;
+			comment
+		}).collect()
+	}
+
 	pub fn next(&mut self) -> Spanned<Token> {
 		let lexer = &mut self.lexer;
 		let byte_offset = self.byte_offset;
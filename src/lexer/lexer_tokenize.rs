@@ -1,10 +1,14 @@
 use std::iter::Peekable;
+use std::sync::Arc;
 
+use crate::basic::Item;
+use crate::intrinsic::Intrinsic;
 use crate::source::SourceKey;
 use crate::span::Spanned;
 
+use super::LexerError;
 use super::source_split::SourceSplit;
-use super::token::{LexerToken, Token};
+use super::token::{Comment, LexerToken, Token};
 
 /// Parses a string into lexer tokens.
 /// Annotates string slices provided by `SourceSplit`.
@@ -33,11 +37,24 @@ impl<'a> Iterator for LexerTokenize<'a> {
 
 		Some(Spanned::new(match lexeme.node {
 			"\t" => LexerToken::Indent,
+			comment if comment.starts_with("///") && !comment.starts_with("////") =>
+				LexerToken::Comment(Comment::Documentation(comment[3..].into())),
+			comment if comment.starts_with("//") =>
+				LexerToken::Comment(Comment::Line(comment[2..].into())),
+			comment if comment.starts_with("/*") => {
+				let comment = &comment[2..];
+				LexerToken::Comment(Comment::Block(match comment.ends_with("*/") {
+					true => &comment[..comment.len() - 2],
+					false => comment,
+				}.into()))
+			}
 			token => LexerToken::Token(match token {
 				"\n" => Token::LineBreak,
 				"fn" => Token::Function,
 				"data" => Token::Data,
 				"define" => Token::Define,
+				"interface" => Token::Interface,
+				"for" => Token::For,
 				"module" => Token::Module,
 				"export" => Token::Export,
 				"use" => Token::Use,
@@ -45,17 +62,27 @@ impl<'a> Iterator for LexerTokenize<'a> {
 				"loop" => Token::Loop,
 				"drop" => Token::Drop,
 				"if" => Token::If,
+				"match" => Token::Match,
 				"self" => Token::SelfVariable,
-				"&" => Token::Reference,
+				"and" => Token::And,
+				"or" => Token::Or,
+				"not" => Token::Not,
+				"&" => match whitespace {
+					false => Token::Reference,
+					true => Token::BitAnd,
+				},
 				"~&" => Token::Unique,
 				"#" => Token::Compile,
 				"_" => Token::Wildcard,
 				"(" => Token::ParenthesisOpen,
 				")" => Token::ParenthesisClose,
+				"[" => Token::BracketOpen,
+				"]" => Token::BracketClose,
 				"." => Token::Dot,
 				":" => Token::Separator,
 				"::" => Token::PathSeparator,
 				"," => Token::ListSeparator,
+				";" => Token::Terminator,
 				"$" => Token::Template,
 				"~" => Token::Mutable,
 				"'" => Token::Prime,
@@ -68,19 +95,46 @@ impl<'a> Iterator for LexerTokenize<'a> {
 				"+=" => Token::AddAssign,
 				"-=" => Token::MinusAssign,
 				"*=" => Token::MultiplyAssign,
+				"/" => Token::Divide,
+				"%" => Token::Modulo,
+				"|" => Token::BitOr,
+				"^" => Token::BitXor,
+				"<<" => Token::ShiftLeft,
+				">>" => Token::ShiftRight,
+				"/=" => Token::DivideAssign,
+				"%=" => Token::ModuloAssign,
+				"&=" => Token::BitAndAssign,
+				"|=" => Token::BitOrAssign,
+				"^=" => Token::BitXorAssign,
+				"<<=" => Token::ShiftLeftAssign,
+				">>=" => Token::ShiftRightAssign,
 				"=" => Token::Assign,
 				"<" => Token::AngleLeft,
 				">" => Token::AngleRight,
 				"<=" => Token::LessEqual,
 				">=" => Token::GreaterEqual,
 				"==" => Token::Equality,
+				"!=" => Token::NotEqual,
 				"=>" => Token::Implies,
 				"<=>" => Token::Swap,
 				"->" => Token::ReturnSeparator,
 				_ if is_whitespace => return self.next(),
 				other => {
-					if let Ok(integer) = other.parse::<i128>() {
-						Token::Integer(integer)
+					if other.starts_with(|character: char| character.is_ascii_digit()) {
+						match integer(other) {
+							Ok((integer, intrinsic)) => Token::Integer(integer, intrinsic),
+							Err(error) => Token::Error(error),
+						}
+					} else if other.starts_with('"') {
+						match string(other) {
+							Ok(string) => Token::String(string),
+							Err(error) => Token::Error(error),
+						}
+					} else if other.starts_with('\'') {
+						match escape(other, &other[1..other.len() - 1]) {
+							Ok(string) => Token::Character(string.chars().next().unwrap()),
+							Err(error) => Token::Error(error),
+						}
 					} else if let Ok(truth) = other.parse::<bool>() {
 						Token::Truth(truth)
 					} else {
@@ -92,6 +146,83 @@ impl<'a> Iterator for LexerTokenize<'a> {
 	}
 }
 
+/// Parses an integer literal with an optional radix prefix,
+/// digit separators and type suffix.
+fn integer(literal: &str) -> Result<(u128, Option<Intrinsic>), LexerError> {
+	let (radix, digits) = match literal.get(..2) {
+		Some("0x") => (16, &literal[2..]),
+		Some("0o") => (8, &literal[2..]),
+		Some("0b") => (2, &literal[2..]),
+		_ => (10, literal),
+	};
+
+	let malformed = || LexerError::MalformedInteger(literal.into());
+	let split = digits.find(|character: char| !character.is_digit(radix) && character != '_')
+		.unwrap_or(digits.len());
+	let (digits, suffix) = digits.split_at(split);
+	let digits: String = digits.chars().filter(|character| character != &'_').collect();
+	if digits.is_empty() {
+		return Err(malformed());
+	}
+
+	let intrinsic = match suffix {
+		"" => None,
+		suffix => match Intrinsic::parse(suffix) {
+			Some(Intrinsic::Truth) | Some(Intrinsic::Character) |
+			Some(Intrinsic::String) | None => return Err(malformed()),
+			intrinsic => intrinsic,
+		},
+	};
+
+	// Digits have been validated so parsing can only fail on overflow.
+	let range = || LexerError::IntegerRange(literal.into());
+	let integer = u128::from_str_radix(&digits, radix).map_err(|_| range())?;
+	if let Some(intrinsic) = intrinsic {
+		// The literal may be negated so the magnitude of the minimum is permitted.
+		if Item::literal(intrinsic, integer, false).or_else(|| Item::literal(intrinsic, integer, true)).is_none() {
+			return Err(range());
+		}
+	}
+	Ok((integer, intrinsic))
+}
+
+/// Parses a string literal including the enclosing quotes.
+fn string(literal: &str) -> Result<Arc<str>, LexerError> {
+	let unterminated = || LexerError::UnterminatedString(literal.into());
+	if literal.len() < 2 || !literal.ends_with('"') {
+		return Err(unterminated());
+	}
+
+	// The closing quote is escaped if preceded by an odd number of backslashes.
+	let body = &literal[1..literal.len() - 1];
+	match (body.len() - body.trim_end_matches('\\').len()) % 2 {
+		0 => escape(literal, body).map(Arc::from),
+		_ => Err(unterminated()),
+	}
+}
+
+/// Replaces the escape sequences within the body of a text literal.
+fn escape(literal: &str, body: &str) -> Result<String, LexerError> {
+	let mut string = String::new();
+	let mut characters = body.chars();
+	while let Some(character) = characters.next() {
+		string.push(match character {
+			'\\' => match characters.next() {
+				Some('n') => '\n',
+				Some('r') => '\r',
+				Some('t') => '\t',
+				Some('0') => '\0',
+				Some('\\') => '\\',
+				Some('\'') => '\'',
+				Some('"') => '"',
+				_ => return Err(LexerError::InvalidEscape(literal.into())),
+			},
+			other => other,
+		});
+	}
+	Ok(string)
+}
+
 fn whitespace(lexeme: Option<&Spanned<&str>>) -> bool {
 	lexeme.and_then(|lexeme| lexeme.node.chars().next().map(char::is_whitespace)) == Some(true)
 }
@@ -123,7 +254,115 @@ mod tests {
 			.map(|lexeme| lexeme.node).collect();
 		assert_eq!(lexemes, &[Token(Use), Token(Identifier("test".into())), Token(PathSeparator),
 			Token(Asterisk), Token(LineBreak), Token(Asterisk), Token(ParenthesisOpen),
-			Token(Integer(2)), Token(Multiply), Token(Integer(3)), Token(ParenthesisClose)]);
+			Token(Integer(2, None)), Token(Multiply), Token(Integer(3, None)), Token(ParenthesisClose)]);
+	}
+
+	#[test]
+	fn test_operators() {
+		let string = "x & y | &z\nx <<= 2 % 3";
+		let lexemes: Vec<_> = LexerTokenize::new(string, SourceKey::INTERNAL)
+			.map(|lexeme| lexeme.node).collect();
+		assert_eq!(lexemes, &[Token(Identifier("x".into())), Token(BitAnd), Token(Identifier("y".into())),
+			Token(BitOr), Token(Reference), Token(Identifier("z".into())), Token(LineBreak),
+			Token(Identifier("x".into())), Token(ShiftLeftAssign), Token(Integer(2, None)),
+			Token(Modulo), Token(Integer(3, None))]);
+	}
+
+	#[test]
+	fn test_logical() {
+		let string = "not x != y or z";
+		let lexemes: Vec<_> = LexerTokenize::new(string, SourceKey::INTERNAL)
+			.map(|lexeme| lexeme.node).collect();
+		assert_eq!(lexemes, &[Token(Not), Token(Identifier("x".into())), Token(NotEqual),
+			Token(Identifier("y".into())), Token(Or), Token(Identifier("z".into()))]);
+	}
+
+	#[test]
+	fn test_variants() {
+		let string = "| Circle: radius: u32\nmatch shape:";
+		let lexemes: Vec<_> = LexerTokenize::new(string, SourceKey::INTERNAL)
+			.map(|lexeme| lexeme.node).collect();
+		assert_eq!(lexemes, &[Token(BitOr), Token(Identifier("Circle".into())), Token(Separator),
+			Token(Identifier("radius".into())), Token(Separator), Token(Identifier("u32".into())),
+			Token(LineBreak), Token(Match), Token(Identifier("shape".into())), Token(Separator)]);
+	}
+
+	#[test]
+	fn test_interface() {
+		let string = "define Shape for Square:\nfn total<$T: Shape>";
+		let lexemes: Vec<_> = LexerTokenize::new(string, SourceKey::INTERNAL)
+			.map(|lexeme| lexeme.node).collect();
+		assert_eq!(lexemes, &[Token(Define), Token(Identifier("Shape".into())), Token(For),
+			Token(Identifier("Square".into())), Token(Separator), Token(LineBreak), Token(Function),
+			Token(Identifier("total".into())), Token(AngleLeft), Token(Template), Token(Identifier("T".into())),
+			Token(Separator), Token(Identifier("Shape".into())), Token(AngleRight)]);
+	}
+
+	#[test]
+	fn test_array() {
+		let string = "let a: [u32; 2] = [1, 2]\na[0] <=> a[1]";
+		let lexemes: Vec<_> = LexerTokenize::new(string, SourceKey::INTERNAL)
+			.map(|lexeme| lexeme.node).collect();
+		assert_eq!(lexemes, &[Token(Let), Token(Identifier("a".into())), Token(Separator),
+			Token(BracketOpen), Token(Identifier("u32".into())), Token(Terminator), Token(Integer(2, None)),
+			Token(BracketClose), Token(Assign), Token(BracketOpen), Token(Integer(1, None)),
+			Token(ListSeparator), Token(Integer(2, None)), Token(BracketClose), Token(LineBreak),
+			Token(Identifier("a".into())), Token(BracketOpen), Token(Integer(0, None)), Token(BracketClose),
+			Token(Swap), Token(Identifier("a".into())), Token(BracketOpen), Token(Integer(1, None)),
+			Token(BracketClose)]);
+	}
+
+	#[test]
+	fn test_integer() {
+		let string = "0xff 0b1010 0o17 1_000_000 42u8 0x_7fi16 0xffffffffffffffffffffffffffffffffu128";
+		let lexemes: Vec<_> = LexerTokenize::new(string, SourceKey::INTERNAL)
+			.map(|lexeme| lexeme.node).collect();
+		assert_eq!(lexemes, &[Token(Integer(255, None)), Token(Integer(10, None)),
+			Token(Integer(15, None)), Token(Integer(1_000_000, None)),
+			Token(Integer(42, Some(Intrinsic::Unsigned8))), Token(Integer(127, Some(Intrinsic::Signed16))),
+			Token(Integer(u128::max_value(), Some(Intrinsic::Unsigned128)))]);
+	}
+
+	#[test]
+	fn test_integer_error() {
+		let string = "0x 12ab 0b102 256u8 128i8 0xffffffffffffffffffffffffffffffffff";
+		let lexemes: Vec<_> = LexerTokenize::new(string, SourceKey::INTERNAL)
+			.map(|lexeme| lexeme.node).collect();
+		assert_eq!(lexemes, &[Token(Error(LexerError::MalformedInteger("0x".into()))),
+			Token(Error(LexerError::MalformedInteger("12ab".into()))),
+			Token(Error(LexerError::MalformedInteger("0b102".into()))),
+			Token(Error(LexerError::IntegerRange("256u8".into()))),
+			Token(Integer(128, Some(Intrinsic::Signed8))),
+			Token(Error(LexerError::IntegerRange("0xffffffffffffffffffffffffffffffffff".into())))]);
+	}
+
+	#[test]
+	fn test_text() {
+		let string = "'a' == '\\n' \"label\\t\\\"\\\"\" &'a \"\\q\" \"open";
+		let lexemes: Vec<_> = LexerTokenize::new(string, SourceKey::INTERNAL)
+			.map(|lexeme| lexeme.node).collect();
+		assert_eq!(lexemes, &[Token(Character('a')), Token(Equality), Token(Character('\n')),
+			Token(String("label\t\"\"".into())), Token(Reference), Token(Prime), Token(Identifier("a".into())),
+			Token(Error(LexerError::InvalidEscape("\"\\q\"".into()))),
+			Token(Error(LexerError::UnterminatedString("\"open".into())))]);
+	}
+
+	#[test]
+	fn test_comment() {
+		let string = "x // line\n/* block */";
+		let lexemes: Vec<_> = LexerTokenize::new(string, SourceKey::INTERNAL)
+			.map(|lexeme| lexeme.node).collect();
+		assert_eq!(lexemes, &[Token(Identifier("x".into())), Comment(super::Comment::Line(" line".into())),
+			Token(LineBreak), Comment(super::Comment::Block(" block ".into()))]);
+	}
+
+	#[test]
+	fn test_documentation() {
+		let string = "/// documentation\n//// line";
+		let lexemes: Vec<_> = LexerTokenize::new(string, SourceKey::INTERNAL)
+			.map(|lexeme| lexeme.node).collect();
+		assert_eq!(lexemes, &[Comment(super::Comment::Documentation(" documentation".into())),
+			Token(LineBreak), Comment(super::Comment::Line("// line".into()))]);
 	}
 
 	#[test]
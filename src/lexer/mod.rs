@@ -1,5 +1,5 @@
-pub use lexer::Lexer;
-pub use token::Token;
+pub use lexer::{Lexer, LexerError};
+pub use token::{Comment, Token};
 
 mod token;
 mod source_split;
@@ -4,7 +4,7 @@ use std::str::CharIndices;
 use crate::source::SourceKey;
 use crate::span::{Span, Spanned};
 
-pub const SINGULARITIES: &[char] = &['\t', '\n', '(', ')', ',', '*', '.', '$'];
+pub const SINGULARITIES: &[char] = &['\t', '\n', '(', ')', '[', ']', ';', ',', '*', '.', '$', '"'];
 
 /// Splits a source string into spanned string slices.
 #[derive(Debug, Clone)]
@@ -26,14 +26,83 @@ impl<'a> SourceSplit<'a> {
 		Spanned::new(&self.string[byte_start..byte_end], span)
 	}
 
-	fn comment(&mut self) -> Option<()> {
-		loop {
-			let (_, character) = self.characters.peek()?;
+	fn byte_offset(&mut self) -> usize {
+		let byte_end = self.byte_end;
+		self.characters.peek().map(|(index, _)| *index).unwrap_or(byte_end)
+	}
+
+	fn line_comment(&mut self, byte_start: usize) -> Spanned<&'a str> {
+		while let Some((_, character)) = self.characters.peek() {
 			match character {
-				'\n' => break Some(()),
+				'\n' => break,
 				_ => self.characters.next(),
 			};
 		}
+		let byte_end = self.byte_offset();
+		self.slice(byte_start, byte_end)
+	}
+
+	/// Consumes a block comment including any nested block comments.
+	/// Unterminated comments extend to the end of the source.
+	fn block_comment(&mut self, byte_start: usize) -> Spanned<&'a str> {
+		self.characters.next();
+		let mut depth = 1;
+		while depth > 0 {
+			let (_, character) = match self.characters.next() {
+				Some(character) => character,
+				None => break,
+			};
+
+			let next = self.characters.peek().map(|(_, next)| *next);
+			match (character, next) {
+				('/', Some('*')) => {
+					self.characters.next();
+					depth += 1;
+				}
+				('*', Some('/')) => {
+					self.characters.next();
+					depth -= 1;
+				}
+				_ => (),
+			}
+		}
+		let byte_end = self.byte_offset();
+		self.slice(byte_start, byte_end)
+	}
+
+	/// Consumes a string literal including any escaped quotes.
+	/// Unterminated strings extend to the end of the line.
+	fn string(&mut self, byte_start: usize) -> Spanned<&'a str> {
+		let mut escape = false;
+		while let Some((_, character)) = self.characters.peek().cloned() {
+			if character == '\n' { break; }
+			self.characters.next();
+			match character {
+				'"' if !escape => break,
+				'\\' => escape = !escape,
+				_ => escape = false,
+			}
+		}
+		let byte_end = self.byte_offset();
+		self.slice(byte_start, byte_end)
+	}
+
+	/// Consumes a character literal if the prime begins one.
+	/// Otherwise the prime is left to annotate a lifetime.
+	fn character(&mut self, byte_start: usize) -> Option<Spanned<&'a str>> {
+		let mut characters = self.characters.clone();
+		match characters.next()? {
+			(_, '\\') => { characters.next()?; }
+			(_, '\'') | (_, '\n') => return None,
+			_ => (),
+		}
+
+		match characters.next()? {
+			(_, '\'') => self.characters = characters,
+			_ => return None,
+		}
+		let byte_end = self.byte_offset();
+		Some(self.slice(byte_start, byte_end))
 	}
 }
 
@@ -42,17 +111,30 @@ impl<'a> Iterator for SourceSplit<'a> {
 
 	fn next(&mut self) -> Option<Self::Item> {
 		let (byte_start, initial) = self.characters.next()?;
+		match (initial, self.characters.peek()) {
+			('/', Some((_, '/'))) => return Some(self.line_comment(byte_start)),
+			('/', Some((_, '*'))) => return Some(self.block_comment(byte_start)),
+			('*', Some((_, '='))) => {
+				self.characters.next();
+				return Some(self.slice(byte_start, byte_start + 2));
+			}
+			_ => (),
+		}
+
+		match initial {
+			'"' => return Some(self.string(byte_start)),
+			'\'' => if let Some(character) = self.character(byte_start) {
+				return Some(character);
+			},
+			_ => (),
+		}
+
 		if SINGULARITIES.contains(&initial) || initial == '\'' {
 			let byte_end = self.characters.peek()
 				.map(|(index, _)| *index).unwrap_or(self.byte_end);
 			return Some(self.slice(byte_start, byte_end));
 		}
 
-		if let ('/', Some((_, '/'))) = (initial, self.characters.peek()) {
-			self.comment()?;
-			return self.next();
-		}
-
 		let mut byte_end: Option<usize> = None;
 		while let Some((index, character)) = self.characters.peek() {
 			let kind_difference = CharacterKind::kind(initial) !=
@@ -109,10 +191,28 @@ mod tests {
 		assert_eq!(&lexemes, &["root", "::", "first", "::", "second", "::", "third"]);
 	}
 
+	#[test]
+	fn test_multiply_assign() {
+		let string = "x *= *y";
+		let lexemes: Vec<_> = SourceSplit::new(string, SourceKey::INTERNAL)
+			.map(|node| node.node).collect();
+		assert_eq!(&lexemes, &["x", " ", "*=", " ", "*", "y"]);
+	}
+
 	#[test]
 	fn test_comment() {
 		let string = "// comment";
-		assert!(SourceSplit::new(string, SourceKey::INTERNAL).next().is_none());
+		let lexemes: Vec<_> = SourceSplit::new(string, SourceKey::INTERNAL)
+			.map(|node| node.node).collect();
+		assert_eq!(&lexemes, &["// comment"]);
+	}
+
+	#[test]
+	fn test_block_comment() {
+		let string = "a /* outer /* inner */\n*/ b";
+		let lexemes: Vec<_> = SourceSplit::new(string, SourceKey::INTERNAL)
+			.map(|node| node.node).collect();
+		assert_eq!(&lexemes, &["a", " ", "/* outer /* inner */\n*/", " ", "b"]);
 	}
 
 	#[test]
@@ -124,6 +224,15 @@ mod tests {
 			":", " ", "&", "'", "lifetime", ")"]);
 	}
 
+	#[test]
+	fn test_text() {
+		let string = "f('a', '\\'', \"a \\\"b\\\" c\", &'b x)";
+		let lexemes: Vec<_> = SourceSplit::new(string, SourceKey::INTERNAL)
+			.map(|node| node.node).collect();
+		assert_eq!(&lexemes, &["f", "(", "'a'", ",", " ", "'\\''", ",", " ",
+			"\"a \\\"b\\\" c\"", ",", " ", "&", "'", "b", " ", "x", ")"]);
+	}
+
 	#[test]
 	fn test_unique() {
 		let string = "let (~variable) = ~&expression";
@@ -1,4 +1,4 @@
-use crate::lexer::Token;
+use crate::lexer::{Comment, Token};
 use crate::source::SourceKey;
 use crate::span::Spanned;
 
@@ -16,25 +16,31 @@ pub struct SpaceLexer<'a> {
 }
 
 impl<'a> SpaceLexer<'a> {
-	pub fn new(string: &'a str, source_key: SourceKey) -> Self {
+	pub fn new(string: &'a str, source_key: SourceKey, base_indent: usize) -> Self {
 		SpaceLexer {
-			lexer: IndentLexer::new(string, source_key),
+			lexer: IndentLexer::new(string, source_key, base_indent),
 			brackets: Vec::new(),
 			bracket_indent: 0,
 			buffer: None,
 		}
 	}
 
+	pub fn trivia(&mut self) -> Vec<Spanned<Comment>> {
+		self.lexer.trivia()
+	}
+
 	pub fn next(&mut self) -> Spanned<Token> {
 		let token = self.next_token();
 		match token.node {
 			Token::ParenthesisOpen => self.brackets.push(token.node.clone()),
 			Token::ParenthesisClose => self.pop_expected(Token::ParenthesisOpen),
+			Token::BracketOpen => self.brackets.push(token.node.clone()),
+			Token::BracketClose => self.pop_expected(Token::BracketOpen),
 			Token::LineBreak if !self.brackets.is_empty() => return self.next(),
 			Token::BlockOpen if !self.brackets.is_empty() => return self.block_open(),
 			Token::BlockClose if !self.brackets.is_empty() || self.bracket_indent > 0 =>
 				return self.block_close(),
-			Token::Module | Token::Function | Token::Data => self.reset(),
+			Token::Module | Token::Function | Token::Data | Token::Interface => self.reset(),
 			_ => (),
 		}
 		token
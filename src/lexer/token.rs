@@ -1,24 +1,36 @@
 use std::sync::Arc;
 
+use crate::intrinsic::Intrinsic;
+
+use super::LexerError;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Token {
 	Data,
 	Define,
+	Interface,
+	For,
 	Function,
 	Module,
 	Export,
 	Use,
 	SelfVariable,
 	Identifier(Arc<str>),
-	Integer(i128),
+	/// Contains the magnitude of the literal as negation is a separate operator.
+	Integer(u128, Option<Intrinsic>),
 	Truth(bool),
+	Character(char),
+	String(Arc<str>),
 	ParenthesisOpen,
 	ParenthesisClose,
+	BracketOpen,
+	BracketClose,
 	BlockOpen,
 	BlockClose,
 	Dot,
 	Separator,
 	ListSeparator,
+	Terminator,
 	ReturnSeparator,
 	PathSeparator,
 	Wildcard,
@@ -29,12 +41,17 @@ pub enum Token {
 	Loop,
 	Drop,
 	If,
+	Match,
 	Assign,
 	AngleLeft,
 	AngleRight,
 	LessEqual,
 	GreaterEqual,
 	Equality,
+	NotEqual,
+	And,
+	Or,
+	Not,
 	Implies,
 	Swap,
 	Template,
@@ -47,12 +64,37 @@ pub enum Token {
 	AddAssign,
 	MinusAssign,
 	MultiplyAssign,
+	Divide,
+	Modulo,
+	BitAnd,
+	BitOr,
+	BitXor,
+	ShiftLeft,
+	ShiftRight,
+	DivideAssign,
+	ModuloAssign,
+	BitAndAssign,
+	BitOrAssign,
+	BitXorAssign,
+	ShiftLeftAssign,
+	ShiftRightAssign,
 	LineBreak,
+	Error(LexerError),
 	End,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum LexerToken {
 	Token(Token),
+	Comment(Comment),
 	Indent,
 }
+
+/// Source text that is ignored by the parser.
+/// Contains the text between the comment delimiters.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Comment {
+	Line(Arc<str>),
+	Block(Arc<str>),
+	Documentation(Arc<str>),
+}
@@ -14,8 +14,13 @@ mod basic;
 mod extension;
 mod evaluation;
 mod inference;
+mod borrow;
 mod intrinsic;
 mod interface;
+mod format;
+mod document;
+mod reload;
+mod server;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
 	let arguments: Vec<_> = std::env::args_os().collect();
@@ -24,8 +29,40 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 		None => return Ok(eprintln!("The module root file is not specified")),
 	};
 
+	if arguments.get(2).map(|argument| argument == "--server").unwrap_or(false) {
+		return server::server(root_path);
+	}
+
 	let context = &context::Context::default();
 	crate::declaration::module_root(context, root_path);
 	crate::node::load_definitions(context);
-	interface::interface(context)
+	if arguments.get(2).map(|argument| argument == "--fmt").unwrap_or(false) {
+		let is_check = arguments.get(3).map(|argument| argument == "--check").unwrap_or(false);
+		return match format::format(context, is_check) {
+			false => std::process::exit(1),
+			true => Ok(()),
+		};
+	}
+
+	if arguments.get(2).map(|argument| argument == "--doc").unwrap_or(false) {
+		let directory = arguments.get(3).map(std::path::PathBuf::from)
+			.unwrap_or_else(|| std::path::PathBuf::from("doc"));
+		return match document::document(context, &directory) {
+			false => std::process::exit(1),
+			true => Ok(()),
+		};
+	}
+
+	let is_watch = arguments.get(2).map(|argument| argument == "--watch").unwrap_or(false);
+	match arguments.len() > 2 && !is_watch {
+		false => interface::interface(context, is_watch),
+		true => {
+			let arguments: Vec<_> = arguments[2..].iter()
+				.map(|argument| argument.to_string_lossy().into_owned()).collect();
+			match interface::batch(context, &arguments) {
+				false => std::process::exit(1),
+				true => Ok(()),
+			}
+		}
+	}
 }
@@ -1,11 +1,11 @@
 use std::sync::Arc;
 
 use crate::context::Context;
-use crate::declaration::{FunctionPath, StructurePath};
+use crate::declaration::{FunctionPath, InterfacePath, StructurePath};
 use crate::error::Diagnostic;
 use crate::span::Spanned;
 
-use super::{FunctionType, NodeFunction, Structure};
+use super::{FunctionType, Interface, NodeFunction, Structure};
 
 pub fn function_type(context: &Context, function_path: &Spanned<Arc<FunctionPath>>)
                      -> Result<Arc<FunctionType>, Diagnostic> {
@@ -36,6 +36,7 @@ pub fn function(context: &Context, function_path: &Spanned<Arc<FunctionPath>>)
 	let FunctionPath(declaration_path) = function_path.node.as_ref();
 	let mut function = crate::parser::function(context, function_path)?;
 	super::shadow::shadow_function(&mut function)?;
+	super::mutability::mutability_function(&mut function)?;
 
 	let context_path = context.node_definitions.get(&function_path.node).map(|definition|
 		definition.declaration.clone()).unwrap_or(declaration_path.module_path.clone());
@@ -62,3 +63,26 @@ pub fn structure(context: &Context, structure_path: &Spanned<Arc<StructurePath>>
 	context.node_structures.insert(structure_path.node.clone(), structure.clone());
 	Ok(structure)
 }
+
+pub fn interface(context: &Context, interface_path: &Spanned<Arc<InterfacePath>>)
+                 -> Result<Arc<Interface>, Diagnostic> {
+	if let Some(interface) = context.node_interfaces.get(&interface_path.node) {
+		return Ok(interface.clone());
+	}
+
+	let InterfacePath(declaration_path) = interface_path.node.as_ref();
+	let mut interface = crate::parser::interface(context, interface_path)?;
+	let module_contexts = context.module_contexts.read();
+	let module_context = module_contexts.get(&declaration_path.module_path).unwrap();
+	let methods = interface.methods.iter().map(|(identifier, function_type)| {
+		let mut function_type = function_type.as_ref().clone();
+		super::shadow::shadow_function_type(&mut function_type)?;
+		super::resolution::resolve_function_type(context, module_context, &mut function_type)?;
+		Ok((identifier.clone(), Arc::new(function_type)))
+	}).collect::<Result<_, Diagnostic>>()?;
+	interface.methods = methods;
+
+	let interface = Arc::new(interface);
+	context.node_interfaces.insert(interface_path.node.clone(), interface.clone());
+	Ok(interface)
+}
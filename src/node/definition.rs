@@ -1,13 +1,15 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use chashmap::CHashMap;
 
 use crate::context::Context;
-use crate::declaration::{DeclarationPath, FunctionPath, ModuleContext, ModulePath, StructurePath};
+use crate::declaration::{DeclarationPath, FunctionPath, InterfacePath, ModuleContext,
+	ModulePath, StructurePath};
 use crate::error::Diagnostic;
 use crate::span::Spanned;
 
-use super::NodeError;
+use super::{Ascription, AscriptionPattern, FunctionType, NodeError, Parameter, Pattern};
 
 pub type NodeDefinitions = CHashMap<Arc<FunctionPath>, Arc<Definition>>;
 
@@ -15,6 +17,7 @@ pub type NodeDefinitions = CHashMap<Arc<FunctionPath>, Arc<Definition>>;
 pub struct Definition {
 	pub declaration: Arc<ModulePath>,
 	pub structure: Spanned<StructurePath>,
+	pub interface: Option<Spanned<InterfacePath>>,
 	pub templates: Vec<Spanned<Arc<str>>>,
 }
 
@@ -25,6 +28,14 @@ pub fn load_definitions(context: &Context) {
 	}
 }
 
+/// Loads the definitions from the contexts of the given modules.
+pub fn reload_definitions(context: &Context, modules: &HashSet<Arc<ModulePath>>) {
+	for (module_path, module) in context.module_contexts.read().iter()
+		.filter(|(module_path, _)| modules.contains(*module_path)) {
+		context.emit(module_definitions(context, module_path.clone(), module));
+	}
+}
+
 fn module_definitions(context: &Context, module_path: Arc<ModulePath>,
                       module: &ModuleContext) -> Result<(), Diagnostic> {
 	module.definitions.iter().try_for_each(|definition| {
@@ -32,11 +43,20 @@ fn module_definitions(context: &Context, module_path: Arc<ModulePath>,
 			module_path.clone(), &definition.declaration)?;
 		super::resolution::resolve_structure_path(context,
 			module, &mut node_definition.structure)?;
+		if let Some(interface) = &mut node_definition.interface {
+			super::resolution::resolve_interface_path(context, module, interface)?;
+		}
 
 		let node_definition = Arc::new(node_definition);
 		let StructurePath(path) = &node_definition.structure.node;
 		let module_path = path.module_path.clone().push(path.identifier.clone());
 
+		let function_paths = definition.methods.iter().map(|(identifier, declaration)| {
+			let module_path = module_path.clone();
+			let path = DeclarationPath { module_path, identifier: identifier.clone() };
+			Spanned::new(Arc::new(FunctionPath(path)), declaration.span)
+		}).collect::<Vec<_>>();
+
 		definition.methods.iter().cloned().try_for_each(|(identifier, declaration)| {
 			let module_path = module_path.clone();
 			let path = DeclarationPath { module_path, identifier: identifier.clone() };
@@ -57,6 +77,189 @@ fn module_definitions(context: &Context, module_path: Arc<ModulePath>,
 						.note(format!("Duplicate declared in: {}", location)))
 				}
 			}
-		})
+		})?;
+
+		match &node_definition.interface {
+			Some(interface) => implementation(context, &node_definition, interface, &function_paths),
+			None => Ok(()),
+		}
 	})
 }
+
+/// Registers an implementation of an interface if the methods
+/// of the definition match the methods of the interface.
+fn implementation(context: &Context, definition: &Arc<Definition>, interface_path: &Spanned<InterfacePath>,
+                  function_paths: &[Spanned<Arc<FunctionPath>>]) -> Result<(), Diagnostic> {
+	let structure = definition.structure.node.clone();
+	let key = (structure.clone(), interface_path.node.clone());
+	if context.node_implementations.contains_key(&key) {
+		let error = NodeError::DuplicateImplementation(interface_path.node.clone(), structure);
+		return Err(Diagnostic::new(Spanned::new(error, interface_path.span)));
+	}
+
+	conformance(context, definition, interface_path, function_paths)?;
+	Ok(context.node_implementations.insert(key, definition.clone()).unwrap_none())
+}
+
+/// Checks the implementations of the given interfaces again.
+/// Implementations that no longer match the interface are removed.
+pub fn reload_implementations(context: &Context, interfaces: &HashSet<Arc<InterfacePath>>) {
+	for (key, definition) in context.node_implementations.clone() {
+		let (_, interface_path) = &key;
+		if !interfaces.contains(interface_path) {
+			continue;
+		}
+
+		let function_paths: Vec<_> = context.node_definitions.clone().into_iter()
+			.filter(|(_, other)| Arc::ptr_eq(other, &definition)).map(|(path, _)| {
+				let span = context.declarations_function.get(&path).unwrap().span();
+				Spanned::new(path, span)
+			}).collect();
+
+		let interface_path = definition.interface.clone().unwrap();
+		let conformance = conformance(context, &definition, &interface_path, &function_paths);
+		if context.emit(conformance).is_none() {
+			context.node_implementations.remove(&key);
+		}
+	}
+}
+
+fn conformance(context: &Context, definition: &Definition, interface_path: &Spanned<InterfacePath>,
+               function_paths: &[Spanned<Arc<FunctionPath>>]) -> Result<(), Diagnostic> {
+	let interface = crate::node::interface(context, &interface_path.clone().map(Arc::new))?;
+	for function_path in function_paths {
+		let FunctionPath(path) = function_path.node.as_ref();
+		let (method, interface_type) = interface.methods.iter()
+			.find(|(method, _)| method.node == path.identifier).ok_or_else(|| {
+				let interface = interface_path.node.clone();
+				let error = NodeError::UndefinedInterfaceMethod(interface, path.identifier.clone());
+				Diagnostic::new(Spanned::new(error, function_path.span))
+			})?;
+
+		let function_type = crate::node::function_type(context, function_path)?;
+		if !conforms(definition, interface_type, &function_type) {
+			let interface = interface_path.node.clone();
+			let error = NodeError::InterfaceSignature(interface, path.identifier.clone());
+			return Err(Diagnostic::new(Spanned::new(error, function_path.span))
+				.note(format!("Interface method declared in: {}", method.span.location(context))));
+		}
+	}
+
+	for (method, _) in &interface.methods {
+		if !function_paths.iter().any(|function_path| {
+			let FunctionPath(path) = function_path.node.as_ref();
+			path.identifier == method.node
+		}) {
+			let interface = interface_path.node.clone();
+			let error = NodeError::MissingInterfaceMethod(interface, method.node.clone());
+			return Err(Diagnostic::new(Spanned::new(error, interface_path.span)));
+		}
+	}
+	Ok(())
+}
+
+/// Checks that a method has the signature of an interface method
+/// with the template: `Self`, substituted by the implementing structure.
+fn conforms(definition: &Definition, interface: &FunctionType, method: &FunctionType) -> bool {
+	let parameters = Iterator::zip(interface.parameters.iter(), method.parameters.iter())
+		.all(|(interface, method)| {
+			let (Parameter(_, interface), Parameter(_, method)) = (&interface.node, &method.node);
+			ascription(definition, interface, method)
+		});
+
+	method.bounds.is_empty() && parameters &&
+		interface.parameters.len() == method.parameters.len() &&
+		ascription(definition, &interface.return_type.node, &method.return_type.node)
+}
+
+fn ascription(definition: &Definition, interface: &AscriptionPattern, method: &AscriptionPattern) -> bool {
+	let patterns = |interface: &[AscriptionPattern], method: &[AscriptionPattern]|
+		interface.len() == method.len() && Iterator::zip(interface.iter(), method.iter())
+			.all(|(interface, method)| ascription(definition, interface, method));
+	match (interface, method) {
+		(Pattern::Wildcard, Pattern::Wildcard) => true,
+		(Pattern::Tuple(interface), Pattern::Tuple(method)) => patterns(interface, method),
+		(Pattern::Terminal(interface), Pattern::Terminal(method)) =>
+			match (&interface.node, &method.node) {
+				(Ascription::Template(template), Ascription::Structure(structure, templates))
+				if template.as_ref() == "Self" => structure == &definition.structure.node &&
+					templates.len() == definition.templates.len() &&
+					Iterator::zip(templates.iter(), definition.templates.iter())
+						.all(|(template, definition)| match template {
+							Pattern::Terminal(template) => match &template.node {
+								Ascription::Template(template) => template == &definition.node,
+								_ => false,
+							},
+							_ => false,
+						}),
+				(Ascription::Template(interface), Ascription::Template(method)) => interface == method,
+				(Ascription::Structure(interface, interface_templates),
+					Ascription::Structure(method, method_templates)) =>
+					interface == method && patterns(interface_templates, method_templates),
+				(Ascription::Reference(interface_permission, _, interface),
+					Ascription::Reference(method_permission, _, method)) =>
+					interface_permission == method_permission && ascription(definition, interface, method),
+				(Ascription::Array(interface, interface_length),
+					Ascription::Array(method, method_length)) =>
+					interface_length == method_length && ascription(definition, interface, method),
+				_ => false,
+			}
+		_ => false,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::error::CompileError;
+	use crate::source::context;
+	use crate::span::Span;
+
+	use super::*;
+
+	const SHAPE: &str = "interface Shape:\n\tfn area(&self) -> u32\n\n\
+		data Square:\n\tside: u32,\n\n";
+
+	fn path(identifier: &str) -> DeclarationPath {
+		DeclarationPath { module_path: ModulePath::root(), identifier: identifier.into() }
+	}
+
+	fn error(source: &str) -> Option<NodeError> {
+		let context = &context(source);
+		let mut errors = context.errors.write();
+		errors.pop().map(|diagnostic| match diagnostic.error.node {
+			CompileError::Node(error) => error,
+			other => panic!("Expected node error, instead got: {:?}", other),
+		})
+	}
+
+	#[test]
+	fn test_implementation() {
+		let context = &context(&format!("{}define Shape for Square:\n\t\
+			fn area(&self) -> u32:\n\t\tself.side * self.side\n", SHAPE));
+		assert!(context.errors.read().is_empty());
+
+		let interface_path = Spanned::new(Arc::new(InterfacePath(path("Shape"))), Span::INTERNAL);
+		let interface = crate::node::interface(context, &interface_path).unwrap();
+		assert!(interface.method("area").is_some());
+
+		let key = (StructurePath(path("Square")), InterfacePath(path("Shape")));
+		assert!(context.node_implementations.contains_key(&key));
+	}
+
+	#[test]
+	fn test_conformance() {
+		let shape = InterfacePath(path("Shape"));
+		assert_eq!(error(&format!("{}define Shape for Square:\n\t\
+			fn side(&self) -> u32:\n\t\tself.side\n", SHAPE)),
+			Some(NodeError::UndefinedInterfaceMethod(shape.clone(), "side".into())));
+		assert_eq!(error(&format!("{}define Shape for Square:\n\t\
+			fn area(&self) -> u64:\n\t\t0\n", SHAPE)),
+			Some(NodeError::InterfaceSignature(shape.clone(), "area".into())));
+		assert_eq!(error(&format!("{}define Shape for Square:\n\t\
+			fn area(&self, scale: u32) -> u32:\n\t\tscale\n", SHAPE)),
+			Some(NodeError::InterfaceSignature(shape.clone(), "area".into())));
+		assert_eq!(error(&format!("{}interface Sized:\n\tfn width(&self) -> u32\n\t\
+			fn height(&self) -> u32\n\ndefine Sized for Square:\n\tfn width(&self) -> u32:\n\t\tself.side\n", SHAPE)),
+			Some(NodeError::MissingInterfaceMethod(InterfacePath(path("Sized")), "height".into())));
+	}
+}
@@ -4,9 +4,10 @@ use std::sync::Arc;
 
 use crate::basic::Item;
 use crate::declaration::{FunctionPath, StructurePath};
+use crate::intrinsic::Intrinsic;
 use crate::span::{Span, Spanned};
 
-use super::{AscriptionPattern, BindingPattern, ExpressionPattern,
+use super::{AscriptionPattern, BindingPattern, BindingVariable, ExpressionPattern,
 	Permission, Variable, VariablePattern};
 
 pub type ConditionStart = ExpressionKey;
@@ -33,17 +34,29 @@ pub enum Expression {
 	Unary(Spanned<UnaryOperator>, ExpressionKey),
 	Binary(Spanned<BinaryOperator>, ExpressionKey, ExpressionKey),
 	Conditional(Vec<Branch>),
+	Match(ExpressionKey, Vec<Arm>),
 	Field(ExpressionKey, Spanned<Arc<str>>),
+	Index(ExpressionKey, ExpressionKey),
 	MethodCall(ExpressionKey, Spanned<Arc<str>>, Vec<ExpressionKey>),
 	FunctionCall(Spanned<FunctionPath>, Vec<ExpressionKey>, Execution),
 	Structure(Spanned<StructurePath>, HashMap<Arc<str>, (Span, ExpressionKey)>),
+	Variant(Spanned<StructurePath>, Spanned<Arc<str>>, HashMap<Arc<str>, (Span, ExpressionKey)>),
+	Array(Vec<ExpressionKey>),
 	Pattern(ExpressionPattern),
 	Variable(Variable),
-	Integer(i128),
+	Integer(u128, Option<Intrinsic>),
 	Truth(bool),
 	Item(Item),
 }
 
+#[derive(Debug, Clone)]
+pub struct Arm {
+	pub variant: Spanned<Arc<str>>,
+	/// Variables bound to the variant fields of the same identifier.
+	pub bindings: Vec<Spanned<BindingVariable>>,
+	pub expression: ExpressionKey,
+}
+
 #[derive(Debug, Clone)]
 pub enum Execution {
 	Compile,
@@ -53,6 +66,7 @@ pub enum Execution {
 #[derive(Debug, Clone)]
 pub enum UnaryOperator {
 	Negate,
+	Not,
 	Dereference,
 	Reference(Permission),
 }
@@ -61,6 +75,7 @@ impl fmt::Display for UnaryOperator {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		match self {
 			UnaryOperator::Negate => write!(f, "-"),
+			UnaryOperator::Not => write!(f, "not "),
 			UnaryOperator::Dereference => write!(f, "*"),
 			UnaryOperator::Reference(permission) => write!(f, "{}", permission),
 		}
@@ -72,6 +87,13 @@ pub enum Arithmetic {
 	Add,
 	Minus,
 	Multiply,
+	Divide,
+	Modulo,
+	BitAnd,
+	BitOr,
+	BitXor,
+	ShiftLeft,
+	ShiftRight,
 }
 
 impl fmt::Display for Arithmetic {
@@ -80,11 +102,18 @@ impl fmt::Display for Arithmetic {
 			Arithmetic::Add => write!(f, "+"),
 			Arithmetic::Minus => write!(f, "-"),
 			Arithmetic::Multiply => write!(f, "*"),
+			Arithmetic::Divide => write!(f, "/"),
+			Arithmetic::Modulo => write!(f, "%"),
+			Arithmetic::BitAnd => write!(f, "&"),
+			Arithmetic::BitOr => write!(f, "|"),
+			Arithmetic::BitXor => write!(f, "^"),
+			Arithmetic::ShiftLeft => write!(f, "<<"),
+			Arithmetic::ShiftRight => write!(f, ">>"),
 		}
 	}
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum BinaryOperator {
 	Arithmetic(Arithmetic),
 	GreaterThan,
@@ -92,6 +121,9 @@ pub enum BinaryOperator {
 	LessThan,
 	LessEqual,
 	Equality,
+	NotEqual,
+	And,
+	Or,
 }
 
 impl fmt::Display for BinaryOperator {
@@ -103,6 +135,9 @@ impl fmt::Display for BinaryOperator {
 			BinaryOperator::LessThan => write!(f, "<"),
 			BinaryOperator::LessEqual => write!(f, "<="),
 			BinaryOperator::Equality => write!(f, "=="),
+			BinaryOperator::NotEqual => write!(f, "!="),
+			BinaryOperator::And => write!(f, "and"),
+			BinaryOperator::Or => write!(f, "or"),
 		}
 	}
 }
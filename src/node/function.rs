@@ -1,33 +1,45 @@
+use std::collections::HashMap;
 use std::ops::{Index, IndexMut};
 use std::sync::Arc;
 
 use chashmap::CHashMap;
 
-use crate::declaration::FunctionPath;
+use crate::declaration::{FunctionPath, InterfacePath};
 use crate::span::{Span, Spanned};
 
-use super::{AscriptionPattern, BindingPattern, Expression, ExpressionKey};
+use super::{AscriptionPattern, BindingPattern, Expression, ExpressionKey, NodeError};
 
 pub type FunctionTypes = CHashMap<Arc<FunctionPath>, Arc<FunctionType>>;
 pub type NodeFunctions = CHashMap<Arc<FunctionPath>, Arc<NodeFunction>>;
 
 #[derive(Debug, Clone)]
 pub struct FunctionType {
+	pub bounds: Vec<Bound>,
 	pub parameters: Vec<Spanned<Parameter>>,
 	pub return_type: Spanned<AscriptionPattern>,
 	pub function_byte_offset: usize,
 }
 
 impl FunctionType {
-	pub fn new(parameters: Vec<Spanned<Parameter>>, return_type: Spanned<AscriptionPattern>,
-	           function_byte_offset: usize) -> Self {
-		FunctionType { parameters, return_type, function_byte_offset }
+	pub fn new(bounds: Vec<Bound>, parameters: Vec<Spanned<Parameter>>,
+	           return_type: Spanned<AscriptionPattern>, function_byte_offset: usize) -> Self {
+		FunctionType { bounds, parameters, return_type, function_byte_offset }
+	}
+
+	/// Provides the interfaces that a template must implement.
+	pub fn bounds<'a>(&'a self, template: &'a str) -> impl Iterator<Item=&'a Spanned<InterfacePath>> {
+		self.bounds.iter().filter(move |Bound(bound, _)| bound.node.as_ref() == template)
+			.flat_map(|Bound(_, interfaces)| interfaces.iter())
 	}
 }
 
 #[derive(Debug, Clone)]
 pub struct Parameter(pub BindingPattern, pub AscriptionPattern);
 
+/// A template with the interfaces it is required to implement.
+#[derive(Debug, Clone)]
+pub struct Bound(pub Spanned<Arc<str>>, pub Vec<Spanned<InterfacePath>>);
+
 #[derive(Debug, Clone)]
 pub struct NodeFunction {
 	pub context: FunctionContext,
@@ -46,11 +58,13 @@ impl NodeFunction {
 pub struct FunctionContext {
 	pub function_path: Arc<FunctionPath>,
 	pub expressions: Vec<Spanned<Expression>>,
+	/// Method receivers that cannot be borrowed uniquely.
+	pub receivers: HashMap<ExpressionKey, Spanned<NodeError>>,
 }
 
 impl FunctionContext {
 	pub fn new(function_path: Arc<FunctionPath>) -> Self {
-		FunctionContext { function_path, expressions: Vec::new() }
+		FunctionContext { function_path, expressions: Vec::new(), receivers: HashMap::new() }
 	}
 }
 
@@ -95,6 +109,11 @@ impl FunctionContext {
 								context.traverse(condition_end, function)).transpose()?;
 							context.traverse(expression, function)
 						}),
+					Expression::Match(expression, arms) => {
+						context.traverse(expression, function)?;
+						arms.iter().try_for_each(|arm|
+							context.traverse(&arm.expression, function))
+					}
 					Expression::Mutation(_, mutable, expression) => {
 						context.traverse(mutable, function)?;
 						context.traverse(expression, function)
@@ -103,6 +122,10 @@ impl FunctionContext {
 						context.traverse(expression, function),
 					Expression::Field(expression, _) =>
 						context.traverse(expression, function),
+					Expression::Index(expression, index) => {
+						context.traverse(expression, function)?;
+						context.traverse(index, function)
+					}
 					Expression::MethodCall(expression, _, arguments) => {
 						context.traverse(expression, function)?;
 						arguments.iter().try_for_each(|expression|
@@ -116,11 +139,14 @@ impl FunctionContext {
 						context.traverse(left, function)?;
 						context.traverse(right, function)
 					}
-					Expression::Structure(_, expressions) => expressions.values()
+					Expression::Structure(_, expressions) |
+					Expression::Variant(_, _, expressions) => expressions.values()
 						.try_for_each(|(_, expression)| context.traverse(expression, function)),
+					Expression::Array(elements) => elements.iter()
+						.try_for_each(|expression| context.traverse(expression, function)),
 					Expression::Pattern(pattern) => pattern.apply(&mut |terminal|
 						context.traverse(terminal, function)),
-					Expression::Variable(_) | Expression::Integer(_) |
+					Expression::Variable(_) | Expression::Integer(_, _) |
 					Expression::Truth(_) | Expression::Item(_) => Ok(()),
 				}?;
 			}
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use chashmap::CHashMap;
+
+use crate::context::Context;
+use crate::declaration::{InterfacePath, StructurePath};
+use crate::error::Diagnostic;
+use crate::span::Spanned;
+
+use super::{Definition, FunctionType};
+
+pub type NodeInterfaces = CHashMap<Arc<InterfacePath>, Arc<Interface>>;
+pub type NodeImplementations = CHashMap<(StructurePath, InterfacePath), Arc<Definition>>;
+
+#[derive(Debug)]
+pub struct Interface {
+	pub methods: Vec<(Spanned<Arc<str>>, Arc<FunctionType>)>,
+}
+
+impl Interface {
+	pub fn method(&self, identifier: &str) -> Option<&Arc<FunctionType>> {
+		self.methods.iter().find(|(method, _)| method.node.as_ref() == identifier)
+			.map(|(_, function_type)| function_type)
+	}
+}
+
+/// Finds the signature of a method on a template through the bounds of a function.
+pub fn template_method(context: &Context, function_type: &FunctionType, template: &str,
+                       method: &str) -> Result<Option<Arc<FunctionType>>, Diagnostic> {
+	for interface in function_type.bounds(template) {
+		let interface = crate::node::interface(context, &interface.clone().map(Arc::new))?;
+		if let Some(function_type) = interface.method(method) {
+			return Ok(Some(function_type.clone()));
+		}
+	}
+	Ok(None)
+}
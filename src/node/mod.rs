@@ -1,11 +1,15 @@
-pub use context::{function, function_type, structure};
-pub use definition::{Definition, NodeDefinitions, load_definitions};
-pub use expression::{Arithmetic, BinaryOperator, Branch, ConditionEnd,
+pub use context::{function, function_type, interface, structure};
+pub use definition::{Definition, NodeDefinitions, load_definitions, reload_definitions,
+	reload_implementations};
+pub use expression::{Arithmetic, Arm, BinaryOperator, Branch, ConditionEnd,
 	ConditionStart, Execution, Expression, ExpressionKey, MutationKind, UnaryOperator};
-pub use function::{FunctionContext, FunctionType, FunctionTypes, NodeFunction, NodeFunctions,
-	Parameter};
+pub use function::{Bound, FunctionContext, FunctionType, FunctionTypes, NodeFunction,
+	NodeFunctions, Parameter};
+pub use interface::{Interface, NodeImplementations, NodeInterfaces, template_method};
+pub use mutability::{mutation, references};
 pub use node::NodeError;
-pub use structure::{NodeStructures, Structure};
+pub use resolution::{resolve_structure_path, resolve_variant, visible_method};
+pub use structure::{NodeStructures, Structure, Variant};
 pub use variable::{Ascription, AscriptionPattern, BindingPattern, BindingVariable,
 	ExpressionPattern, Mutability, Pattern, Permission, Variable, VariablePattern};
 
@@ -16,5 +20,7 @@ mod context;
 mod structure;
 mod resolution;
 mod shadow;
+mod mutability;
 mod definition;
+mod interface;
 mod node;
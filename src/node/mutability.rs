@@ -0,0 +1,359 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::context::Context;
+use crate::declaration::FunctionPath;
+use crate::error::Diagnostic;
+use crate::inference::{TypeContext, TypeResolution};
+use crate::span::{Span, Spanned};
+
+use super::{Ascription, AscriptionPattern, BindingPattern, BindingVariable, Expression,
+	ExpressionKey, FunctionContext, MutationKind, Mutability, NodeError, NodeFunction,
+	Parameter, Pattern, Permission, UnaryOperator, Variable};
+
+/// Bindings are references only if they are ascribed or bound to a reference.
+#[derive(Debug, Default)]
+struct MutabilityContext {
+	variables: HashMap<Variable, (Mutability, Option<Permission>)>,
+}
+
+impl MutabilityContext {
+	fn register(&mut self, pattern: &BindingPattern, ascription: Option<&AscriptionPattern>,
+	            value: Option<Permission>) {
+		match (pattern, ascription) {
+			(Pattern::Tuple(patterns), Some(Pattern::Tuple(ascriptions))) =>
+				patterns.iter().zip(ascriptions).for_each(|(pattern, ascription)|
+					self.register(pattern, Some(ascription), None)),
+			(Pattern::Terminal(terminal), ascription) => {
+				let BindingVariable(variable, mutability) = &terminal.node;
+				let permission = match ascription {
+					Some(Pattern::Terminal(ascription)) => match &ascription.node {
+						Ascription::Reference(permission, _, _) => Some(*permission),
+						_ => None,
+					},
+					_ => value,
+				};
+				self.variables.insert(variable.clone(), (*mutability, permission));
+			}
+			_ => pattern.traverse(&mut |terminal| {
+				let BindingVariable(variable, mutability) = &terminal.node;
+				self.variables.insert(variable.clone(), (*mutability, None));
+			}),
+		}
+	}
+
+	/// Provides the permission of the reference an expression evaluates to if it is known.
+	fn permission(&self, function: &FunctionContext, expression: &ExpressionKey) -> Option<Permission> {
+		match &function[expression].node {
+			Expression::Unary(operator, _) => match operator.node {
+				UnaryOperator::Reference(permission) => Some(permission),
+				_ => None,
+			},
+			Expression::Variable(variable) => self.variables.get(variable)
+				.and_then(|(_, permission)| *permission),
+			_ => None,
+		}
+	}
+
+	/// Checks that the location an expression refers to can be mutated.
+	fn mutable(&self, function: &FunctionContext, expression: &ExpressionKey) -> Result<(), Spanned<NodeError>> {
+		match &function[expression].node {
+			Expression::Variable(variable) => self.binding(variable, function[expression].span),
+			Expression::Field(_, _) | Expression::Index(_, _) | Expression::Unary(_, _) =>
+				self.projection(function, expression),
+			Expression::Pattern(pattern) => {
+				let mut elements = Vec::new();
+				pattern.traverse(&mut |element| elements.push(*element));
+				elements.iter().try_for_each(|element| self.mutable(function, element))
+			}
+			_ => Ok(()),
+		}
+	}
+
+	/// Checks that the location at the end of projections and dereferences can be mutated.
+	/// Locations reached through a reference are checked against its permission.
+	fn projection(&self, function: &FunctionContext, expression: &ExpressionKey) -> Result<(), Spanned<NodeError>> {
+		let span = function[expression].span;
+		let mut is_dereference = false;
+		let mut expression = expression;
+		loop {
+			expression = match &function[expression].node {
+				Expression::Field(inner, _) | Expression::Index(inner, _) => inner,
+				Expression::Pattern(Pattern::Terminal(inner)) => inner,
+				Expression::Unary(operator, inner) => match operator.node {
+					UnaryOperator::Dereference => {
+						is_dereference = true;
+						inner
+					}
+					_ => return Ok(()),
+				},
+				_ => break,
+			};
+		}
+
+		match &function[expression].node {
+			Expression::Variable(variable) => match self.variables.get(variable) {
+				Some((_, Some(Permission::Shared))) => self.shared(variable, span),
+				Some((_, Some(Permission::Unique))) => Ok(()),
+				// Dereferenced bindings are checked against their types after inference.
+				_ if is_dereference => Ok(()),
+				_ => self.binding(variable, span),
+			},
+			_ => self.mutable(function, expression),
+		}
+	}
+
+	fn binding(&self, variable: &Variable, span: Span) -> Result<(), Spanned<NodeError>> {
+		match self.variables.get(variable) {
+			Some((Mutability::Immutable, _)) => {
+				let Variable(identifier, _) = variable;
+				Err(Spanned::new(NodeError::ImmutableBinding(identifier.clone()), span))
+			}
+			_ => Ok(()),
+		}
+	}
+
+	fn shared(&self, variable: &Variable, span: Span) -> Result<(), Spanned<NodeError>> {
+		let Variable(identifier, _) = variable;
+		Err(Spanned::new(NodeError::SharedMutation(identifier.clone()), span))
+	}
+}
+
+/// Constructs the diagnostic for a location that cannot be mutated.
+pub fn mutation(error: Spanned<NodeError>) -> Diagnostic {
+	let note = match &error.node {
+		NodeError::ImmutableBinding(identifier) =>
+			format!("Declare the binding as mutable with: ~{}", identifier),
+		_ => "Declare the reference as unique with: ~&".to_owned(),
+	};
+	Diagnostic::new(error).note(note)
+}
+
+pub fn mutability_function(function: &mut NodeFunction) -> Result<(), Diagnostic> {
+	let context = &mut MutabilityContext::default();
+	for parameter in &function.function_type.parameters {
+		let Parameter(pattern, ascription) = &parameter.node;
+		context.register(pattern, Some(ascription), None);
+	}
+
+	function.context.traverse(&function.expression, &mut |function, expression| {
+		match &expression.node {
+			Expression::Binding(pattern, ascription, value) => {
+				let permission = context.permission(function, value);
+				context.register(&pattern.node, ascription.as_ref(), permission);
+			}
+			Expression::Mutation(kind, left, right) => {
+				context.mutable(function, left).map_err(mutation)?;
+				if kind.node == MutationKind::Swap {
+					context.mutable(function, right).map_err(mutation)?;
+				}
+			}
+			Expression::MethodCall(receiver, _, _) => {
+				// Whether the receiver is borrowed uniquely is only known after inference.
+				if let Err(error) = context.projection(function, receiver) {
+					function.receivers.insert(*receiver, error);
+				}
+			}
+			Expression::Match(value, arms) => for arm in arms {
+				for binding in &arm.bindings {
+					let BindingVariable(variable, mutability) = &binding.node;
+					context.variables.insert(variable.clone(), (*mutability, None));
+					if let Mutability::Mutable = mutability {
+						context.mutable(function, value).map_err(mutation)?;
+					}
+				}
+			},
+			Expression::ExplicitDrop(pattern, _) => {
+				let mut variables = Vec::new();
+				pattern.traverse(&mut |variable| variables.push(variable.clone()));
+				variables.iter().try_for_each(|variable|
+					context.binding(&variable.node, variable.span)).map_err(mutation)?;
+			}
+			_ => (),
+		}
+		Ok(false)
+	})
+}
+
+/// Checks that every reference that is dereferenced to reach a mutated
+/// location is unique. Fields and indices dereference their receivers.
+pub fn references(context: &Context, function: &FunctionContext,
+                  types: &TypeContext) -> Result<(), Diagnostic> {
+	for expression in &function.expressions {
+		match &expression.node {
+			Expression::Mutation(kind, left, right) => {
+				dereferences(function, types, left).map_err(mutation)?;
+				if kind.node == MutationKind::Swap {
+					dereferences(function, types, right).map_err(mutation)?;
+				}
+			}
+			Expression::Match(value, arms) => {
+				let is_mutable = arms.iter().flat_map(|arm| arm.bindings.iter())
+					.any(|binding| match binding.node {
+						BindingVariable(_, Mutability::Mutable) => true,
+						_ => false,
+					});
+				if is_mutable {
+					dereferences(function, types, value).map_err(mutation)?;
+				}
+			}
+			Expression::MethodCall(receiver, method, _) => {
+				let mut resolution = &types[receiver];
+				while let TypeResolution::Reference(_, inner) = resolution {
+					resolution = inner;
+				}
+
+				if let TypeResolution::Instance(structure, _) = resolution {
+					let path = method.clone().map(|method|
+						Arc::new(FunctionPath::method(structure.clone(), method)));
+					let function_type = super::function_type(context, &path)?;
+					let Parameter(_, ascription) = &function_type.parameters[0].node;
+					if let Pattern::Terminal(ascription) = ascription {
+						if let Ascription::Reference(Permission::Unique, _, _) = ascription.node {
+							unique(function, types, receiver, false)
+								.and_then(|_| dereferences(function, types, receiver))
+								.map_err(mutation)?;
+						}
+					}
+				}
+			}
+			_ => (),
+		}
+	}
+	Ok(())
+}
+
+/// Checks the references dereferenced by projections to reach a location.
+fn dereferences(function: &FunctionContext, types: &TypeContext,
+                expression: &ExpressionKey) -> Result<(), Spanned<NodeError>> {
+	match &function[expression].node {
+		Expression::Field(inner, _) | Expression::Index(inner, _) =>
+			unique(function, types, inner, false).and_then(|_| dereferences(function, types, inner)),
+		Expression::Unary(operator, inner) => match operator.node {
+			UnaryOperator::Dereference => unique(function, types, inner, true)
+				.and_then(|_| dereferences(function, types, inner)),
+			_ => Ok(()),
+		},
+		Expression::Pattern(pattern) => {
+			let mut elements = Vec::new();
+			pattern.traverse(&mut |element| elements.push(*element));
+			elements.iter().try_for_each(|element| dereferences(function, types, element))
+		}
+		_ => Ok(()),
+	}
+}
+
+/// Checks that the references of an expression are unique.
+/// An explicit dereference only removes the outermost reference.
+fn unique(function: &FunctionContext, types: &TypeContext, expression: &ExpressionKey,
+          is_explicit: bool) -> Result<(), Spanned<NodeError>> {
+	let mut resolution = &types[expression];
+	while let TypeResolution::Reference(permission, reference) = resolution {
+		if *permission == Permission::Shared {
+			let error = match &function[expression].node {
+				Expression::Variable(Variable(identifier, _)) =>
+					NodeError::SharedMutation(identifier.clone()),
+				_ => NodeError::SharedReference,
+			};
+			return Err(Spanned::new(error, function[expression].span));
+		}
+
+		match is_explicit {
+			true => break,
+			false => resolution = reference,
+		}
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::error::CompileError;
+	use crate::source::{context, function_path};
+
+	use super::*;
+
+	const COUNTER: &str = "data Counter:\n\tvalue: u32,\n\n\
+		define Counter:\n\tfn increment(~&self):\n\t\tself.value += 1\n\n";
+
+	fn error(result: Result<impl std::fmt::Debug, Diagnostic>) -> NodeError {
+		match result.unwrap_err().error.node {
+			CompileError::Node(error) => error,
+			other => panic!("Expected node error, instead got: {:?}", other),
+		}
+	}
+
+	#[test]
+	fn test_shared_dereference() {
+		let context = &context("data Holder:\n\ttarget: ~&u32,\n\n\
+			fn bump(holder: &Holder):\n\t*holder.target += 1\n");
+		let error = error(crate::node::function(context, &function_path("bump")));
+		match error {
+			NodeError::SharedMutation(_) => (),
+			other => panic!("Unexpected error: {:?}", other),
+		}
+	}
+
+	#[test]
+	fn test_unique_dereference() {
+		let context = &context("data Holder:\n\ttarget: ~&u32,\n\n\
+			fn bump(holder: ~&Holder):\n\t*holder.target += 1\n");
+		assert!(crate::node::function(context, &function_path("bump")).is_ok());
+	}
+
+	#[test]
+	fn test_immutable_receiver() {
+		let source = format!("{}fn main():\n\tlet counter = Counter: value: 1\n\
+			\tcounter.increment()\n", COUNTER);
+		let error = error(crate::inference::function(&context(&source), &function_path("main")));
+		match error {
+			NodeError::ImmutableBinding(_) => (),
+			other => panic!("Unexpected error: {:?}", other),
+		}
+	}
+
+	#[test]
+	fn test_shared_receiver() {
+		let source = format!("{}fn main(counter: &Counter):\n\tcounter.increment()\n", COUNTER);
+		let error = error(crate::inference::function(&context(&source), &function_path("main")));
+		match error {
+			NodeError::SharedMutation(_) => (),
+			other => panic!("Unexpected error: {:?}", other),
+		}
+	}
+
+	#[test]
+	fn test_mutable_receiver() {
+		let source = format!("{}fn main():\n\tlet ~counter = Counter: value: 1\n\
+			\tcounter.increment()\n\tlet reference = ~&counter\n\treference.increment()\n", COUNTER);
+		assert!(crate::inference::function(&context(&source), &function_path("main")).is_ok());
+	}
+
+	#[test]
+	fn test_shared_field() {
+		let holder = "data Holder:\n\ttarget: &u32,\n\n";
+		let source = format!("{}fn f(holder: ~&Holder):\n\t*holder.target += 1\n", holder);
+		let error = error(crate::inference::function(&context(&source), &function_path("f")));
+		assert_eq!(error, NodeError::SharedReference);
+
+		let source = format!("{}fn f(holder: ~&Holder) -> u32:\n\t*holder.target\n", holder);
+		assert!(crate::inference::function(&context(&source), &function_path("f")).is_ok());
+	}
+
+	#[test]
+	fn test_shared_field_projection() {
+		let boxed = format!("{}data Boxed:\n\tcounter: &Counter,\n\n", COUNTER);
+		let source = format!("{}fn f(boxed: ~&Boxed):\n\tboxed.counter.value += 1\n", boxed);
+		let result = crate::inference::function(&context(&source), &function_path("f"));
+		assert_eq!(error(result), NodeError::SharedReference);
+
+		let source = format!("{}data Inner:\n\tcounter: Counter,\n\ndata Outer:\n\tinner: &Inner,\n\n\
+			fn f(outer: ~&Outer):\n\touter.inner.counter.increment()\n", COUNTER);
+		let result = crate::inference::function(&context(&source), &function_path("f"));
+		assert_eq!(error(result), NodeError::SharedReference);
+
+		let source = format!("{}data Unique:\n\tcounter: ~&Counter,\n\n\
+			fn f(unique: ~&Unique):\n\tunique.counter.value += 1\n\tunique.counter.increment()\n", COUNTER);
+		assert!(crate::inference::function(&context(&source), &function_path("f")).is_ok());
+	}
+}
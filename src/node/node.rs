@@ -1,10 +1,10 @@
 use std::fmt;
 use std::sync::Arc;
 
-use crate::declaration::{DeclarationPath, StructurePath};
+use crate::declaration::{DeclarationPath, InterfacePath, ModulePath, StructurePath};
 use crate::error::CompileError;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum NodeError {
 	DroppedVariable(Arc<str>),
 	UndefinedVariable(Arc<str>),
@@ -12,6 +12,15 @@ pub enum NodeError {
 	ResolutionConflict(DeclarationPath),
 	UnresolvedResolution(DeclarationPath),
 	DuplicateMethod(StructurePath, Arc<str>),
+	DuplicateImplementation(InterfacePath, StructurePath),
+	UndefinedInterfaceMethod(InterfacePath, Arc<str>),
+	MissingInterfaceMethod(InterfacePath, Arc<str>),
+	InterfaceSignature(InterfacePath, Arc<str>),
+	PrivateItem(DeclarationPath),
+	PrivateModule(Arc<ModulePath>),
+	ImmutableBinding(Arc<str>),
+	SharedMutation(Arc<str>),
+	SharedReference,
 }
 
 impl fmt::Display for NodeError {
@@ -29,6 +38,24 @@ impl fmt::Display for NodeError {
 				write!(f, "Item: {}, has no matching resolutions", item),
 			NodeError::DuplicateMethod(structure, identifier) =>
 				write!(f, "Method: {}, is already defined for structure: {}", identifier, structure),
+			NodeError::DuplicateImplementation(interface, structure) =>
+				write!(f, "Interface: {}, is already implemented for structure: {}", interface, structure),
+			NodeError::UndefinedInterfaceMethod(interface, identifier) =>
+				write!(f, "Method: {}, is not declared in interface: {}", identifier, interface),
+			NodeError::MissingInterfaceMethod(interface, identifier) =>
+				write!(f, "Method: {}, of interface: {}, is not implemented", identifier, interface),
+			NodeError::InterfaceSignature(interface, identifier) =>
+				write!(f, "Method: {}, does not match the signature declared in interface: {}", identifier, interface),
+			NodeError::PrivateItem(item) =>
+				write!(f, "Item: {}, is not exported", item),
+			NodeError::PrivateModule(module) =>
+				write!(f, "Module: {}, is not exported", module),
+			NodeError::ImmutableBinding(variable) =>
+				write!(f, "Variable: {}, is not mutable", variable),
+			NodeError::SharedMutation(variable) =>
+				write!(f, "Variable: {}, is a shared reference and cannot be mutated through", variable),
+			NodeError::SharedReference =>
+				write!(f, "Shared reference cannot be mutated through"),
 		}
 	}
 }
@@ -1,17 +1,24 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::context::Context;
-use crate::declaration::{DeclarationPath, FunctionPath, InclusionTerminal,
-	ModuleContext, ModulePath, StructurePath};
+use crate::declaration::{Declaration, DeclarationPath, FunctionPath, InclusionTerminal,
+	InterfacePath, ModuleContext, ModulePath, StructurePath};
 use crate::error::Diagnostic;
 use crate::intrinsic::Intrinsic;
 use crate::node::Structure;
 use crate::span::{Span, Spanned};
 
-use super::{Ascription, Expression, FunctionContext, FunctionType, NodeError, Parameter, Pattern};
+use super::{Ascription, Bound, Expression, FunctionContext, FunctionType, NodeError,
+	Parameter, Pattern};
 
 pub fn resolve_function_type(context: &Context, module_context: &ModuleContext,
                              function_type: &mut FunctionType) -> Result<(), Diagnostic> {
+	for Bound(_, interfaces) in &mut function_type.bounds {
+		interfaces.iter_mut().try_for_each(|interface|
+			resolve_interface_path(context, module_context, interface))?;
+	}
+
 	for parameter in &mut function_type.parameters {
 		let Parameter(_, ascriptions) = &mut parameter.node;
 		resolve_ascriptions(context, module_context, ascriptions)?;
@@ -28,8 +35,20 @@ pub fn resolve_function(context: &Context, module_context: &ModuleContext,
 				resolve_ascriptions(context, module_context, ascriptions)?,
 			Expression::FunctionCall(function_path, _, _) =>
 				resolve_function_path(context, module_context, function_path)?,
-			Expression::Structure(structure_path, _) =>
-				resolve_structure_path(context, module_context, structure_path)?,
+			Expression::Structure(structure_path, fields) =>
+				match resolve_variant(context, module_context, structure_path) {
+					Some(path) => {
+						let StructurePath(declaration_path) = &structure_path.node;
+						let variant = declaration_path.identifier.clone();
+						let variant = Spanned::new(variant, structure_path.span);
+						let fields = std::mem::replace(fields, HashMap::new());
+						expression.node = Expression::Variant(path, variant, fields);
+					}
+					None => resolve_structure_path(context, module_context, structure_path)?,
+				},
+			Expression::Variant(structure_path, _, _) =>
+				resolve_structure_path(context, module_context, structure_path).map_err(|diagnostic|
+					diagnostic.note("In resolving the structure of a variant without fields"))?,
 			_ => (),
 		}
 	}
@@ -38,18 +57,44 @@ pub fn resolve_function(context: &Context, module_context: &ModuleContext,
 
 pub fn resolve_structure(context: &Context, module_context: &ModuleContext,
                          structure: &mut Structure) -> Result<(), Diagnostic> {
-	structure.fields.values_mut().try_for_each(|ascriptions|
+	structure.fields.values_mut().chain(structure.variants.iter_mut()
+		.flat_map(|variant| variant.fields.values_mut())).try_for_each(|ascriptions|
 		resolve_ascriptions(context, module_context, ascriptions))
 }
 
+/// Resolves the structure of a literal path that names a variant of a sum type.
+pub fn resolve_variant(context: &Context, module_context: &ModuleContext,
+                       structure_path: &Spanned<StructurePath>) -> Option<Spanned<StructurePath>> {
+	let StructurePath(declaration_path) = &structure_path.node;
+	let module_path = &declaration_path.module_path;
+	let identifier = module_path.identifier.clone();
+	let module_path = module_path.parent.clone()?;
+
+	let path = StructurePath(DeclarationPath { module_path, identifier });
+	let mut path = Spanned::new(path, structure_path.span);
+	resolve_structure_path(context, module_context, &mut path).ok()?;
+	context.declarations_structure.get(&path.node)?;
+	Some(path)
+}
+
 pub fn resolve_structure_path(context: &Context, module_context: &ModuleContext,
                               structure_path: &mut Spanned<StructurePath>) -> Result<(), Diagnostic> {
 	let StructurePath(declaration_path) = &mut structure_path.node;
 	if !declaration_path.module_path.any_unresolved() { return Ok(()); }
 
 	let structures = &context.declarations_structure;
-	resolve_declaration(module_context, declaration_path, structure_path.span,
-		&mut |candidate| structures.contains_key(&StructurePath(candidate)))
+	resolve_declaration(context, module_context, declaration_path, structure_path.span,
+		&mut |candidate| structures.get(&StructurePath(candidate)).map(|declaration| declaration.clone()))
+}
+
+pub fn resolve_interface_path(context: &Context, module_context: &ModuleContext,
+                              interface_path: &mut Spanned<InterfacePath>) -> Result<(), Diagnostic> {
+	let InterfacePath(declaration_path) = &mut interface_path.node;
+	if !declaration_path.module_path.any_unresolved() { return Ok(()); }
+
+	let interfaces = &context.declarations_interface;
+	resolve_declaration(context, module_context, declaration_path, interface_path.span,
+		&mut |candidate| interfaces.get(&InterfacePath(candidate)).map(|declaration| declaration.clone()))
 }
 
 fn resolve_ascriptions(context: &Context, module_context: &ModuleContext,
@@ -65,7 +110,7 @@ fn resolve_ascription(context: &Context, module_context: &ModuleContext,
 				resolve_ascriptions(context, module_context, template))?;
 			declaration_path
 		}
-		Ascription::Reference(_, _, ascription) =>
+		Ascription::Reference(_, _, ascription) | Ascription::Array(ascription, _) =>
 			return resolve_ascriptions(context, module_context, ascription),
 		_ => return Ok(()),
 	};
@@ -78,8 +123,8 @@ fn resolve_ascription(context: &Context, module_context: &ModuleContext,
 	}
 
 	let structures = &context.declarations_structure;
-	resolve_declaration(module_context, declaration_path, ascription.span,
-		&mut |candidate| structures.contains_key(&StructurePath(candidate)))
+	resolve_declaration(context, module_context, declaration_path, ascription.span,
+		&mut |candidate| structures.get(&StructurePath(candidate)).map(|declaration| declaration.clone()))
 }
 
 fn resolve_function_path(context: &Context, module_context: &ModuleContext,
@@ -88,13 +133,14 @@ fn resolve_function_path(context: &Context, module_context: &ModuleContext,
 	if !declaration_path.module_path.any_unresolved() { return Ok(()); }
 
 	let functions = &context.declarations_function;
-	resolve_declaration(module_context, declaration_path, function_path.span,
-		&mut |candidate| functions.contains_key(&FunctionPath(candidate)))
+	resolve_declaration(context, module_context, declaration_path, function_path.span,
+		&mut |candidate| functions.get(&FunctionPath(candidate)).map(|declaration| declaration.clone()))
 }
 
-fn resolve_declaration<F>(module_context: &ModuleContext, declaration_path: &mut DeclarationPath,
-                          span: Span, predicate: &mut F) -> Result<(), Diagnostic>
-	where F: FnMut(DeclarationPath) -> bool {
+fn resolve_declaration<F>(context: &Context, module_context: &ModuleContext,
+                          declaration_path: &mut DeclarationPath, span: Span,
+                          declaration: &mut F) -> Result<(), Diagnostic>
+	where F: FnMut(DeclarationPath) -> Option<Declaration> {
 	if declaration_path.module_path.head().map(|head| head.is_root()).unwrap_or(false) {
 		declaration_path.module_path = declaration_path.module_path.clone().tail();
 	}
@@ -111,7 +157,7 @@ fn resolve_declaration<F>(module_context: &ModuleContext, declaration_path: &mut
 				let mut candidate = comparison_path.clone();
 				candidate.module_path = inclusion.node.module_path.clone()
 					.append(&candidate.module_path);
-				if predicate(candidate.clone()) {
+				if declaration(candidate.clone()).is_some() {
 					resolve(declaration_path, inclusion.node.module_path.clone(), span)?;
 				}
 			}
@@ -119,7 +165,10 @@ fn resolve_declaration<F>(module_context: &ModuleContext, declaration_path: &mut
 	}
 
 	match declaration_path.module_path.any_unresolved() {
-		false => Ok(()),
+		false => match declaration(declaration_path.clone()) {
+			Some(declaration) => visible(context, module_context, declaration_path, &declaration, span),
+			None => Ok(()),
+		},
 		true => {
 			let error = NodeError::UnresolvedResolution(declaration_path.clone());
 			let note = format!("Add an include with 'use module::{}'", declaration_path.head());
@@ -128,6 +177,61 @@ fn resolve_declaration<F>(module_context: &ModuleContext, declaration_path: &mut
 	}
 }
 
+/// Checks that a declaration in another module is exported along with every
+/// module between the declaration and the closest module that contains the usage.
+fn visible(context: &Context, module_context: &ModuleContext, declaration_path: &DeclarationPath,
+           declaration: &Declaration, span: Span) -> Result<(), Diagnostic> {
+	let module_path = &module_context.module_path;
+	if !declaration.is_exported && !module_path.is_within(&declaration_path.module_path) {
+		let error = NodeError::PrivateItem(declaration_path.clone());
+		let location = declaration.span().location(context);
+		return Err(Diagnostic::new(Spanned::new(error, span))
+			.note(format!("Declared in: {}", location)));
+	}
+
+	let mut current = &declaration_path.module_path;
+	while let Some(parent) = &current.parent {
+		if module_path.is_within(parent) {
+			break;
+		}
+
+		let module_contexts = context.module_contexts.read();
+		let module = &module_contexts[current];
+		if !module.is_exported {
+			let error = NodeError::PrivateModule(current.clone());
+			let location = module.inclusions[0].span.location(context);
+			return Err(Diagnostic::new(Spanned::new(error, span))
+				.note(format!("Declared in: {}", location)));
+		}
+		current = parent;
+	}
+	Ok(())
+}
+
+/// Checks that a method that is not exported is only called within the module that
+/// defines it. Methods that implement an interface are visible wherever the structure is.
+pub fn visible_method(context: &Context, function_path: &FunctionPath,
+                      method: &Spanned<Arc<FunctionPath>>) -> Result<(), Diagnostic> {
+	let declaration = context.declarations_function.get(&method.node).map(|declaration| declaration.clone());
+	let (definition, declaration) = match (context.node_definitions.get(&method.node), declaration) {
+		(Some(definition), Some(declaration)) if definition.interface.is_none() =>
+			(definition.clone(), declaration),
+		_ => return Ok(()),
+	};
+
+	let FunctionPath(caller) = function_path;
+	let module_path = context.node_definitions.get(function_path).map(|caller|
+		caller.declaration.clone()).unwrap_or_else(|| caller.module_path.clone());
+	if !declaration.is_exported && !module_path.is_within(&definition.declaration) {
+		let FunctionPath(path) = method.node.as_ref();
+		let error = NodeError::PrivateItem(path.clone());
+		let location = declaration.span().location(context);
+		return Err(Diagnostic::new(Spanned::new(error, method.span))
+			.note(format!("Declared in: {}", location)));
+	}
+	Ok(())
+}
+
 fn resolve(declaration_path: &mut DeclarationPath, candidate: Arc<ModulePath>,
            span: Span) -> Result<(), Diagnostic> {
 	match declaration_path.module_path.any_unresolved() {
@@ -141,3 +245,82 @@ fn resolve(declaration_path: &mut DeclarationPath, candidate: Arc<ModulePath>,
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use crate::error::CompileError;
+	use crate::source::{function_path, TemporaryDirectory};
+
+	use super::*;
+
+	const SHAPES: &str = "module inner\n\n\
+		export data Square:\n\tside: u32,\n\n\
+		export interface Shape:\n\tfn area(&self) -> u32\n\n\
+		define Square:\n\tfn half(&self) -> u32:\n\t\tself.side / 2\n\n\t\
+		export fn double(&self) -> u32:\n\t\tself.side * 2\n\n\t\
+		fn quarter(&self) -> u32:\n\t\tself.half() / 2\n\n\
+		define Shape for Square:\n\tfn area(&self) -> u32:\n\t\tself.side * self.side\n\n\
+		fn secret() -> u32:\n\t1\n\n\
+		export fn square() -> Square:\n\tSquare: side: 4\n\n\
+		export fn half() -> u32:\n\tsquare().half()\n";
+
+	const MAIN: &str = "module shapes\n\nuse crate::shapes::*\n\n\
+		fn secret() -> u32:\n\tcrate::shapes::secret()\n\n\
+		fn inner() -> u32:\n\tcrate::shapes::inner::inner()\n\n\
+		fn half() -> u32:\n\tsquare().half()\n\n\
+		fn double() -> u32:\n\tsquare().double()\n\n\
+		fn area() -> u32:\n\tsquare().area()\n\n\
+		fn exported() -> u32:\n\tcrate::shapes::half()\n";
+
+	fn directory() -> TemporaryDirectory {
+		TemporaryDirectory::new(&[("main.lx", MAIN), ("shapes/module.lx", SHAPES),
+			("shapes/inner.lx", "export fn inner() -> u32:\n\t1\n")])
+	}
+
+	fn error(context: &Context, function: &str) -> NodeError {
+		match crate::inference::function(context, &function_path(function)).unwrap_err().error.node {
+			CompileError::Node(error) => error,
+			other => panic!("Expected node error, instead got: {:?}", other),
+		}
+	}
+
+	#[test]
+	fn test_private_item() {
+		let directory = directory();
+		let context = &directory.context();
+		match error(context, "secret") {
+			NodeError::PrivateItem(path) => assert_eq!(path.to_string(), "crate::shapes::secret"),
+			other => panic!("Unexpected error: {:?}", other),
+		}
+	}
+
+	#[test]
+	fn test_private_module() {
+		let directory = directory();
+		let context = &directory.context();
+		match error(context, "inner") {
+			NodeError::PrivateModule(path) => assert_eq!(path.to_string(), "crate::shapes::inner"),
+			other => panic!("Unexpected error: {:?}", other),
+		}
+	}
+
+	#[test]
+	fn test_private_method() {
+		let directory = directory();
+		let context = &directory.context();
+		match error(context, "half") {
+			NodeError::PrivateItem(path) => assert_eq!(path.to_string(), "crate::shapes::Square::half"),
+			other => panic!("Unexpected error: {:?}", other),
+		}
+
+		for function in &["double", "area", "exported"] {
+			let result = crate::inference::function(context, &function_path(function));
+			assert!(result.is_ok(), "{}: {:?}", function, result.err());
+		}
+
+		let module_path = ModulePath::root().push("shapes".into());
+		let structure = StructurePath(DeclarationPath { module_path, identifier: "Square".into() });
+		let quarter = Arc::new(FunctionPath::method(structure, "quarter".into()));
+		assert!(crate::inference::function(context, &Spanned::new(quarter, Span::INTERNAL)).is_ok());
+	}
+}
@@ -127,6 +127,16 @@ fn shadow(function: &mut FunctionContext, context: &mut ShadowContext,
 				})?;
 				shadow(function, context, expression)?;
 			}
+			Expression::Match(expression, arms) => {
+				shadow(function, context, expression)?;
+				for arm in arms {
+					context.new_frame();
+					arm.bindings.iter_mut().for_each(|binding|
+						context.register_variable(&mut binding.node));
+					shadow(function, context, &arm.expression)?;
+					context.pop_frame();
+				}
+			}
 			Expression::Variable(variable) =>
 				context.resolve_variable(variable, expression.span)?,
 			_ => return Ok(false),
@@ -13,4 +13,25 @@ pub type NodeStructures = CHashMap<Arc<StructurePath>, Arc<Structure>>;
 pub struct Structure {
 	pub templates: Vec<Spanned<Arc<str>>>,
 	pub fields: HashMap<Arc<str>, AscriptionPattern>,
+	/// Empty unless the structure is a sum type.
+	pub variants: Vec<Variant>,
+}
+
+impl Structure {
+	pub fn variant(&self, identifier: &str) -> Option<(usize, &Variant)> {
+		self.variants.iter().enumerate()
+			.find(|(_, variant)| variant.identifier.node.as_ref() == identifier)
+	}
+
+	/// Ascriptions of all fields including those of variants.
+	pub fn ascriptions(&self) -> impl Iterator<Item=&AscriptionPattern> {
+		self.fields.values().chain(self.variants.iter()
+			.flat_map(|variant| variant.fields.values()))
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct Variant {
+	pub identifier: Spanned<Arc<str>>,
+	pub fields: HashMap<Arc<str>, AscriptionPattern>,
 }
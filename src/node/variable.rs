@@ -78,6 +78,7 @@ pub enum Ascription {
 	Structure(StructurePath, Vec<AscriptionPattern>),
 	Reference(Permission, Option<Spanned<Lifetime>>, Box<AscriptionPattern>),
 	Template(Arc<str>),
+	Array(Box<AscriptionPattern>, usize),
 }
 
 impl fmt::Debug for Ascription {
@@ -90,6 +91,7 @@ impl fmt::Display for Ascription {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		match self {
 			Ascription::Template(template) => write!(f, "${}", template),
+			Ascription::Array(ascription, length) => write!(f, "[{}; {}]", ascription, length),
 			Ascription::Reference(permission, lifetime, ascription) => {
 				write!(f, "{}", permission)?;
 				lifetime.iter().try_for_each(|lifetime| write!(f, "{} ", lifetime))?;
@@ -1,8 +1,10 @@
 use crate::error::Diagnostic;
 use crate::lexer::{Lexer, Token};
-use crate::node::{ConditionEnd, ConditionStart, Expression, ExpressionKey, FunctionContext};
+use crate::node::{Arm, ConditionEnd, ConditionStart, Expression, ExpressionKey, FunctionContext};
 use crate::span::Spanned;
 
+use super::ParserError;
+
 pub fn termination_loop(context: &mut FunctionContext, lexer: &mut Lexer) -> Result<ExpressionKey, Diagnostic> {
 	let initial_span = super::expect(lexer, Token::Loop)?;
 	let condition_start = match lexer.peek().node {
@@ -47,6 +49,47 @@ pub fn conditional(context: &mut FunctionContext, lexer: &mut Lexer) -> Result<E
 	Ok(context.register(Spanned::new(conditional, initial_span.merge(span))))
 }
 
+pub fn matching(context: &mut FunctionContext, lexer: &mut Lexer) -> Result<ExpressionKey, Diagnostic> {
+	let initial_span = super::expect(lexer, Token::Match)?;
+	let value = super::condition_value(context, lexer).map_err(|diagnostic|
+		diagnostic.note("In parsing match value"))?;
+
+	let mut arms: Vec<Arm> = Vec::new();
+	super::expect(lexer, Token::Separator)?;
+	super::expect(lexer, Token::BlockOpen)?;
+	while lexer.peek().node != Token::BlockClose {
+		let arm = arm(context, lexer)?;
+		if arms.iter().any(|other| other.variant.node == arm.variant.node) {
+			let error = ParserError::DuplicateArm(arm.variant.node.clone());
+			return Err(Diagnostic::new(Spanned::new(error, arm.variant.span)));
+		}
+
+		arms.push(arm);
+		super::skip(lexer, &Token::LineBreak);
+	}
+
+	let span = initial_span.merge(super::expect(lexer, Token::BlockClose)?);
+	let expression = Expression::Match(value, arms);
+	Ok(context.register(Spanned::new(expression, span)))
+}
+
+fn arm(context: &mut FunctionContext, lexer: &mut Lexer) -> Result<Arm, Diagnostic> {
+	let variant = super::identifier(lexer).map_err(|diagnostic|
+		diagnostic.note("In parsing match arm variant"))?;
+
+	let mut bindings = Vec::new();
+	if lexer.peek().node == Token::ParenthesisOpen {
+		super::list(lexer.consume(), Token::ParenthesisClose, Token::ListSeparator,
+			&mut |lexer| Ok(bindings.push(super::binding_variable(lexer)?)))?;
+		super::expect(lexer, Token::ParenthesisClose)?;
+	}
+
+	super::expect(lexer, Token::Separator).map_err(|diagnostic|
+		diagnostic.note("In parsing a match arm"))?;
+	let expression = super::expression(context, lexer)?;
+	Ok(Arm { variant, bindings, expression })
+}
+
 pub fn branch(context: &mut FunctionContext, lexer: &mut Lexer)
               -> Result<(ConditionStart, Option<ConditionEnd>), Diagnostic> {
 	let condition_start = super::root_value(context, lexer).map_err(|diagnostic|
@@ -62,3 +105,59 @@ pub fn branch(context: &mut FunctionContext, lexer: &mut Lexer)
 		diagnostic.note("In parsing a conditional branch condition"))?;
 	Ok((condition_start, condition_end))
 }
+
+#[cfg(test)]
+mod tests {
+	use crate::error::CompileError;
+	use crate::node::Expression;
+	use crate::parser::ParserError;
+	use crate::source::{context, function_path};
+
+	const SHAPE: &str = "data Shape:\n\t| Circle: radius: u32\n\t| Empty\n\n";
+
+	#[test]
+	fn test_matching() {
+		let context = &context(&format!("{}fn f(shape: Shape) -> u32:\n\tmatch shape:\n\t\t\
+			Circle(radius): radius\n\t\tEmpty: 0\n\nfn g() -> Shape:\n\tShape::Empty\n", SHAPE));
+		let function = crate::parser::function(context, &function_path("f")).unwrap();
+		let (value, arms) = function.context.expressions.iter().find_map(|expression| match &expression.node {
+			Expression::Match(value, arms) => Some((value, arms)),
+			_ => None,
+		}).unwrap();
+
+		match &function.context[value].node {
+			Expression::Variable(_) => (),
+			other => panic!("Expected match value variable, instead got: {:?}", other),
+		}
+
+		let variants: Vec<_> = arms.iter().map(|arm| arm.variant.node.as_ref()).collect();
+		assert_eq!(variants, &["Circle", "Empty"]);
+		assert_eq!(arms[0].bindings.len(), 1);
+
+		let function = crate::parser::function(context, &function_path("g")).unwrap();
+		let variant = function.context.expressions.iter().find_map(|expression| match &expression.node {
+			Expression::Variant(_, variant, fields) if fields.is_empty() => Some(variant.node.clone()),
+			_ => None,
+		});
+		assert_eq!(variant.as_ref().map(AsRef::as_ref), Some("Empty"));
+	}
+
+	#[test]
+	fn test_duplicate_arm() {
+		let context = &context(&format!("{}fn f(shape: Shape) -> u32:\n\tmatch shape:\n\t\t\
+			Empty: 0\n\t\tEmpty: 1\n", SHAPE));
+		match crate::parser::function(context, &function_path("f")).unwrap_err().error.node {
+			CompileError::Parser(ParserError::DuplicateArm(variant)) => assert_eq!(variant.as_ref(), "Empty"),
+			other => panic!("Expected duplicate arm error, instead got: {:?}", other),
+		}
+	}
+
+	#[test]
+	fn test_path_association() {
+		let context = &context("fn f() -> u32:\n\tcrate::g\n\nfn g() -> u32:\n\t0\n");
+		match crate::parser::function(context, &function_path("f")).unwrap_err().error.node {
+			CompileError::Parser(ParserError::ExpectedPathAssociation(_)) => (),
+			other => panic!("Expected path association error, instead got: {:?}", other),
+		}
+	}
+}
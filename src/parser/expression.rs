@@ -18,6 +18,8 @@ pub fn expression(context: &mut FunctionContext, lexer: &mut Lexer) -> Result<Ex
 			.map_err(|diagnostic| diagnostic.note("In parsing a termination loop")),
 		Token::If => super::conditional::conditional(context, lexer)
 			.map_err(|diagnostic| diagnostic.note("In parsing a conditional")),
+		Token::Match => super::conditional::matching(context, lexer)
+			.map_err(|diagnostic| diagnostic.note("In parsing a match")),
 		Token::Drop => explicit_drop(context, lexer)
 			.map_err(|diagnostic| diagnostic.note("In parsing an explicit drop")),
 		_ => expression_terminator(context, lexer),
@@ -66,6 +68,13 @@ fn expression_terminator(context: &mut FunctionContext, lexer: &mut Lexer) -> Re
 		Token::AddAssign => MutationKind::Arithmetic(Arithmetic::Add),
 		Token::MinusAssign => MutationKind::Arithmetic(Arithmetic::Minus),
 		Token::MultiplyAssign => MutationKind::Arithmetic(Arithmetic::Multiply),
+		Token::DivideAssign => MutationKind::Arithmetic(Arithmetic::Divide),
+		Token::ModuloAssign => MutationKind::Arithmetic(Arithmetic::Modulo),
+		Token::BitAndAssign => MutationKind::Arithmetic(Arithmetic::BitAnd),
+		Token::BitOrAssign => MutationKind::Arithmetic(Arithmetic::BitOr),
+		Token::BitXorAssign => MutationKind::Arithmetic(Arithmetic::BitXor),
+		Token::ShiftLeftAssign => MutationKind::Arithmetic(Arithmetic::ShiftLeft),
+		Token::ShiftRightAssign => MutationKind::Arithmetic(Arithmetic::ShiftRight),
 		Token::LineBreak => return Ok(value),
 		_ => return Err(Diagnostic::new(token.map(|token|
 			ParserError::ExpectedExpressionTerminator(token)))),
@@ -137,6 +146,7 @@ pub fn ascription(lexer: &mut Lexer) -> Result<Spanned<Ascription>, Diagnostic>
 			.map(|identifier| Ascription::Template(identifier)),
 		Token::Reference => reference(lexer, Permission::Shared)?,
 		Token::Unique => reference(lexer, Permission::Unique)?,
+		Token::BracketOpen => array(lexer)?,
 		_ => {
 			let path = path(lexer).map(|path| path.map(|path| StructurePath(path)))?;
 			Spanned::new(Ascription::Structure(path.node, match lexer.peek().node {
@@ -161,6 +171,20 @@ fn reference(lexer: &mut Lexer, permission: Permission) -> Result<Spanned<Ascrip
 	Ok(Spanned::new(ascription, initial.merge(pattern.span)))
 }
 
+fn array(lexer: &mut Lexer) -> Result<Spanned<Ascription>, Diagnostic> {
+	let initial = lexer.next().span;
+	let pattern = super::pattern(lexer, &mut ascription)?;
+	super::expect(lexer, Token::Terminator)?;
+	let token = lexer.next();
+	let length = match token.node {
+		Token::Integer(length, None) if length > 0 && length <= usize::max_value() as u128 => length as usize,
+		other => return Err(Diagnostic::new(Spanned::new(ParserError::ExpectedArrayLength(other), token.span))),
+	};
+
+	let span = initial.merge(super::expect(lexer, Token::BracketClose)?);
+	Ok(Spanned::new(Ascription::Array(Box::new(pattern.node), length), span))
+}
+
 pub fn lifetime(lexer: &mut Lexer) -> Result<Option<Spanned<Arc<str>>>, Diagnostic> {
 	Ok(match lexer.peek().node {
 		Token::Prime => Some(super::identifier(lexer.consume())?),
@@ -171,6 +195,27 @@ pub fn lifetime(lexer: &mut Lexer) -> Result<Option<Spanned<Arc<str>>>, Diagnost
 fn expect_terminator(lexer: &mut Lexer, expression: &Spanned<Expression>) -> Result<Span, Diagnostic> {
 	match expression.node {
 		Expression::Block(_) => Ok(expression.span),
+		// Structure literals with fields in a block are terminated by the block.
+		Expression::Structure(_, _) if lexer.peek().node != Token::LineBreak => Ok(expression.span),
 		_ => super::expect(lexer, Token::LineBreak),
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use crate::source::{context, function_path};
+
+	#[test]
+	fn test_structure_block_binding() {
+		let context = &context("data Point:\n\tx: u32,\n\ty: u32,\n\n\
+			fn main() -> u32:\n\tlet point = Point:\n\t\tx: 1,\n\t\ty: 2,\n\tpoint.x\n");
+		assert!(crate::parser::function(context, &function_path("main")).is_ok());
+	}
+
+	#[test]
+	fn test_structure_inline_binding() {
+		let context = &context("data Point:\n\tx: u32,\n\ty: u32,\n\n\
+			fn main() -> u32:\n\tlet point = Point: x: 1, y: 2\n\tpoint.x\n");
+		assert!(crate::parser::function(context, &function_path("main")).is_ok());
+	}
+}
@@ -1,11 +1,11 @@
 use std::sync::Arc;
 
 use crate::context::Context;
-use crate::declaration::FunctionPath;
+use crate::declaration::{FunctionPath, InterfacePath};
 use crate::error::Diagnostic;
 use crate::lexer::{Lexer, Token};
 use crate::node::*;
-use crate::span::Spanned;
+use crate::span::{Span, Spanned};
 
 use super::ParserError;
 
@@ -16,22 +16,78 @@ pub fn function_type(context: &Context, function_path: &Spanned<Arc<FunctionPath
 	let source = declaration.source.get(context);
 
 	let lexer = &mut Lexer::declaration(&source, &declaration)?;
+	super::skip(lexer, &Token::Export);
 	super::expect(lexer, Token::Function)?;
 	super::identifier(lexer)?;
+	let bounds = bounds(lexer)?;
 
 	let definition = context.node_definitions.get(&function_path.node);
-	let definition = definition.as_ref().map(|definition| definition.as_ref());
-	let parameters = parameters(lexer, definition).map_err(|diagnostic|
+	let receiver = definition.as_ref().map(|definition| move |span: Span| {
+		let templates = definition.templates.iter().cloned().map(|template|
+			Pattern::Terminal(Spanned::new(Ascription::Template(template.node), span))).collect();
+		Ascription::Structure(definition.structure.node.clone(), templates)
+	});
+
+	let receiver = receiver.as_ref().map(|receiver| receiver as &dyn Fn(Span) -> Ascription);
+	let parameters = parameters(lexer, receiver).map_err(|diagnostic|
 		diagnostic.note("In parsing function parameters"))?;
 	let return_type = return_type(lexer)?;
 
 	let function_offset = super::expect(lexer, Token::Separator)?.byte_end;
-	Ok(FunctionType::new(parameters, return_type, function_offset))
+	Ok(FunctionType::new(bounds, parameters, return_type, function_offset))
+}
+
+/// Parses the method signatures of an interface.
+/// The receiver of each method is ascribed with the template: `Self`.
+pub fn interface(context: &Context, interface_path: &Spanned<Arc<InterfacePath>>)
+                 -> Result<Interface, Diagnostic> {
+	let declaration = context.declarations_interface.get(&interface_path.node).ok_or_else(||
+		Diagnostic::new(interface_path.clone().map(|path| ParserError::UndefinedInterface(path))))?;
+	let source = declaration.source.get(context);
+
+	let lexer = &mut Lexer::declaration(&source, &declaration)?;
+	super::skip(lexer, &Token::Export);
+	super::expect(lexer, Token::Interface)?;
+	super::identifier(lexer)?;
+	super::expect(lexer, Token::Separator)?;
+	super::expect(lexer, Token::BlockOpen)?;
+
+	let mut methods: Vec<(Spanned<Arc<str>>, Arc<FunctionType>)> = Vec::new();
+	let receiver = &|_| Ascription::Template("Self".into());
+	while lexer.peek().node != Token::BlockClose {
+		super::expect(lexer, Token::Function)?;
+		let identifier = super::identifier(lexer)?;
+		if methods.iter().any(|(method, _)| method.node == identifier.node) {
+			let error = ParserError::DuplicateMethod(identifier.node);
+			return Err(Diagnostic::new(Spanned::new(error, identifier.span)));
+		}
+
+		let parameters = parameters(lexer, Some(receiver)).map_err(|diagnostic|
+			diagnostic.note("In parsing interface method parameters"))?;
+		let return_type = return_type(lexer)?;
+		let function_offset = return_type.span.byte_end;
+		let function_type = FunctionType::new(Vec::new(), parameters, return_type, function_offset);
+		methods.push((identifier, Arc::new(function_type)));
+
+		match lexer.peek().node {
+			Token::LineBreak => super::skip(lexer, &Token::LineBreak),
+			_ => break,
+		}
+	}
+
+	super::expect(lexer, Token::BlockClose)?;
+	Ok(Interface { methods })
 }
 
 pub fn function(context: &Context, function_path: &Spanned<Arc<FunctionPath>>)
                 -> Result<NodeFunction, Diagnostic> {
 	let function_type = crate::node::function_type(context, function_path)?;
+	function_body(context, function_path, function_type)
+}
+
+/// Parses the body of a function with an existing function type.
+pub fn function_body(context: &Context, function_path: &Spanned<Arc<FunctionPath>>,
+                     function_type: Arc<FunctionType>) -> Result<NodeFunction, Diagnostic> {
 	let source_key = context.declarations_function.get(&function_path.node).unwrap().source;
 	let source = source_key.get(context);
 
@@ -43,14 +99,46 @@ pub fn function(context: &Context, function_path: &Spanned<Arc<FunctionPath>>)
 	Ok(NodeFunction::new(function_context, expression, function_type))
 }
 
-fn parameters(lexer: &mut Lexer, definition: Option<&Definition>)
+/// Parses the templates of a function and the interfaces that they must implement.
+fn bounds(lexer: &mut Lexer) -> Result<Vec<Bound>, Diagnostic> {
+	let mut bounds = Vec::new();
+	if lexer.peek().node != Token::AngleLeft {
+		return Ok(bounds);
+	}
+
+	super::list(lexer.consume(), Token::AngleRight, Token::ListSeparator, &mut |lexer| {
+		super::expect(lexer, Token::Template)?;
+		let template = super::identifier(lexer)?;
+		let mut interfaces = Vec::new();
+		if lexer.peek().node == Token::Separator {
+			lexer.next();
+			loop {
+				interfaces.push(super::path(lexer)?.map(InterfacePath));
+				match lexer.peek().node {
+					Token::Add => lexer.next(),
+					_ => break,
+				};
+			}
+		}
+		Ok(bounds.push(Bound(template, interfaces)))
+	})?;
+
+	super::expect(lexer, Token::AngleRight)?;
+	Ok(bounds)
+}
+
+/// Parses the parameters of a function. The receiver provides
+/// the ascription of the self variable if the function is a method.
+fn parameters(lexer: &mut Lexer, receiver: Option<&dyn Fn(Span) -> Ascription>)
               -> Result<Vec<Spanned<Parameter>>, Diagnostic> {
 	let mut parameters = Vec::new();
 	super::expect(lexer, Token::ParenthesisOpen)?;
 	super::list(lexer, Token::ParenthesisClose, Token::ListSeparator, &mut |lexer| {
+		let is_mutable_self = lexer.clone().consume().peek().node == Token::SelfVariable;
 		match lexer.peek().node {
-			Token::Reference | Token::Unique => self_variable(definition, &mut parameters, lexer),
-			Token::SelfVariable | Token::Mutable => self_variable(definition, &mut parameters, lexer),
+			Token::Reference | Token::Unique => self_variable(receiver, &mut parameters, lexer),
+			Token::SelfVariable => self_variable(receiver, &mut parameters, lexer),
+			Token::Mutable if is_mutable_self => self_variable(receiver, &mut parameters, lexer),
 			_ => {
 				let pattern = super::pattern(lexer, &mut super::binding_variable)?;
 				super::expect(lexer, Token::Separator)?;
@@ -66,7 +154,7 @@ fn parameters(lexer: &mut Lexer, definition: Option<&Definition>)
 	Ok(parameters)
 }
 
-fn self_variable(definition: Option<&Definition>, parameters: &mut Vec<Spanned<Parameter>>,
+fn self_variable(receiver: Option<&dyn Fn(Span) -> Ascription>, parameters: &mut Vec<Spanned<Parameter>>,
                  lexer: &mut Lexer) -> Result<(), Diagnostic> {
 	use super::expression;
 	let (permission, lifetime, mutability) = match lexer.peek().node {
@@ -83,7 +171,7 @@ fn self_variable(definition: Option<&Definition>, parameters: &mut Vec<Spanned<P
 	};
 
 	let span = super::expect(lexer, Token::SelfVariable)?;
-	let definition = definition.ok_or({
+	let receiver = receiver.ok_or({
 		let error = ParserError::FunctionSelfVariable;
 		Diagnostic::new(Spanned::new(error, span))
 	})?;
@@ -93,10 +181,7 @@ fn self_variable(definition: Option<&Definition>, parameters: &mut Vec<Spanned<P
 		return Err(Diagnostic::new(Spanned::new(error, span)));
 	}
 
-	let templates = definition.templates.iter().cloned().map(|template|
-		Pattern::Terminal(Spanned::new(Ascription::Template(template.node), span))).collect();
-	let ascription = Ascription::Structure(definition.structure.node.clone(), templates);
-	let mut ascription = Pattern::Terminal(Spanned::new(ascription, span));
+	let mut ascription = Pattern::Terminal(Spanned::new(receiver(span), span));
 	if let Some(permission) = permission {
 		let reference = Ascription::Reference(permission, lifetime, Box::new(ascription));
 		ascription = Pattern::Terminal(Spanned::new(reference, span));
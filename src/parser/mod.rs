@@ -1,11 +1,11 @@
 use expression::{ascription, binding_variable, expression};
 pub use expression::path;
-pub use function::{function, function_type};
+pub use function::{function, function_body, function_type, interface};
 use parser::{pattern, skip};
 pub use parser::{expect, identifier, list};
 pub use parser::ParserError;
 pub use structure::{definition, structure};
-use value::root_value;
+pub use value::{condition_value, root_value};
 
 mod function;
 mod parser;
@@ -1,7 +1,7 @@
 use std::fmt;
 use std::sync::Arc;
 
-use crate::declaration::{FunctionPath, StructurePath};
+use crate::declaration::{FunctionPath, InterfacePath, StructurePath};
 use crate::error::{CompileError, Diagnostic};
 use crate::lexer::{Lexer, Token};
 use crate::node::Pattern;
@@ -11,13 +11,19 @@ use crate::span::{Span, Spanned};
 pub enum ParserError {
 	UndefinedFunction(Arc<FunctionPath>),
 	UndefinedStructure(Arc<StructurePath>),
+	UndefinedInterface(Arc<InterfacePath>),
 	ExpectedExpression(Token),
 	ExpectedIdentifier(Token),
 	ExpectedToken(Token, Token),
 	ExpectedExpressionTerminator(Token),
 	ExpectedStructureTerminator(Token),
 	ExpectedPathAssociation(Token),
+	ExpectedArrayLength(Token),
+	EmptyArray,
 	DuplicateField(Arc<str>),
+	DuplicateVariant(Arc<str>),
+	DuplicateArm(Arc<str>),
+	DuplicateMethod(Arc<str>),
 	FunctionSelfVariable,
 	BindingSelfVariable,
 	SelfVariablePosition,
@@ -30,6 +36,8 @@ impl fmt::Display for ParserError {
 				write!(f, "Function: {}, is not defined", path),
 			ParserError::UndefinedStructure(path) =>
 				write!(f, "Structure: {}, is not defined", path),
+			ParserError::UndefinedInterface(path) =>
+				write!(f, "Interface: {}, is not defined", path),
 			ParserError::ExpectedExpression(token) =>
 				write!(f, "Expected an expression, instead got token: {:?}", token),
 			ParserError::ExpectedIdentifier(token) =>
@@ -40,10 +48,20 @@ impl fmt::Display for ParserError {
 				write!(f, "Expected line break or mutation operator, instead got token: {:?}", token),
 			ParserError::ExpectedStructureTerminator(token) =>
 				write!(f, "Expected separator or template declaration, instead got token: {:?}", token),
+			ParserError::ExpectedArrayLength(token) =>
+				write!(f, "Expected a nonzero array length, instead got token: {:?}", token),
+			ParserError::EmptyArray =>
+				write!(f, "Array literals must have at least one element"),
 			ParserError::ExpectedPathAssociation(token) =>
 				write!(f, "Expected a function call or structure literal instead got token: {:?}", token),
 			ParserError::DuplicateField(field) =>
 				write!(f, "Field with identifier: {}, has already been defined", field),
+			ParserError::DuplicateVariant(variant) =>
+				write!(f, "Variant with identifier: {}, has already been defined", variant),
+			ParserError::DuplicateArm(variant) =>
+				write!(f, "Variant: {}, has already been matched", variant),
+			ParserError::DuplicateMethod(method) =>
+				write!(f, "Method with identifier: {}, has already been declared", method),
 			ParserError::FunctionSelfVariable =>
 				write!(f, "Variable: self, cannot appear in function that is not in a definition"),
 			ParserError::BindingSelfVariable =>
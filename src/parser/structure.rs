@@ -2,11 +2,11 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::context::Context;
-use crate::declaration::{Declaration, ModulePath, StructurePath};
+use crate::declaration::{Declaration, InterfacePath, ModulePath, StructurePath};
 use crate::error::Diagnostic;
 use crate::lexer::{Lexer, Token};
 use crate::node::{AscriptionPattern, Definition, Expression, ExpressionKey,
-	FunctionContext, Structure, Variable};
+	FunctionContext, Structure, Variable, Variant};
 use crate::span::{Span, Spanned};
 
 use super::ParserError;
@@ -18,20 +18,26 @@ pub fn structure(context: &Context, structure_path: &Spanned<Arc<StructurePath>>
 	let source = declaration.source.get(context);
 
 	let lexer = &mut Lexer::declaration(&source, &declaration)?;
+	super::skip(lexer, &Token::Export);
 	super::expect(lexer, Token::Data)?;
 	super::identifier(lexer)?;
 
 	let templates = templates(lexer)?;
-	let fields = match lexer.peek().node {
+	let (fields, variants) = match lexer.peek().node {
 		Token::BlockOpen => {
-			let fields = fields(lexer.consume(), Some(Token::LineBreak), Token::BlockClose)?;
+			let body = match lexer.consume().peek().node {
+				Token::BitOr => (HashMap::new(), variants(lexer, Some(Token::LineBreak))?),
+				_ => (fields(lexer, Some(Token::LineBreak), Token::BlockClose)?, Vec::new()),
+			};
+
 			super::expect(lexer, Token::BlockClose)?;
-			fields
+			body
 		}
-		_ => fields(lexer, None, Token::LineBreak)?,
+		Token::BitOr => (HashMap::new(), variants(lexer, None)?),
+		_ => (fields(lexer, None, Token::LineBreak)?, Vec::new()),
 	};
 
-	Ok(Structure { templates, fields })
+	Ok(Structure { templates, fields, variants })
 }
 
 pub fn definition(context: &Context, declaration: Arc<ModulePath>,
@@ -40,10 +46,16 @@ pub fn definition(context: &Context, declaration: Arc<ModulePath>,
 	let lexer = &mut Lexer::declaration(&source, definition)?;
 
 	super::expect(lexer, Token::Define)?;
-	let structure = super::expression::path(lexer)?
-		.map(|path| StructurePath(path));
+	let mut path = super::expression::path(lexer)?;
+	let mut interface = None;
+	if lexer.peek().node == Token::For {
+		interface = Some(path.map(|path| InterfacePath(path)));
+		path = super::expression::path(lexer.consume())?;
+	}
+
+	let structure = path.map(|path| StructurePath(path));
 	let templates = templates(lexer)?;
-	Ok(Definition { declaration, structure, templates })
+	Ok(Definition { declaration, structure, interface, templates })
 }
 
 pub fn literal(context: &mut FunctionContext, lexer: &mut Lexer,
@@ -103,6 +115,27 @@ fn fields(lexer: &mut Lexer, skip_token: Option<Token>, terminator: Token)
 	Ok(fields)
 }
 
+fn variants(lexer: &mut Lexer, skip_token: Option<Token>) -> Result<Vec<Variant>, Diagnostic> {
+	let mut variants: Vec<Variant> = Vec::new();
+	while lexer.peek().node == Token::BitOr {
+		let identifier = super::identifier(lexer.consume())?;
+		if variants.iter().any(|variant| variant.identifier.node == identifier.node) {
+			let error = ParserError::DuplicateVariant(identifier.node);
+			return Err(Diagnostic::new(Spanned::new(error, identifier.span)));
+		}
+
+		let fields = match lexer.peek().node {
+			Token::Separator => fields(lexer.consume(), None, Token::LineBreak)
+				.map_err(|diagnostic| diagnostic.note("In parsing structure variant"))?,
+			_ => HashMap::new(),
+		};
+
+		variants.push(Variant { identifier, fields });
+		skip_token.as_ref().map(|token| super::skip(lexer, token));
+	}
+	Ok(variants)
+}
+
 fn field(lexer: &mut Lexer, fields: &mut HashMap<Arc<str>, AscriptionPattern>)
          -> Result<(), Diagnostic> {
 	let identifier = super::identifier(lexer)?;
@@ -122,7 +155,7 @@ fn literal_fields(context: &mut FunctionContext, lexer: &mut Lexer, skip_token:
 		let expression = literal_field(context, lexer, &mut fields)
 			.map_err(|diagnostic| diagnostic.note("In parsing structure field"))?;
 		match &context[&expression].node {
-			Expression::Structure(_, _) => (),
+			Expression::Structure(_, _) if lexer.peek().node != Token::ListSeparator => (),
 			_ => match lexer.peek().node {
 				Token::ListSeparator => { lexer.next(); }
 				_ => break,
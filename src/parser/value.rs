@@ -1,4 +1,7 @@
-use crate::declaration::{FunctionPath, StructurePath};
+use std::collections::HashMap;
+
+use crate::basic::Item;
+use crate::declaration::{DeclarationPath, FunctionPath, StructurePath};
 use crate::error::Diagnostic;
 use crate::lexer::{Lexer, Token};
 use crate::node::{Arithmetic, BinaryOperator, Execution, Expression, ExpressionKey,
@@ -9,27 +12,36 @@ use super::ParserError;
 
 pub fn root_value(context: &mut FunctionContext, lexer: &mut Lexer)
                   -> Result<ExpressionKey, Diagnostic> {
-	value(context, lexer, 0)
+	value(context, lexer, 0, true)
+}
+
+/// Parses a value that is followed by a separator and
+/// therefore cannot contain a structure literal outside of parentheses.
+pub fn condition_value(context: &mut FunctionContext, lexer: &mut Lexer)
+                       -> Result<ExpressionKey, Diagnostic> {
+	value(context, lexer, 0, false)
 }
 
-fn value(context: &mut FunctionContext, lexer: &mut Lexer, precedence: usize)
-         -> Result<ExpressionKey, Diagnostic> {
-	let mut left = terminal(context, lexer)?;
+fn value(context: &mut FunctionContext, lexer: &mut Lexer, precedence: usize,
+         structure: bool) -> Result<ExpressionKey, Diagnostic> {
+	let mut left = terminal(context, lexer, structure)?;
 	while token_precedence(&lexer.peek().node) > precedence {
-		left = binder(context, lexer, left)?;
+		left = binder(context, lexer, left, structure)?;
 	}
 	Ok(left)
 }
 
-fn binder(context: &mut FunctionContext, lexer: &mut Lexer, left: ExpressionKey)
-          -> Result<ExpressionKey, Diagnostic> {
+fn binder(context: &mut FunctionContext, lexer: &mut Lexer, left: ExpressionKey,
+          structure: bool) -> Result<ExpressionKey, Diagnostic> {
 	let binder = lexer.next();
-	if binder.node == Token::Dot {
-		return projection(context, lexer, left);
+	match binder.node {
+		Token::Dot => return projection(context, lexer, left),
+		Token::BracketOpen => return index(context, lexer, left),
+		_ => (),
 	}
 
 	let precedence = token_precedence(&binder.node);
-	let right = value(context, lexer, precedence)?;
+	let right = value(context, lexer, precedence, structure)?;
 	let span = context[&left].span.merge(context[&right].span);
 
 	let operator = match binder.node {
@@ -38,9 +50,19 @@ fn binder(context: &mut FunctionContext, lexer: &mut Lexer, left: ExpressionKey)
 		Token::AngleLeft => BinaryOperator::LessThan,
 		Token::LessEqual => BinaryOperator::LessEqual,
 		Token::Equality => BinaryOperator::Equality,
+		Token::NotEqual => BinaryOperator::NotEqual,
+		Token::And => BinaryOperator::And,
+		Token::Or => BinaryOperator::Or,
 		Token::Add => BinaryOperator::Arithmetic(Arithmetic::Add),
 		Token::Minus => BinaryOperator::Arithmetic(Arithmetic::Minus),
 		Token::Multiply => BinaryOperator::Arithmetic(Arithmetic::Multiply),
+		Token::Divide => BinaryOperator::Arithmetic(Arithmetic::Divide),
+		Token::Modulo => BinaryOperator::Arithmetic(Arithmetic::Modulo),
+		Token::BitAnd => BinaryOperator::Arithmetic(Arithmetic::BitAnd),
+		Token::BitOr => BinaryOperator::Arithmetic(Arithmetic::BitOr),
+		Token::BitXor => BinaryOperator::Arithmetic(Arithmetic::BitXor),
+		Token::ShiftLeft => BinaryOperator::Arithmetic(Arithmetic::ShiftLeft),
+		Token::ShiftRight => BinaryOperator::Arithmetic(Arithmetic::ShiftRight),
 		_ => panic!("Invalid value binder: {:?}", binder.node),
 	};
 
@@ -50,17 +72,24 @@ fn binder(context: &mut FunctionContext, lexer: &mut Lexer, left: ExpressionKey)
 
 fn token_precedence(token: &Token) -> usize {
 	match token {
-		Token::Equality => 1,
-		Token::AngleLeft | Token::AngleRight => 2,
-		Token::LessEqual | Token::GreaterEqual => 2,
-		Token::Add | Token::Minus => 3,
-		Token::Multiply => 4,
-		Token::Dot => 5,
+		Token::Or => 1,
+		Token::And => 2,
+		Token::Equality | Token::NotEqual => 3,
+		Token::AngleLeft | Token::AngleRight => 4,
+		Token::LessEqual | Token::GreaterEqual => 4,
+		Token::BitOr => 5,
+		Token::BitXor => 6,
+		Token::BitAnd => 7,
+		Token::ShiftLeft | Token::ShiftRight => 8,
+		Token::Add | Token::Minus => 9,
+		Token::Multiply | Token::Divide | Token::Modulo => 10,
+		Token::Dot | Token::BracketOpen => 11,
 		_ => 0,
 	}
 }
 
-fn terminal(context: &mut FunctionContext, lexer: &mut Lexer) -> Result<ExpressionKey, Diagnostic> {
+fn terminal(context: &mut FunctionContext, lexer: &mut Lexer,
+            structure: bool) -> Result<ExpressionKey, Diagnostic> {
 	let token = lexer.peek();
 	match &token.node {
 		Token::BlockOpen => block(context, lexer),
@@ -68,30 +97,70 @@ fn terminal(context: &mut FunctionContext, lexer: &mut Lexer) -> Result<Expressi
 			let pattern = super::pattern(lexer, &mut |lexer| root_value(context, lexer))?;
 			Ok(context.register(pattern.map(|pattern| Expression::Pattern(pattern))))
 		}
+		Token::BracketOpen => array(context, lexer),
 		Token::Compile => {
 			lexer.next();
 			let function_path = super::expression::path(lexer)?.map(|path| FunctionPath(path));
 			function_call(context, lexer, Execution::Compile, function_path)
 		}
-		_ => consume_terminal(context, lexer),
+		_ => consume_terminal(context, lexer, structure),
 	}
 }
 
-fn consume_terminal(context: &mut FunctionContext, lexer: &mut Lexer) -> Result<ExpressionKey, Diagnostic> {
+/// Parses a path without a call or fields as a variant without fields.
+fn unit_variant(context: &mut FunctionContext, lexer: &mut Lexer,
+                path: Spanned<DeclarationPath>) -> Result<ExpressionKey, Diagnostic> {
+	let span = path.span;
+	let DeclarationPath { module_path, identifier } = path.node;
+	let parent = match module_path.parent.clone() {
+		// Declarations directly within the root module are not variants.
+		Some(parent) if !module_path.is_root() => parent,
+		_ => {
+			let token = lexer.next();
+			let error = ParserError::ExpectedPathAssociation(token.node);
+			return Err(Diagnostic::new(Spanned::new(error, token.span)));
+		}
+	};
+
+	let structure_path = StructurePath(DeclarationPath {
+		module_path: parent,
+		identifier: module_path.identifier.clone(),
+	});
+
+	let structure_path = Spanned::new(structure_path, span);
+	let variant = Spanned::new(identifier, span);
+	let expression = Expression::Variant(structure_path, variant, HashMap::new());
+	Ok(context.register(Spanned::new(expression, span)))
+}
+
+fn consume_terminal(context: &mut FunctionContext, lexer: &mut Lexer,
+                    structure: bool) -> Result<ExpressionKey, Diagnostic> {
 	let token = lexer.next();
 	match token.node {
-		Token::Integer(integer) => Ok(context
-			.register(Spanned::new(Expression::Integer(integer), token.span))),
+		Token::Integer(integer, intrinsic) => Ok(context
+			.register(Spanned::new(Expression::Integer(integer, intrinsic), token.span))),
+		Token::Error(error) => Err(Diagnostic::new(Spanned::new(error, token.span))),
 		Token::Truth(truth) => Ok(context
 			.register(Spanned::new(Expression::Truth(truth), token.span))),
+		Token::Character(character) => Ok(context
+			.register(Spanned::new(Expression::Item(Item::Character(character)), token.span))),
+		Token::String(string) => Ok(context
+			.register(Spanned::new(Expression::Item(Item::String(string)), token.span))),
 		Token::Minus => unary(context, lexer,
-			Spanned::new(UnaryOperator::Negate, token.span)),
+			Spanned::new(UnaryOperator::Negate, token.span), structure),
 		Token::Asterisk => unary(context, lexer,
-			Spanned::new(UnaryOperator::Dereference, token.span)),
+			Spanned::new(UnaryOperator::Dereference, token.span), structure),
 		Token::Reference => unary(context, lexer,
-			Spanned::new(UnaryOperator::Reference(Permission::Shared), token.span)),
+			Spanned::new(UnaryOperator::Reference(Permission::Shared), token.span), structure),
 		Token::Unique => unary(context, lexer,
-			Spanned::new(UnaryOperator::Reference(Permission::Unique), token.span)),
+			Spanned::new(UnaryOperator::Reference(Permission::Unique), token.span), structure),
+		Token::Not => {
+			// Negation binds more loosely than comparisons but more tightly than conjunctions.
+			let expression = value(context, lexer, token_precedence(&Token::And), structure)?;
+			let span = token.span.merge(context[&expression].span);
+			let operator = Spanned::new(UnaryOperator::Not, token.span);
+			Ok(context.register(Spanned::new(Expression::Unary(operator, expression), span)))
+		}
 		Token::SelfVariable => {
 			let expression = Expression::Variable(Variable::new("self".into()));
 			Ok(context.register(Spanned::new(expression, token.span)))
@@ -105,16 +174,12 @@ fn consume_terminal(context: &mut FunctionContext, lexer: &mut Lexer) -> Result<
 						let function_path = path.map(|path| FunctionPath(path));
 						function_call(context, lexer, Execution::Runtime, function_path)
 					}
-					Token::Separator => super::structure::literal(context, lexer,
+					Token::Separator if structure => super::structure::literal(context, lexer,
 						path.map(|path| StructurePath(path))),
-					_ => {
-						let token = lexer.next();
-						let error = ParserError::ExpectedPathAssociation(token.node);
-						Err(Diagnostic::new(Spanned::new(error, token.span)))
-					}
+					_ => unit_variant(context, lexer, path),
 				}
 			}
-			Token::Separator => {
+			Token::Separator if structure => {
 				let recover = &mut lexer.clone();
 				let identifier = Spanned::new(identifier, token.span);
 				let path = super::expression::path_identifier(recover, identifier.clone())?;
@@ -141,9 +206,9 @@ fn consume_terminal(context: &mut FunctionContext, lexer: &mut Lexer) -> Result<
 	}
 }
 
-fn unary(context: &mut FunctionContext, lexer: &mut Lexer,
-         operator: Spanned<UnaryOperator>) -> Result<ExpressionKey, Diagnostic> {
-	let expression = root_value(context, lexer)?;
+fn unary(context: &mut FunctionContext, lexer: &mut Lexer, operator: Spanned<UnaryOperator>,
+         structure: bool) -> Result<ExpressionKey, Diagnostic> {
+	let expression = value(context, lexer, 0, structure)?;
 	let span = operator.span.merge(context[&expression].span);
 	Ok(context.register(Spanned::new(Expression::Unary(operator, expression), span)))
 }
@@ -170,6 +235,26 @@ fn projection(context: &mut FunctionContext, lexer: &mut Lexer,
 	Ok(context.register(expression))
 }
 
+fn index(context: &mut FunctionContext, lexer: &mut Lexer,
+         expression: ExpressionKey) -> Result<ExpressionKey, Diagnostic> {
+	let index = root_value(context, lexer).map_err(|diagnostic|
+		diagnostic.note("In parsing an index"))?;
+	let span = context[&expression].span.merge(super::expect(lexer, Token::BracketClose)?);
+	Ok(context.register(Spanned::new(Expression::Index(expression, index), span)))
+}
+
+fn array(context: &mut FunctionContext, lexer: &mut Lexer) -> Result<ExpressionKey, Diagnostic> {
+	let initial_span = super::expect(lexer, Token::BracketOpen)?;
+	let (elements, _) = super::list(lexer, Token::BracketClose, Token::ListSeparator,
+		&mut |lexer| root_value(context, lexer).map_err(|diagnostic|
+			diagnostic.note("In parsing an array element")))?;
+	let span = initial_span.merge(super::expect(lexer, Token::BracketClose)?);
+	match elements.is_empty() {
+		true => Err(Diagnostic::new(Spanned::new(ParserError::EmptyArray, span))),
+		false => Ok(context.register(Spanned::new(Expression::Array(elements), span))),
+	}
+}
+
 fn arguments(context: &mut FunctionContext, lexer: &mut Lexer)
              -> Result<Spanned<Vec<ExpressionKey>>, Diagnostic> {
 	let mut arguments = Vec::new();
@@ -0,0 +1,381 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::context::Context;
+use crate::declaration::{FunctionPath, InclusionTerminal, InterfacePath, ModulePath,
+	ModulePending, StructurePath};
+use crate::node::{Ascription, AscriptionPattern, Bound, Expression,
+	FunctionType, NodeFunction};
+use crate::inference::{Instantiation, TypeResolution};
+use crate::source::{Source, SourceKey};
+
+/// Declarations that are removed or depend on a removed declaration.
+#[derive(Debug, Default)]
+pub struct Invalidation {
+	pub modules: HashSet<Arc<ModulePath>>,
+	pub functions: HashSet<Arc<FunctionPath>>,
+	pub structures: HashSet<Arc<StructurePath>>,
+	pub interfaces: HashSet<Arc<InterfacePath>>,
+	pub implementations: HashSet<(StructurePath, InterfacePath)>,
+}
+
+impl Invalidation {
+	fn function(&self, function: &NodeFunction) -> bool {
+		self.function_type(&function.function_type) || function.context.expressions.iter()
+			.any(|expression| match &expression.node {
+				Expression::FunctionCall(path, _, _) => self.functions.contains(&path.node),
+				Expression::Structure(path, _) | Expression::Variant(path, _, _) =>
+					self.structures.contains(&path.node),
+				Expression::Binding(_, Some(ascription), _) => self.ascription(ascription),
+				Expression::MethodCall(_, method, _) => self.functions.iter().any(|path| {
+					let FunctionPath(path) = path.as_ref();
+					path.identifier == method.node
+				}),
+				_ => false,
+			})
+	}
+
+	fn function_type(&self, function_type: &FunctionType) -> bool {
+		self.ascription(&function_type.return_type.node) || function_type.parameters.iter()
+			.any(|parameter| self.ascription(&parameter.node.1)) || function_type.bounds.iter()
+			.any(|Bound(_, interfaces)| interfaces.iter().any(|interface|
+				self.interfaces.contains(&interface.node)))
+	}
+
+	fn ascription(&self, ascription: &AscriptionPattern) -> bool {
+		let mut is_invalid = false;
+		ascription.traverse(&mut |ascription| is_invalid |= match &ascription.node {
+			Ascription::Structure(path, templates) => self.structures.contains(path) ||
+				templates.iter().any(|template| self.ascription(template)),
+			Ascription::Reference(_, _, ascription) |
+			Ascription::Array(ascription, _) => self.ascription(ascription),
+			Ascription::Template(_) => false,
+		});
+		is_invalid
+	}
+
+	fn resolution(&self, resolution: &TypeResolution) -> bool {
+		match resolution {
+			TypeResolution::Instance(path, resolutions) => self.structures.contains(path) ||
+				resolutions.iter().any(|resolution| self.resolution(resolution)),
+			TypeResolution::Reference(_, resolution) |
+			TypeResolution::Array(resolution, _) => self.resolution(resolution),
+			TypeResolution::Template(_) => false,
+		}
+	}
+}
+
+/// Provides the modules that have a source that changed since it was loaded.
+pub fn modified(context: &Context) -> Vec<Arc<ModulePath>> {
+	let mut modules: Vec<_> = context.module_pending.clone().into_iter()
+		.filter(|(_, pending)| paths(pending).any(|path| match context.source_keys.get(path) {
+			None => path.is_file(),
+			Some(source_key) => std::fs::read(path.as_ref()).map(|data|
+				data != source_key.get(context).data).unwrap_or(true),
+		})).map(|(module_path, _)| module_path).collect();
+	modules.sort_by_key(|module_path| module_path.to_string());
+	modules
+}
+
+/// Reloads the sources of the modules and invalidates the declarations
+/// loaded from them along with everything that transitively depends on them.
+pub fn reload(context: &Context, modules: &[Arc<ModulePath>]) -> Invalidation {
+	let mut invalidation = Invalidation::default();
+	let pending: Vec<_> = modules.iter().filter_map(|module_path|
+		context.module_pending.get(module_path).map(|pending| pending.clone())).collect();
+
+	let (mut sources, mut owned_modules) = (HashSet::new(), Vec::new());
+	for pending in &pending {
+		sources.extend(refresh(context, pending));
+		owned_modules.extend(owned(context, &pending.module_path));
+	}
+
+	// External modules declared in the reloaded sources are retained
+	// if they are declared again and are not modified themselves.
+	let retained: Vec<_> = context.module_pending.clone().into_iter()
+		.filter(|(module_path, pending)| !modules.contains(module_path) &&
+			sources.contains(&pending.declaration_span.source)).map(|(_, pending)| pending).collect();
+	retained.iter().for_each(|pending| { context.module_pending.remove(&pending.module_path); });
+	remove(context, owned_modules, &sources, &mut invalidation);
+
+	let existing: HashSet<_> = context.module_contexts.read().keys().cloned().collect();
+	pending.into_iter().for_each(|pending| { crate::declaration::load_module(context, pending); });
+	let loaded = context.module_contexts.read().keys()
+		.filter(|module_path| !existing.contains(*module_path)).cloned().collect();
+	crate::node::reload_definitions(context, &loaded);
+
+	for orphan in retained.iter().filter(|pending| !context.module_pending.contains_key(&pending.module_path)) {
+		let mut sources: HashSet<_> = paths(orphan).filter_map(|path|
+			context.source_keys.get(path).map(|source_key| *source_key)).collect();
+		for (module_path, pending) in context.module_pending.clone() {
+			if module_path.is_within(&orphan.module_path) {
+				context.module_pending.remove(&module_path);
+				sources.extend(paths(&pending).filter_map(|path|
+					context.source_keys.get(path).map(|source_key| *source_key)));
+			}
+		}
+
+		let modules: Vec<_> = context.module_contexts.read().keys()
+			.filter(|module_path| module_path.is_within(&orphan.module_path)).cloned().collect();
+		remove(context, modules, &sources, &mut invalidation);
+	}
+
+	invalidate(context, &mut invalidation);
+	crate::node::reload_implementations(context, &invalidation.interfaces);
+	invalidation
+}
+
+/// Rereads the sources of a module. Returns the keys of the previously loaded sources.
+fn refresh(context: &Context, pending: &ModulePending) -> Vec<SourceKey> {
+	paths(pending).filter_map(|path| {
+		let source_key = *context.source_keys.get(path)?;
+		match std::fs::read(path.as_ref()) {
+			Ok(data) => context.sources.insert(source_key,
+				Arc::new(Source { path: path.clone(), data })).map(|_| ()),
+			Err(_) => context.source_keys.remove(path).map(|_| ()),
+		};
+		Some(source_key)
+	}).collect()
+}
+
+/// Removes the module contexts and the declarations from the sources.
+fn remove(context: &Context, modules: Vec<Arc<ModulePath>>,
+          sources: &HashSet<SourceKey>, invalidation: &mut Invalidation) {
+	let mut module_contexts = context.module_contexts.write();
+	modules.iter().for_each(|module_path| { module_contexts.remove(module_path); });
+	invalidation.modules.extend(modules);
+	drop(module_contexts);
+
+	for (path, declaration) in context.declarations_function.clone() {
+		if sources.contains(&declaration.source) {
+			context.declarations_function.remove(&path);
+			context.function_types.remove(&path);
+			context.node_definitions.remove(&path);
+			context.declarations_test.write().remove(&path);
+			invalidation.functions.insert(path);
+		}
+	}
+
+	for (path, declaration) in context.declarations_structure.clone() {
+		if sources.contains(&declaration.source) {
+			context.declarations_structure.remove(&path);
+			invalidation.structures.insert(path);
+		}
+	}
+
+	for (path, declaration) in context.declarations_interface.clone() {
+		if sources.contains(&declaration.source) {
+			context.declarations_interface.remove(&path);
+			context.node_interfaces.remove(&path);
+			invalidation.interfaces.insert(path);
+		}
+	}
+
+	for (key, definition) in context.node_implementations.clone() {
+		if invalidation.modules.contains(&definition.declaration) {
+			context.node_implementations.remove(&key);
+			invalidation.implementations.insert(key);
+		}
+	}
+}
+
+/// Invalidates declarations until all dependents of invalidated declarations
+/// are invalidated and removes them from the context caches.
+fn invalidate(context: &Context, invalidation: &mut Invalidation) {
+	// Paths in modules that include an invalidated module may resolve differently.
+	let including: HashSet<_> = context.module_contexts.read().iter()
+		.filter(|(_, module)| module.inclusions.iter().any(|inclusion| {
+			let inclusion = &inclusion.node;
+			invalidation.modules.contains(&inclusion.module_path) || match &inclusion.terminal {
+				InclusionTerminal::Identifier(identifier) => invalidation.modules
+					.contains(&inclusion.module_path.clone().push(identifier.clone())),
+				InclusionTerminal::Wildcard => false,
+			}
+		})).map(|(module_path, _)| module_path.clone()).collect();
+
+	// Functions that invoke functions with bounds depend on the implementations.
+	let is_implementing = !invalidation.interfaces.is_empty() ||
+		!invalidation.implementations.is_empty();
+	let instantiating: HashSet<_> = match is_implementing {
+		false => HashSet::new(),
+		true => context.type_contexts.clone().into_iter()
+			.filter(|(_, types)| types.is_instantiating())
+			.map(|((function_path, _), _)| function_path).collect(),
+	};
+
+	loop {
+		let count = invalidation.functions.len() + invalidation.structures.len();
+		for (path, structure) in context.node_structures.clone() {
+			if structure.ascriptions().any(|field| invalidation.ascription(field)) {
+				invalidation.structures.insert(path);
+			}
+		}
+
+		for (path, function_type) in context.function_types.clone() {
+			if invalidation.function_type(&function_type) {
+				invalidation.functions.insert(path);
+			}
+		}
+
+		for (path, function) in context.node_functions.clone() {
+			let FunctionPath(declaration_path) = path.as_ref();
+			let module_path = context.node_definitions.get(&path).map(|definition|
+				definition.declaration.clone()).unwrap_or(declaration_path.module_path.clone());
+			let is_instantiating = instantiating.contains(&path);
+			if including.contains(&module_path) || is_instantiating || invalidation.function(&function) {
+				invalidation.functions.insert(path);
+			}
+		}
+
+		if invalidation.functions.len() + invalidation.structures.len() == count {
+			break;
+		}
+	}
+
+	for path in &invalidation.functions {
+		context.function_types.remove(path);
+		context.node_functions.remove(path);
+		context.partial_functions.remove(path);
+	}
+
+	// Instances are invalid if they are instantiated with an invalid structure.
+	let is_invalid = |path: &Arc<FunctionPath>, instantiation: &Instantiation| invalidation.functions
+		.contains(path) || instantiation.values().any(|resolution| invalidation.resolution(resolution));
+	context.type_contexts.retain(|(path, instantiation), _| !is_invalid(path, instantiation));
+	context.basic_functions.retain(|(path, _, instantiation), _| !is_invalid(path, instantiation));
+	for path in &invalidation.structures {
+		context.node_structures.remove(path);
+	}
+
+	for path in &invalidation.interfaces {
+		context.node_interfaces.remove(path);
+	}
+}
+
+/// Provides the module contexts that are loaded from the sources of the module.
+fn owned(context: &Context, module_path: &Arc<ModulePath>) -> Vec<Arc<ModulePath>> {
+	context.module_contexts.read().keys().filter(|path| {
+		let mut path = Some(*path);
+		while let Some(current) = path {
+			if context.module_pending.contains_key(current) {
+				return current == module_path;
+			}
+			path = current.parent.as_ref();
+		}
+		false
+	}).cloned().collect()
+}
+
+fn paths(pending: &ModulePending) -> impl Iterator<Item=&Arc<PathBuf>> {
+	std::iter::once(&pending.expected_path).chain(pending.expected_module_path.iter())
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::declaration::DeclarationPath;
+	use crate::source::{function_path, TemporaryDirectory};
+
+	use super::*;
+
+	const SHAPES: &str = "export data Square:\n\tside: u32,\n\n\
+		export fn area(square: &Square) -> u32:\n\tsquare.side * square.side\n";
+
+	const UTILITY: &str = "export fn identity<$T>(value: $T) -> $T:\n\tvalue\n\n\
+		export fn constant() -> u32:\n\tidentity(1u32)\n";
+
+	const MAIN: &str = "module shapes\nmodule utility\n\n\
+		use crate::shapes::*\nuse crate::utility::*\n\n\
+		fn total() -> u32:\n\tlet square = Square: side: 2\n\tarea(&square)\n\n\
+		fn square() -> Square:\n\tidentity(Square: side: 1)\n";
+
+	fn sources() -> TemporaryDirectory {
+		TemporaryDirectory::new(&[("main.lx", MAIN), ("shapes.lx", SHAPES), ("utility.lx", UTILITY)])
+	}
+
+	fn context(sources: &TemporaryDirectory) -> Context {
+		let context = sources.context();
+		for function in &[function_path("total"), function_path("square"), utility("constant")] {
+			crate::inference::function(&context, function).unwrap();
+		}
+
+		assert!(context.errors.read().is_empty());
+		context
+	}
+
+	fn utility(identifier: &str) -> crate::span::Spanned<Arc<FunctionPath>> {
+		let module_path = ModulePath::root().push("utility".into());
+		let path = DeclarationPath { module_path, identifier: identifier.into() };
+		crate::span::Spanned::new(Arc::new(FunctionPath(path)), crate::span::Span::INTERNAL)
+	}
+
+	fn sorted<T: ToString>(paths: &HashSet<T>) -> Vec<String> {
+		let mut paths: Vec<_> = paths.iter().map(ToString::to_string).collect();
+		paths.sort();
+		paths
+	}
+
+	#[test]
+	fn test_modified() {
+		let sources = sources();
+		let context = &context(&sources);
+		assert!(modified(context).is_empty());
+
+		sources.write("shapes.lx", &SHAPES.replace("square.side * square.side", "square.side"));
+		let modules: Vec<_> = modified(context).iter().map(ToString::to_string).collect();
+		assert_eq!(modules, &["crate::shapes"]);
+	}
+
+	#[test]
+	fn test_reload_dependents() {
+		let sources = sources();
+		let context = &context(&sources);
+		sources.write("shapes.lx", &SHAPES.replace("square.side * square.side", "square.side"));
+		let invalidation = reload(context, &modified(context));
+
+		assert_eq!(sorted(&invalidation.modules), &["crate::shapes"]);
+		assert_eq!(sorted(&invalidation.structures), &["crate::shapes::Square"]);
+		assert_eq!(sorted(&invalidation.functions), &["crate::shapes::area", "crate::square", "crate::total"]);
+		assert!(!context.node_functions.contains_key(&function_path("total").node));
+		assert!(context.node_functions.contains_key(&utility("constant").node));
+		assert!(context.node_functions.contains_key(&utility("identity").node));
+		assert!(context.declarations_function.contains_key(&function_path("total").node));
+	}
+
+	#[test]
+	fn test_reload_instances() {
+		let sources = sources();
+		let context = &context(&sources);
+		let identity = utility("identity").node;
+		let instances = |context: &Context| {
+			let mut instances: Vec<_> = context.type_contexts.clone().into_iter()
+				.filter(|((path, _), _)| path == &identity)
+				.map(|((_, instantiation), _)| instantiation.values()
+					.map(ToString::to_string).collect::<Vec<_>>().join(", ")).collect();
+			instances.sort();
+			instances
+		};
+
+		let constant = (utility("constant").node, Instantiation::default());
+		assert_eq!(instances(context), &["crate::shapes::Square", "intrinsic::u32"]);
+		sources.write("shapes.lx", &SHAPES.replace("side: u32", "side: u64"));
+		reload(context, &modified(context));
+		assert_eq!(instances(context), &["intrinsic::u32"]);
+		assert!(context.type_contexts.contains_key(&constant));
+	}
+
+	#[test]
+	fn test_reload_definitions() {
+		let sources = sources();
+		let context = &context(&sources);
+		sources.write("shapes.lx", &SHAPES.replace("square.side * square.side", "square.side + 1"));
+		reload(context, &modified(context));
+		assert!(context.errors.read().is_empty());
+		assert!(crate::inference::function(context, &function_path("total")).is_ok());
+
+		sources.write("shapes.lx", "export data Square:\n\tside: u32,\n");
+		reload(context, &modified(context));
+		assert!(crate::inference::function(context, &function_path("total")).is_err());
+		assert!(crate::inference::function(context, &utility("constant")).is_ok());
+	}
+}
@@ -0,0 +1,228 @@
+use std::fmt;
+use std::iter::Peekable;
+use std::ops::Index;
+use std::str::CharIndices;
+
+const NULL: Json = Json::Null;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+	Null,
+	Truth(bool),
+	Number(f64),
+	String(String),
+	Array(Vec<Json>),
+	Object(Vec<(String, Json)>),
+}
+
+impl Json {
+	pub fn object<I, S>(fields: I) -> Self where I: IntoIterator<Item=(S, Json)>, S: Into<String> {
+		Json::Object(fields.into_iter().map(|(field, value)| (field.into(), value)).collect())
+	}
+
+	pub fn string<S>(string: S) -> Self where S: Into<String> {
+		Json::String(string.into())
+	}
+
+	pub fn as_str(&self) -> Option<&str> {
+		match self {
+			Json::String(string) => Some(string),
+			_ => None,
+		}
+	}
+
+	pub fn as_usize(&self) -> Option<usize> {
+		match self {
+			Json::Number(number) if *number >= 0.0 => Some(*number as usize),
+			_ => None,
+		}
+	}
+
+	/// Parses a string as a single JSON value.
+	pub fn parse(string: &str) -> Option<Self> {
+		let characters = &mut string.char_indices().peekable();
+		let value = value(string, characters)?;
+		skip_whitespace(characters);
+		match characters.peek() {
+			None => Some(value),
+			Some(_) => None,
+		}
+	}
+}
+
+impl Index<&str> for Json {
+	type Output = Json;
+
+	fn index(&self, index: &str) -> &Self::Output {
+		match self {
+			Json::Object(fields) => fields.iter().find(|(field, _)| field == index)
+				.map(|(_, value)| value).unwrap_or(&NULL),
+			_ => &NULL,
+		}
+	}
+}
+
+impl fmt::Display for Json {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Json::Null => write!(f, "null"),
+			Json::Truth(truth) => write!(f, "{}", truth),
+			Json::Number(number) => write!(f, "{}", number),
+			Json::String(string) => write!(f, "\"{}\"", crate::error::escape(string)),
+			Json::Array(values) => {
+				write!(f, "[")?;
+				values.iter().enumerate().try_for_each(|(index, value)| match index {
+					0 => write!(f, "{}", value),
+					_ => write!(f, ",{}", value),
+				})?;
+				write!(f, "]")
+			}
+			Json::Object(fields) => {
+				write!(f, "{{")?;
+				fields.iter().enumerate().try_for_each(|(index, (field, value))| {
+					let field = crate::error::escape(field);
+					match index {
+						0 => write!(f, "\"{}\":{}", field, value),
+						_ => write!(f, ",\"{}\":{}", field, value),
+					}
+				})?;
+				write!(f, "}}")
+			}
+		}
+	}
+}
+
+type Characters<'a> = Peekable<CharIndices<'a>>;
+
+fn value(string: &str, characters: &mut Characters) -> Option<Json> {
+	skip_whitespace(characters);
+	let &(start, character) = characters.peek()?;
+	Some(match character {
+		'n' => keyword(string, characters, "null", Json::Null)?,
+		't' => keyword(string, characters, "true", Json::Truth(true))?,
+		'f' => keyword(string, characters, "false", Json::Truth(false))?,
+		'"' => Json::String(string_value(characters)?),
+		'[' => {
+			characters.next();
+			let mut values = Vec::new();
+			list(characters, ']', &mut |characters|
+				Some(values.push(value(string, characters)?)))?;
+			Json::Array(values)
+		}
+		'{' => {
+			characters.next();
+			let mut fields = Vec::new();
+			list(characters, '}', &mut |characters| {
+				skip_whitespace(characters);
+				let field = string_value(characters)?;
+				skip_whitespace(characters);
+				match characters.next()? {
+					(_, ':') => Some(fields.push((field, value(string, characters)?))),
+					_ => None,
+				}
+			})?;
+			Json::Object(fields)
+		}
+		_ => {
+			let mut end = start;
+			while let Some(&(index, character)) = characters.peek() {
+				match character.is_ascii_digit() || "+-.eE".contains(character) {
+					false => break,
+					true => end = index + character.len_utf8(),
+				}
+				characters.next();
+			}
+			Json::Number(string[start..end].parse().ok()?)
+		}
+	})
+}
+
+fn keyword(string: &str, characters: &mut Characters, keyword: &str, value: Json) -> Option<Json> {
+	let &(start, _) = characters.peek()?;
+	match string[start..].starts_with(keyword) {
+		false => None,
+		true => {
+			(0..keyword.len()).for_each(|_| { characters.next(); });
+			Some(value)
+		}
+	}
+}
+
+/// Parses comma separated elements until the terminator.
+fn list<F>(characters: &mut Characters, terminator: char, element: &mut F) -> Option<()>
+	where F: FnMut(&mut Characters) -> Option<()> {
+	skip_whitespace(characters);
+	if characters.peek()?.1 == terminator {
+		characters.next();
+		return Some(());
+	}
+
+	loop {
+		element(characters)?;
+		skip_whitespace(characters);
+		match characters.next()? {
+			(_, ',') => continue,
+			(_, character) if character == terminator => return Some(()),
+			_ => return None,
+		}
+	}
+}
+
+fn string_value(characters: &mut Characters) -> Option<String> {
+	match characters.next()? {
+		(_, '"') => (),
+		_ => return None,
+	}
+
+	let mut string = String::new();
+	loop {
+		match characters.next()?.1 {
+			'"' => return Some(string),
+			'\\' => string.push(match characters.next()?.1 {
+				'n' => '\n',
+				't' => '\t',
+				'r' => '\r',
+				'b' => '\u{8}',
+				'f' => '\u{c}',
+				'u' => {
+					let code: String = (0..4).map(|_| characters.next().map(|(_, character)| character))
+						.collect::<Option<_>>()?;
+					let code = u32::from_str_radix(&code, 16).ok()?;
+					std::char::from_u32(code).unwrap_or(std::char::REPLACEMENT_CHARACTER)
+				}
+				character => character,
+			}),
+			character => string.push(character),
+		}
+	}
+}
+
+fn skip_whitespace(characters: &mut Characters) {
+	while characters.peek().map(|(_, character)| character.is_whitespace()).unwrap_or(false) {
+		characters.next();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse() {
+		let json = Json::parse(r#"{"id": 1, "params": {"uri": "file:///a\"b", "list": [true, null]}}"#);
+		let json = json.unwrap();
+		assert_eq!(json["id"], Json::Number(1.0));
+		assert_eq!(json["params"]["uri"].as_str(), Some("file:///a\"b"));
+		assert_eq!(json["params"]["list"], Json::Array(vec![Json::Truth(true), Json::Null]));
+		assert_eq!(json["missing"], Json::Null);
+		assert!(Json::parse("{\"id\": }").is_none());
+	}
+
+	#[test]
+	fn test_display() {
+		let json = Json::object(vec![("a", Json::Array(vec![Json::Number(2.0),
+			Json::string("\n")])), ("b", Json::Null)]);
+		assert_eq!(json.to_string(), r#"{"a":[2,"\n"],"b":null}"#);
+		assert_eq!(Json::parse(&json.to_string()), Some(json));
+	}
+}
@@ -0,0 +1,6 @@
+use json::Json;
+pub use server::server;
+
+mod server;
+mod json;
+mod query;
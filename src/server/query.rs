@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use crate::context::Context;
+use crate::declaration::{FunctionPath, ModulePath};
+use crate::inference::{Instantiation, TypeResolution};
+use crate::node::{Expression, ExpressionKey, NodeFunction};
+use crate::source::SourceKey;
+use crate::span::Span;
+
+/// Finds the innermost expression that contains the byte offset.
+pub fn expression(context: &Context, source: SourceKey, offset: usize)
+                  -> Option<(Arc<NodeFunction>, ExpressionKey)> {
+	context.node_functions.clone().into_iter()
+		.flat_map(|(_, function)| {
+			let expressions = function.context.expressions.iter().enumerate()
+				.filter(|(_, expression)| contains(&expression.span, source, offset))
+				.map(|(index, expression)| (ExpressionKey(index), expression.span))
+				.collect::<Vec<_>>();
+			expressions.into_iter().map(move |(key, span)| (function.clone(), key, span))
+		})
+		.min_by_key(|(_, _, span)| span.byte_end - span.byte_start)
+		.map(|(function, key, _)| (function, key))
+}
+
+/// Provides the declaration span of the function or structure at the byte offset.
+pub fn definition(context: &Context, source: SourceKey, offset: usize) -> Option<Span> {
+	let (function, expression) = expression(context, source, offset)?;
+	match &function.context[&expression].node {
+		Expression::FunctionCall(path, _, _) => context.declarations_function
+			.get(&path.node).map(|declaration| declaration.span()),
+		Expression::Structure(path, _) | Expression::Variant(path, _, _) => context.declarations_structure
+			.get(&path.node).map(|declaration| declaration.span()),
+		Expression::MethodCall(receiver, method, _) => {
+			let context_key = (function.context.function_path.clone(), Instantiation::new());
+			let type_context = context.type_contexts.get(&context_key)?;
+			let mut resolution = &type_context[receiver];
+			while let TypeResolution::Reference(_, inner) = resolution {
+				resolution = inner;
+			}
+
+			match resolution {
+				TypeResolution::Instance(structure, _) => {
+					let path = FunctionPath::method(structure.clone(), method.node.clone());
+					context.declarations_function.get(&path).map(|declaration| declaration.span())
+				}
+				_ => None,
+			}
+		}
+		_ => None,
+	}
+}
+
+/// Provides the type of the expression at the byte offset.
+pub fn hover(context: &Context, source: SourceKey, offset: usize) -> Option<(Span, String)> {
+	let (function, expression) = expression(context, source, offset)?;
+	let context_key = (function.context.function_path.clone(), Instantiation::new());
+	let type_context = context.type_contexts.get(&context_key)?;
+	let span = function.context[&expression].span;
+	Some((span, type_context[&expression].to_string()))
+}
+
+/// Provides the function paths that complete the path ending at the byte offset.
+/// Paths in the module of the source are completed without the module prefix.
+pub fn completion(context: &Context, source: SourceKey, string: &str, offset: usize) -> Vec<String> {
+	let start = string[..offset].char_indices().rev()
+		.take_while(|(_, character)| character.is_alphanumeric() || "_:'".contains(*character))
+		.last().map(|(index, _)| index).unwrap_or(offset);
+	let prefix = &string[start..offset];
+
+	let mut candidates = crate::interface::function_candidates(context, prefix);
+	if let Some(module_path) = module(context, source) {
+		let module_prefix = format!("{}::", module_path);
+		candidates.extend(crate::interface::function_candidates(context,
+			&format!("{}{}", module_prefix, prefix)).into_iter()
+			.map(|candidate| candidate[module_prefix.len()..].to_owned()));
+	}
+
+	candidates.sort();
+	candidates.dedup();
+	candidates
+}
+
+/// Provides the module path of the declarations in a source.
+fn module(context: &Context, source: SourceKey) -> Option<Arc<ModulePath>> {
+	context.declarations_function.clone().into_iter()
+		.find(|(path, declaration)| declaration.source == source &&
+			!context.node_definitions.contains_key(path))
+		.map(|(path, _)| {
+			let FunctionPath(path) = path.as_ref();
+			path.module_path.clone()
+		})
+}
+
+fn contains(span: &Span, source: SourceKey, offset: usize) -> bool {
+	span.source == source && span.byte_start <= offset && offset <= span.byte_end
+}
@@ -0,0 +1,370 @@
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use crate::context::Context;
+use crate::source::SourceKey;
+use crate::span::Span;
+
+use super::Json;
+
+type ServerResult = Result<(), Box<dyn std::error::Error>>;
+
+const METHOD_NOT_FOUND: f64 = -32601.0;
+const SEVERITY_ERROR: f64 = 1.0;
+const COMPLETION_FUNCTION: f64 = 3.0;
+
+#[derive(Debug)]
+struct Server {
+	root: PathBuf,
+	context: Context,
+	diagnostics: HashMap<String, Vec<Json>>,
+	published: HashSet<String>,
+}
+
+/// Runs a language server over the standard input and output.
+pub fn server(root: PathBuf) -> ServerResult {
+	let (input, output) = (io::stdin(), io::stdout());
+	serve(root, &mut input.lock(), &mut output.lock())
+}
+
+fn serve(root: PathBuf, input: &mut dyn BufRead, output: &mut dyn Write) -> ServerResult {
+	let mut server = Server {
+		root,
+		context: Context::default(),
+		diagnostics: HashMap::new(),
+		published: HashSet::new(),
+	};
+
+	server.compile();
+	while let Some(message) = receive(input)? {
+		let (identifier, parameters) = (&message["id"], &message["params"]);
+		match message["method"].as_str().unwrap_or_default() {
+			"exit" => return Ok(()),
+			"initialize" => respond(output, identifier, capabilities())?,
+			"shutdown" => respond(output, identifier, Json::Null)?,
+			"initialized" | "textDocument/didOpen" => server.publish(output)?,
+			"textDocument/didSave" => {
+				server.compile();
+				server.publish(output)?;
+			}
+			"textDocument/definition" => respond(output, identifier, server.definition(parameters))?,
+			"textDocument/hover" => respond(output, identifier, server.hover(parameters))?,
+			"textDocument/completion" => respond(output, identifier, server.completion(parameters))?,
+			method => if identifier != &Json::Null {
+				let error = Json::object(vec![
+					("code", Json::Number(METHOD_NOT_FOUND)),
+					("message", Json::string(format!("Method: {}, is not supported", method))),
+				]);
+
+				send(output, Json::object(vec![("jsonrpc", Json::string("2.0")),
+					("id", identifier.clone()), ("error", error)]))?;
+			}
+		}
+	}
+	Ok(())
+}
+
+impl Server {
+	/// Reconstructs the context from the root module and collects diagnostics.
+	fn compile(&mut self) {
+		self.context = Context::default();
+		crate::declaration::module_root(&self.context, self.root.clone());
+		crate::node::load_definitions(&self.context);
+		crate::interface::check(&self.context);
+
+		self.diagnostics.clear();
+		let errors = std::mem::replace(self.context.errors.write().as_mut(), Vec::new());
+		for diagnostic in errors {
+			let span = diagnostic.error.span;
+			if span.source == SourceKey::INTERNAL {
+				continue;
+			}
+
+			let source = span.source.get(&self.context);
+			let message = std::iter::once(diagnostic.error.node.to_string())
+				.chain(diagnostic.notes.iter().cloned()).collect::<Vec<_>>().join("\n");
+			let diagnostic = Json::object(vec![
+				("range", self.range(span)),
+				("severity", Json::Number(SEVERITY_ERROR)),
+				("source", Json::string("lexica")),
+				("code", Json::string(diagnostic.error.node.category())),
+				("message", Json::String(message)),
+			]);
+			self.diagnostics.entry(uri(&source.path)).or_default().push(diagnostic);
+		}
+	}
+
+	/// Sends the diagnostics of every source and clears diagnostics that have been resolved.
+	fn publish(&mut self, output: &mut dyn Write) -> ServerResult {
+		let published = std::mem::replace(&mut self.published, HashSet::new());
+		for uri in published.iter().filter(|uri| !self.diagnostics.contains_key(*uri)) {
+			notify(output, "textDocument/publishDiagnostics", Json::object(vec![
+				("uri", Json::string(uri.as_str())), ("diagnostics", Json::Array(Vec::new()))]))?;
+		}
+
+		for (uri, diagnostics) in &self.diagnostics {
+			notify(output, "textDocument/publishDiagnostics", Json::object(vec![
+				("uri", Json::string(uri.as_str())),
+				("diagnostics", Json::Array(diagnostics.clone())),
+			]))?;
+			self.published.insert(uri.clone());
+		}
+		Ok(())
+	}
+
+	fn definition(&self, parameters: &Json) -> Json {
+		let (source, offset) = match self.position(parameters) {
+			Some(position) => position,
+			None => return Json::Null,
+		};
+
+		super::query::definition(&self.context, source, offset).map(|span| {
+			let path = &span.source.get(&self.context).path;
+			Json::object(vec![("uri", Json::String(uri(path))), ("range", self.range(span))])
+		}).unwrap_or(Json::Null)
+	}
+
+	fn hover(&self, parameters: &Json) -> Json {
+		let (source, offset) = match self.position(parameters) {
+			Some(position) => position,
+			None => return Json::Null,
+		};
+
+		super::query::hover(&self.context, source, offset).map(|(span, string)| {
+			let contents = Json::object(vec![("kind", Json::string("plaintext")),
+				("value", Json::String(string))]);
+			Json::object(vec![("contents", contents), ("range", self.range(span))])
+		}).unwrap_or(Json::Null)
+	}
+
+	fn completion(&self, parameters: &Json) -> Json {
+		let (source, offset) = match self.position(parameters) {
+			Some(position) => position,
+			None => return Json::Array(Vec::new()),
+		};
+
+		let source_file = source.get(&self.context);
+		let string = source_file.read_string().unwrap_or_default();
+		Json::Array(super::query::completion(&self.context, source, string, offset)
+			.into_iter().map(|candidate| Json::object(vec![
+				("label", Json::String(candidate)),
+				("kind", Json::Number(COMPLETION_FUNCTION)),
+			])).collect())
+	}
+
+	/// Converts text document position parameters into a source and byte offset.
+	fn position(&self, parameters: &Json) -> Option<(SourceKey, usize)> {
+		let path = path(parameters["textDocument"]["uri"].as_str()?)?;
+		let path = path.canonicalize().ok()?;
+		let source = self.context.source_keys.clone().into_iter()
+			.find(|(source_path, _)| source_path.canonicalize().ok().as_ref() == Some(&path))
+			.map(|(_, source)| source)?;
+
+		let string = source.get(&self.context);
+		let string = string.read_string().ok()?;
+		let position = &parameters["position"];
+		let line = position["line"].as_usize()?;
+		let character = position["character"].as_usize()?;
+
+		let line_offset = match line {
+			0 => 0,
+			_ => string.match_indices('\n').nth(line - 1)?.0 + 1,
+		};
+
+		let mut units = 0;
+		let offset = string[line_offset..].char_indices()
+			.take_while(|(_, character)| character != &'\n')
+			.find(|(_, code)| {
+				let is_target = units >= character;
+				units += code.len_utf16();
+				is_target
+			}).map(|(index, _)| line_offset + index);
+		Some((source, offset.unwrap_or_else(|| line_offset + string[line_offset..]
+			.find('\n').unwrap_or(string.len() - line_offset))))
+	}
+
+	fn range(&self, span: Span) -> Json {
+		let source = span.source.get(&self.context);
+		let string = source.read_string().unwrap_or_default();
+		Json::object(vec![
+			("start", position(string, span.byte_start)),
+			("end", position(string, span.byte_end)),
+		])
+	}
+}
+
+/// Converts a byte offset into a zero indexed line and UTF-16 character position.
+fn position(string: &str, byte_offset: usize) -> Json {
+	let byte_offset = byte_offset.min(string.len());
+	let prefix = &string[..byte_offset];
+	let line_offset = prefix.rfind('\n').map(|offset| offset + 1).unwrap_or(0);
+	let character: usize = prefix[line_offset..].chars().map(char::len_utf16).sum();
+	Json::object(vec![
+		("line", Json::Number(prefix.matches('\n').count() as f64)),
+		("character", Json::Number(character as f64)),
+	])
+}
+
+fn capabilities() -> Json {
+	let synchronization = Json::object(vec![
+		("openClose", Json::Truth(true)),
+		("change", Json::Number(0.0)),
+		("save", Json::Truth(true)),
+	]);
+
+	let completion = Json::object(vec![("triggerCharacters",
+		Json::Array(vec![Json::string(":")]))]);
+	Json::object(vec![("capabilities", Json::object(vec![
+		("textDocumentSync", synchronization),
+		("definitionProvider", Json::Truth(true)),
+		("hoverProvider", Json::Truth(true)),
+		("completionProvider", completion),
+	]))])
+}
+
+fn uri(path: &Path) -> String {
+	let path = path.canonicalize().unwrap_or_else(|_| path.to_owned());
+	let path = path.to_string_lossy();
+	let mut uri = "file://".to_owned();
+	for byte in path.bytes() {
+		match byte.is_ascii_alphanumeric() || b"/-_.~".contains(&byte) {
+			true => uri.push(byte as char),
+			false => uri.push_str(&format!("%{:02X}", byte)),
+		}
+	}
+	uri
+}
+
+fn path(uri: &str) -> Option<PathBuf> {
+	const SCHEME: &str = "file://";
+	if !uri.starts_with(SCHEME) { return None; }
+	let uri = uri[SCHEME.len()..].as_bytes();
+	let mut bytes = Vec::new();
+	let mut index = 0;
+	while index < uri.len() {
+		match uri[index] {
+			b'%' => {
+				let code = std::str::from_utf8(uri.get(index + 1..index + 3)?).ok()?;
+				bytes.push(u8::from_str_radix(code, 16).ok()?);
+				index += 3;
+			}
+			byte => {
+				bytes.push(byte);
+				index += 1;
+			}
+		}
+	}
+	Some(PathBuf::from(String::from_utf8(bytes).ok()?))
+}
+
+/// Reads the next message. Returns `None` if the input has ended.
+fn receive(input: &mut dyn BufRead) -> Result<Option<Json>, Box<dyn std::error::Error>> {
+	loop {
+		let mut length = None;
+		loop {
+			let mut line = String::new();
+			if input.read_line(&mut line)? == 0 {
+				return Ok(None);
+			}
+
+			let line = line.trim();
+			if line.is_empty() {
+				break;
+			}
+
+			const LENGTH_HEADER: &str = "Content-Length:";
+			if line.starts_with(LENGTH_HEADER) {
+				length = Some(line[LENGTH_HEADER.len()..].trim().parse::<usize>()?);
+			}
+		}
+
+		if let Some(length) = length {
+			let mut buffer = vec![0; length];
+			input.read_exact(&mut buffer)?;
+			if let Some(message) = Json::parse(&String::from_utf8_lossy(&buffer)) {
+				return Ok(Some(message));
+			}
+		}
+	}
+}
+
+fn respond(output: &mut dyn Write, identifier: &Json, result: Json) -> ServerResult {
+	send(output, Json::object(vec![("jsonrpc", Json::string("2.0")),
+		("id", identifier.clone()), ("result", result)]))
+}
+
+fn notify(output: &mut dyn Write, method: &str, parameters: Json) -> ServerResult {
+	send(output, Json::object(vec![("jsonrpc", Json::string("2.0")),
+		("method", Json::string(method)), ("params", parameters)]))
+}
+
+fn send(output: &mut dyn Write, message: Json) -> ServerResult {
+	let message = message.to_string();
+	write!(output, "Content-Length: {}\r\n\r\n{}", message.len(), message)?;
+	Ok(output.flush()?)
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io::Read;
+
+	use crate::source::TemporarySource;
+
+	use super::*;
+
+	fn message(method: &str, parameters: Json) -> String {
+		let message = Json::object(vec![("jsonrpc", Json::string("2.0")),
+			("method", Json::string(method)), ("params", parameters)]).to_string();
+		format!("Content-Length: {}\r\n\r\n{}", message.len(), message)
+	}
+
+	fn diagnostics(message: &Json) -> &[Json] {
+		assert_eq!(message["method"].as_str(), Some("textDocument/publishDiagnostics"));
+		match &message["params"]["diagnostics"] {
+			Json::Array(diagnostics) => diagnostics,
+			other => panic!("Expected diagnostics array, instead got: {}", other),
+		}
+	}
+
+	/// Rewrites the source when it is first read so that the
+	/// server compiles the original source before the edit.
+	struct Edit<'a>(&'a TemporarySource, Option<&'a str>, &'a [u8]);
+
+	impl Read for Edit<'_> {
+		fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+			if let Some(string) = self.1.take() {
+				self.0.write(string);
+			}
+			self.2.read(buffer)
+		}
+	}
+
+	#[test]
+	fn test_diagnostics() {
+		let source = TemporarySource::new("fn f() -> u32:\n\tg()\n");
+		let document = Json::object(vec![("textDocument",
+			Json::object(vec![("uri", Json::String(uri(&source.0)))]))]);
+
+		let opened = message("textDocument/didOpen", document.clone());
+		let saved = message("textDocument/didSave", document) + &message("exit", Json::Null);
+		let edit = Edit(&source, Some("fn f() -> u32:\n\t1\n"), saved.as_bytes());
+		let input = &mut io::BufReader::new(opened.as_bytes().chain(edit));
+		let mut output = Vec::new();
+		serve(source.0.clone(), input, &mut output).unwrap();
+
+		let output = &mut output.as_slice();
+		let messages: Vec<_> = std::iter::from_fn(|| receive(output).unwrap()).collect();
+		assert_eq!(messages.len(), 2);
+
+		let diagnostic = &diagnostics(&messages[0])[0];
+		assert_eq!(messages[0]["params"]["uri"], Json::String(uri(&source.0)));
+		assert_eq!(diagnostic["severity"], Json::Number(SEVERITY_ERROR));
+		assert_eq!(diagnostic["range"]["start"]["line"], Json::Number(1.0));
+		assert_eq!(diagnostic["range"]["start"]["character"], Json::Number(1.0));
+		assert!(diagnostic["message"].as_str().unwrap().contains("has no matching resolutions"));
+
+		assert!(diagnostics(&messages[1]).is_empty());
+		assert_eq!(messages[1]["params"]["uri"], Json::String(uri(&source.0)));
+	}
+}
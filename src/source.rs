@@ -87,10 +87,104 @@ pub fn source_key(context: &Context, path: &Arc<PathBuf>) -> Result<SourceKey, S
 	let mut file = File::open(path.deref()).map_err(|_| SourceError::MissingFile(path.clone()))?;
 	file.read_to_end(&mut data).map_err(|error| SourceError::ReadFailure(path.clone(), error))?;
 
-	let source_key = SourceKey(context.source_keys.len());
+	let source_key = SourceKey(context.sources.len());
 	context.source_keys.insert(path.clone(), source_key);
 
 	let source = Arc::new(Source { path: path.clone(), data });
 	context.sources.insert(source_key, source);
 	Ok(source_key)
 }
+
+/// A source in a unique file in the temporary directory. The file is removed when dropped.
+#[cfg(test)]
+#[derive(Debug)]
+pub struct TemporarySource(pub PathBuf);
+
+#[cfg(test)]
+impl TemporarySource {
+	pub fn new(string: &str) -> Self {
+		use std::sync::atomic::{AtomicUsize, Ordering};
+		static SOURCE_INDEX: AtomicUsize = AtomicUsize::new(0);
+
+		let index = SOURCE_INDEX.fetch_add(1, Ordering::SeqCst);
+		let path = std::env::temp_dir().join(format!("lexica-{}-{}.lx", std::process::id(), index));
+		let source = TemporarySource(path);
+		source.write(string);
+		source
+	}
+
+	pub fn write(&self, string: &str) {
+		std::fs::write(&self.0, string).unwrap();
+	}
+
+	/// Creates a context with the source as the root module.
+	pub fn context(&self) -> Context {
+		let context = Context::default();
+		crate::declaration::module_root(&context, self.0.clone());
+		crate::node::load_definitions(&context);
+		context
+	}
+}
+
+#[cfg(test)]
+impl Drop for TemporarySource {
+	fn drop(&mut self) {
+		let _ = std::fs::remove_file(&self.0);
+	}
+}
+
+/// A directory of sources in the temporary directory that is removed when dropped.
+#[cfg(test)]
+#[derive(Debug)]
+pub struct TemporaryDirectory(pub PathBuf);
+
+#[cfg(test)]
+impl TemporaryDirectory {
+	pub fn new(files: &[(&str, &str)]) -> Self {
+		use std::sync::atomic::{AtomicUsize, Ordering};
+		static DIRECTORY_INDEX: AtomicUsize = AtomicUsize::new(0);
+
+		let index = DIRECTORY_INDEX.fetch_add(1, Ordering::SeqCst);
+		let directory = format!("lexica-{}-directory-{}", std::process::id(), index);
+		let directory = TemporaryDirectory(std::env::temp_dir().join(directory));
+		std::fs::create_dir_all(&directory.0).unwrap();
+		files.iter().for_each(|(file, string)| directory.write(file, string));
+		directory
+	}
+
+	pub fn write(&self, file: &str, string: &str) {
+		let path = self.0.join(file);
+		std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+		std::fs::write(path, string).unwrap();
+	}
+
+	/// Creates a context with the `main.lx` of the directory as the root module.
+	pub fn context(&self) -> Context {
+		let context = Context::default();
+		crate::declaration::module_root(&context, self.0.join("main.lx"));
+		crate::node::load_definitions(&context);
+		context
+	}
+}
+
+#[cfg(test)]
+impl Drop for TemporaryDirectory {
+	fn drop(&mut self) {
+		let _ = std::fs::remove_dir_all(&self.0);
+	}
+}
+
+/// Creates a context with a root module that contains the given source.
+/// The source file is removed once the definitions are loaded.
+#[cfg(test)]
+pub fn context(string: &str) -> Context {
+	TemporarySource::new(string).context()
+}
+
+/// Creates a path to a function in the root module.
+#[cfg(test)]
+pub fn function_path(identifier: &str) -> crate::span::Spanned<Arc<crate::declaration::FunctionPath>> {
+	use crate::declaration::{DeclarationPath, FunctionPath, ModulePath};
+	let path = DeclarationPath { module_path: ModulePath::root(), identifier: identifier.into() };
+	crate::span::Spanned::new(Arc::new(FunctionPath(path)), crate::span::Span::INTERNAL)
+}